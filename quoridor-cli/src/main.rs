@@ -1,7 +1,7 @@
 // --- File: quoridor-project/quoridor-cli/src/main.rs ---
 
-use quoridor_core::{Quoridor, Player, Strategy}; // Import from core crate
-use quoridor_core::strategy::{ self, RandomStrategy, ShortestPathStrategy, MCTSStrategy, MinimaxStrategy, DefensiveStrategy, AdaptiveStrategy, BalancedStrategy, MirrorStrategy, SimulatedAnnealingStrategy }; // Import specific strategies
+use quoridor_core::{GameRecord, Quoridor, Player, Strategy}; // Import from core crate
+use quoridor_core::strategy::{ self, ChooseResult, RandomStrategy, ShortestPathStrategy, MCTSStrategy, MinimaxStrategy, DefensiveStrategy, GreedyBlockStrategy, AdaptiveStrategy, BalancedStrategy, MirrorStrategy, SimulatedAnnealingStrategy, TimeLimited, EvalWeights, OpeningDeviation }; // Import specific strategies
 use quoridor_core::openings; // Import the openings module
 use chrono; // Timestamped files
 
@@ -12,6 +12,7 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
 use std::env;
+use std::collections::{HashMap, HashSet};
 
 use csv::Writer;
 use rand::prelude::*;
@@ -19,7 +20,7 @@ use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
 // --- Tournament Structures ---
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TournamentResult {
     strategy1: String,
     strategy2: String,
@@ -28,8 +29,73 @@ pub struct TournamentResult {
     strategy2_wins: usize,
     draws: usize,
     games_played: usize, // Track total games for accurate win %
+    distinct_games: usize, // Games that no earlier game in the match repeated move for move with the same seating
+    strategy1_think_time: Duration, // Total time spent in choose_move across all games
+    strategy2_think_time: Duration,
+    strategy1_timeouts: usize, // Games lost by exceeding the per-move time limit
+    strategy2_timeouts: usize,
+    strategy1_wins_as_p1: usize, // Wins while seated as Player1 (the rest came as Player2)
+    strategy2_wins_as_p1: usize,
+    swaps: usize, // Games where the second player took over the first move (pie rule)
+    strategy1_settings: String, // `Strategy::describe` of the strategy as built for the match's first game
+    strategy2_settings: String,
+}
+
+impl TournamentResult {
+    /// Adds up one pairing's per-opening results into a single record with
+    /// the opening "All". Results for other pairings are left out.
+    fn combined(strategy1: &str, strategy2: &str, results: &[TournamentResult]) -> TournamentResult {
+        let mut total = TournamentResult {
+            strategy1: strategy1.to_string(),
+            strategy2: strategy2.to_string(),
+            opening: "All".to_string(),
+            ..Default::default()
+        };
+        for result in results.iter().filter(|r| r.strategy1 == strategy1 && r.strategy2 == strategy2) {
+            if total.strategy1_settings.is_empty() {
+                total.strategy1_settings = result.strategy1_settings.clone();
+                total.strategy2_settings = result.strategy2_settings.clone();
+            }
+            total.strategy1_wins += result.strategy1_wins;
+            total.strategy2_wins += result.strategy2_wins;
+            total.draws += result.draws;
+            total.games_played += result.games_played;
+            total.distinct_games += result.distinct_games;
+            total.strategy1_think_time += result.strategy1_think_time;
+            total.strategy2_think_time += result.strategy2_think_time;
+            total.strategy1_timeouts += result.strategy1_timeouts;
+            total.strategy2_timeouts += result.strategy2_timeouts;
+            total.strategy1_wins_as_p1 += result.strategy1_wins_as_p1;
+            total.strategy2_wins_as_p1 += result.strategy2_wins_as_p1;
+            total.swaps += result.swaps;
+        }
+        total
+    }
+}
+
+/// How a single game ended, from the point of view of the two seats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameOutcome {
+    winner: Option<Player>,     // None for a draw (move limit reached)
+    timed_out: Option<Player>,  // Player who lost on time, if any
+    think_time: [Duration; 2],  // Total thinking time for the strategies that started as Player1 and Player2
+    transcript: u64,            // GameRecord::transcript_hash of the moves played
+    swapped: bool,              // The pie rule swapped the strategies after the first move
+}
+
+/// Cumulative standing of one strategy in a Swiss-system tournament.
+#[derive(Debug, Clone)]
+pub struct SwissStanding {
+    strategy: String,
+    score: f64,             // Game points: 1 per win, 0.5 per draw, a full match's worth per bye
+    opponents: Vec<String>, // Opponents faced, in round order
+    byes: usize,
 }
 
+/// Extra time a strategy may use beyond the per-move limit before it forfeits.
+/// Searches only check their deadline periodically, so a small overrun is normal.
+const TIMEOUT_GRACE: Duration = Duration::from_millis(100);
+
 pub struct Tournament {
     board_size: usize,
     walls: usize,
@@ -38,8 +104,73 @@ pub struct Tournament {
     // Add time limits or simulation counts if strategies need them
     mcts_simulations: usize,
     mcts_time_limit_secs: Option<f64>,
+    // Per-move time control: searches are told to respect it, and a move
+    // exceeding it (plus TIMEOUT_GRACE) loses the game
+    move_time_limit: Option<Duration>,
+    // Optional state string every game starts from instead of the standard setup
+    start_state: Option<String>,
+    // Optional match filters applied before a round-robin run (see build_match_configs)
+    opening_filter: Option<Vec<String>>,
+    only_strategy: Option<String>,
+    // Optional per-move budget for the TimeLimited decorator: slow moves are
+    // logged (not forfeited), to flag slow strategies
+    slow_move_warning: Option<Duration>,
+    // Scale MCTS simulation counts by board area (see mcts_simulations_for_board)
+    scale_mcts_by_board: bool,
+    // Optional hook called after every ply of play_game with the ply number
+    progress_callback: Option<Box<dyn Fn(usize) + Send + Sync>>,
+    // Also play every opening with its sides swapped (see with_symmetric_openings)
+    symmetric_openings: bool,
+    // Leave repeated games out of match results (see with_skip_duplicate_games)
+    skip_duplicate_games: bool,
+    // Let the second player take over the first move (see with_pie_rule)
+    pie_rule: bool,
+    // What strategies do when their opening line hits an illegal move
+    opening_mode: OpeningDeviation,
 }
 
+/// One scheduled match: (strategy 1, strategy 2, opening, display).
+type MatchConfig = (String, String, String, bool);
+
+/// Rebuilds both strategies after a pie-rule swap, given the first move:
+/// (the strategy that started as Player2, now Player1; the one that started
+/// as Player1, now Player2), each with the opening line of its new seat.
+type Reseat<'a> = dyn Fn(&str) -> (Box<dyn Strategy>, Box<dyn Strategy>) + 'a;
+
+// --- Configuration ---
+/// Strategies in the round-robin run by `run_tournament_parallel`.
+const TOURNAMENT_STRATEGIES: &[&str] = &[
+    // Basic
+    "Random",
+    "ShortestPath",
+    // Intermediate
+    "Defensive",
+    "GreedyBlock",
+    "Balanced",
+    "Adaptive",
+    "Mirror",
+    // Advanced / From Papers
+    "SimulatedAnnealing0.5", // From paper's experiments
+    "SimulatedAnnealing1.0",
+    "Minimax1", // Low depth for speed
+    "Minimax2", // Reference depth from paper
+    "Minimax2-C1", // Paper's simpler heuristics, to compare against C3
+    "Minimax2-C2",
+    // MCTS (adjust simulation counts/time as needed)
+    "MCTS5sec",
+    "MCTS1sec", // 60k in paper's experiments
+];
+
+/// Openings every pairing plays.
+const TOURNAMENT_OPENINGS: &[&str] = &[
+    "No Opening",
+    //"Sidewall Opening",
+    "Standard Opening",
+    //"Shiller Opening",
+    //"Ala Opening",
+];
+// --- End Configuration ---
+
 impl Tournament {
     pub fn new(board_size: usize, walls: usize, games_per_match: usize) -> Self {
         Tournament {
@@ -49,6 +180,17 @@ impl Tournament {
             results: Vec::new(),
             mcts_simulations: 10000, // Default simulations
             mcts_time_limit_secs: None, // Default no time limit
+            move_time_limit: None, // Default no time control
+            start_state: None, // Default standard starting position
+            opening_filter: None, // Default all configured openings
+            only_strategy: None, // Default every pairing
+            slow_move_warning: None, // Default no slow-move logging
+            scale_mcts_by_board: false, // Default same simulation count on every board
+            progress_callback: None, // Default no per-ply feedback
+            symmetric_openings: false, // Default openings as defined for each seat
+            skip_duplicate_games: false, // Default every game counts, repeats included
+            pie_rule: false, // Default seats stay as dealt
+            opening_mode: OpeningDeviation::default(), // Default step past an illegal move and carry on
         }
     }
 
@@ -61,29 +203,198 @@ impl Tournament {
         self.mcts_time_limit_secs = Some(seconds);
         self
     }
+    /// Sets a per-move time budget for every strategy. Time-aware strategies
+    /// (MCTS, Minimax via iterative deepening) stop searching at the budget;
+    /// any move taking longer than the budget plus a small grace loses the game.
+    pub fn set_move_time_limit(mut self, seconds: f64) -> Self {
+        self.move_time_limit = if seconds > 0.0 { Some(Duration::from_secs_f64(seconds)) } else { None };
+        self
+    }
+    /// Starts every game from the given state string (see `Quoridor::new`) instead
+    /// of the standard setup. Openings still apply on top, but any opening move
+    /// that is illegal in that position is skipped.
+    pub fn with_start_state(mut self, state: String) -> Self {
+        self.start_state = Some(state);
+        self
+    }
+    /// Restricts a round-robin run to the named openings.
+    pub fn with_openings(mut self, openings: Vec<String>) -> Self {
+        self.opening_filter = Some(openings);
+        self
+    }
+    /// Restricts a round-robin run to matches involving the named strategy.
+    pub fn with_only_strategy(mut self, strategy: String) -> Self {
+        self.only_strategy = Some(strategy);
+        self
+    }
+    /// Wraps every strategy in `TimeLimited`, warning whenever a move takes longer than `seconds`.
+    pub fn with_slow_move_warning(mut self, seconds: f64) -> Self {
+        self.slow_move_warning = if seconds > 0.0 { Some(Duration::from_secs_f64(seconds)) } else { None };
+        self
+    }
+
+    /// Scales MCTS simulation counts by board area, so an "MCTS10000" is about
+    /// as strong relative to the game on 5x5 as on 9x9 (see `mcts_simulations_for_board`).
+    pub fn with_mcts_board_scaling(mut self, enabled: bool) -> Self {
+        self.scale_mcts_by_board = enabled;
+        self
+    }
+
+    /// Calls `callback` after every ply played by `play_game` (and so `run_match`)
+    /// with the game's ply number, starting at 1, e.g. to drive a spinner or
+    /// print the board during long single matches. Parallel tournament threads
+    /// get their own configuration and do not call it.
+    pub fn with_progress_callback<F: Fn(usize) + Send + Sync + 'static>(mut self, callback: F) -> Self {
+        self.progress_callback = Some(Box::new(callback));
+        self
+    }
+
+    /// Plays every opening from both sides: in alternate pairs of games each
+    /// seat plays the *other* seat's line, reflected to its own side of the
+    /// board (see `openings::mirrored_opening_moves`). A line that favours one
+    /// side is then played by both players from both seats, so its bias cancels
+    /// out of the match totals. With a multiple of 4 games per match every
+    /// strategy plays each line from each seat equally often.
+    pub fn with_symmetric_openings(mut self, enabled: bool) -> Self {
+        self.symmetric_openings = enabled;
+        self
+    }
+
+    /// Leaves games that repeat an earlier game of the same match move for
+    /// move, with the same strategy in each seat, out of the results, so
+    /// deterministic pairings don't count one game many times. Off by
+    /// default; `distinct_games` is tracked either way.
+    pub fn with_skip_duplicate_games(mut self, enabled: bool) -> Self {
+        self.skip_duplicate_games = enabled;
+        self
+    }
+
+    /// Pie rule: after Player1's first move, the strategy seated as Player2 is
+    /// asked (`Strategy::should_swap`) whether it wants to take that move over.
+    /// If so the two strategies change seats for the rest of the game, which
+    /// discourages first moves that are too strong. Off by default; swaps are
+    /// counted in `TournamentResult::swaps`.
+    pub fn with_pie_rule(mut self, enabled: bool) -> Self {
+        self.pie_rule = enabled;
+        self
+    }
+
+    /// Sets what every strategy does when the next move of its opening line
+    /// is illegal (see `OpeningDeviation`); `Report` logs each deviation.
+    pub fn with_opening_mode(mut self, mode: OpeningDeviation) -> Self {
+        self.opening_mode = mode;
+        self
+    }
+
+    /// Simulation count actually used for a nominal MCTS count on this board.
+    /// With board scaling on, the nominal count applies to the standard 9x9 board
+    /// and is multiplied by `board_size² / 81` (rounded, at least 1), e.g.
+    /// 10000 becomes 3086 on 5x5. Without scaling the count is unchanged.
+    fn mcts_simulations_for_board(&self, simulations: usize) -> usize {
+        if !self.scale_mcts_by_board { return simulations; }
+        let area_ratio = (self.board_size * self.board_size) as f64 / 81.0;
+        ((simulations as f64 * area_ratio).round() as usize).max(1)
+    }
+
+    /// Lists every pairing of distinct strategies for each opening, keeping only
+    /// the openings and strategy selected by `with_openings`/`with_only_strategy`.
+    fn build_match_configs(&self, strategy_names: &[&str], opening_names: &[&str], display: bool) -> Vec<MatchConfig> {
+        let opening_selected = |opening: &str| self.opening_filter.as_ref().is_none_or(|filter| filter.iter().any(|o| o == opening));
+        let pair_selected = |a: &str, b: &str| self.only_strategy.as_deref().is_none_or(|only| a == only || b == only);
+
+        let mut match_configs = Vec::new();
+        for opening_name in opening_names.iter().filter(|o| opening_selected(o)) {
+            for i in 0..strategy_names.len() {
+                for j in (i + 1)..strategy_names.len() { // Avoid self-play and duplicate pairs
+                    if !pair_selected(strategy_names[i], strategy_names[j]) { continue; }
+                    match_configs.push((
+                        strategy_names[i].to_string(),
+                        strategy_names[j].to_string(),
+                        opening_name.to_string(),
+                        display,
+                    ));
+                }
+            }
+        }
+        match_configs
+    }
 
 
     /// Creates a strategy instance based on name and player.
     /// This centralizes strategy creation.
     pub fn create_strategy(&self, strategy_name: &str, opening_name: &str, player: Player) -> Box<dyn Strategy> {
-        let opening_moves = openings::get_opening_moves(opening_name, player);
+        self.create_strategy_with_moves(strategy_name, opening_name, openings::get_opening_moves(opening_name, player))
+    }
+
+    /// One seat's opening line, or the other seat's line reflected onto it
+    /// when `mirrored` (see `with_symmetric_openings`).
+    fn opening_line(&self, opening_name: &str, player: Player, mirrored: bool) -> Vec<String> {
+        if mirrored {
+            openings::mirrored_opening_moves(opening_name, player, self.board_size)
+        } else {
+            openings::get_opening_moves(opening_name, player)
+        }
+    }
+
+    /// Both strategies rebuilt for their seats after a pie-rule swap (see
+    /// `Reseat`). The swapper continues Player1's line after `first_move`, or
+    /// gets no line if that move already left it; the other side starts
+    /// Player2's line from the beginning.
+    fn reseated_strategies(
+        &self,
+        first_strategy_type: &str,
+        second_strategy_type: &str,
+        opening_name: &str,
+        mirrored: bool,
+        first_move: &str,
+    ) -> (Box<dyn Strategy>, Box<dyn Strategy>) {
+        let mut p1_line = self.opening_line(opening_name, Player::Player1, mirrored);
+        if p1_line.first().is_some_and(|book_move| book_move == first_move) {
+            p1_line.remove(0);
+        } else {
+            p1_line.clear();
+        }
+        (
+            self.create_strategy_with_moves(second_strategy_type, opening_name, p1_line),
+            self.create_strategy_with_moves(first_strategy_type, opening_name, self.opening_line(opening_name, Player::Player2, mirrored)),
+        )
+    }
 
-        match strategy_name {
+    /// Like `create_strategy`, but with the opening line given explicitly
+    /// (e.g. a mirrored line for symmetric openings).
+    fn create_strategy_with_moves(&self, strategy_name: &str, opening_name: &str, opening_moves: Vec<String>) -> Box<dyn Strategy> {
+        // A heuristic preset suffix selects the evaluation, e.g. "Minimax2-C1"
+        let (strategy_name, preset) = split_preset(strategy_name);
+
+        let mut strategy: Box<dyn Strategy> = match strategy_name {
             "Random" => Box::new(RandomStrategy::new(opening_name, opening_moves)),
             "ShortestPath" => Box::new(ShortestPathStrategy::new(opening_name, opening_moves)),
-            "Defensive" => Box::new(DefensiveStrategy::new(opening_name, opening_moves, 0.7)),
+            "Defensive" => Box::new(DefensiveStrategy::new(opening_name, opening_moves, 0.7, 0.0)),
+            "GreedyBlock" => Box::new(GreedyBlockStrategy::new(opening_name, opening_moves)),
             "Balanced" => Box::new(BalancedStrategy::new(opening_name, opening_moves, 0.5)),
             "Adaptive" => Box::new(AdaptiveStrategy::new(opening_name, opening_moves)),
             "Mirror" => Box::new(MirrorStrategy::new(opening_name, opening_moves)),
             s if s.starts_with("SimulatedAnnealing") => {
                 let factor_str = s.trim_start_matches("SimulatedAnnealing");
                 let factor = factor_str.parse::<f64>().unwrap_or(1.0);
-                Box::new(SimulatedAnnealingStrategy::new(opening_name, opening_moves, factor))
+                let mut sa_strategy = SimulatedAnnealingStrategy::new(opening_name, opening_moves, factor);
+                if let Some(preset) = preset {
+                    sa_strategy = sa_strategy.with_preset(preset);
+                }
+                Box::new(sa_strategy)
             },
             s if s.starts_with("Minimax") => {
                 let depth_str = s.trim_start_matches("Minimax");
                 let depth = depth_str.parse::<usize>().unwrap_or(1);
-                Box::new(MinimaxStrategy::new(opening_name, opening_moves, depth))
+                let mut minimax_strategy = MinimaxStrategy::new(opening_name, opening_moves, depth);
+                if let Some(preset) = preset {
+                    minimax_strategy = minimax_strategy.with_preset(preset);
+                }
+                // Under time control, search iteratively up to `depth` within the budget
+                if let Some(limit) = self.move_time_limit {
+                    minimax_strategy = minimax_strategy.with_time_limit(limit.as_secs_f64());
+                }
+                Box::new(minimax_strategy)
             },
             s if s.starts_with("MCTS") => {
                 // Handle time-based ("MCTS1sec") or simulation-based ("MCTS60k")
@@ -105,17 +416,19 @@ impl Tournament {
                     //println!("Creating MCTS strategy with simulation limit {} (time limit: {:?})", simulations, time_limit_secs);
                  }
 
+                 // Under time control, never think longer than the per-move budget
+                 if let Some(limit) = self.move_time_limit {
+                     let budget = limit.as_secs_f64();
+                     time_limit_secs = Some(time_limit_secs.map_or(budget, |secs| secs.min(budget)));
+                 }
+
                  // Create the MCTS strategy instance
+                 let simulations = self.mcts_simulations_for_board(simulations);
                  let mut mcts_strategy = MCTSStrategy::new(opening_name, opening_moves, simulations);
 
                  // Apply time limit if specified
                  if let Some(seconds) = time_limit_secs {
-                    // This requires MCTSStrategy to have a method like `with_time_limit`
-                    // For now, we'll assume the simulation count is the primary driver in CLI
-                    // or we modify MCTSStrategy later.
-                    //println!("Note: Time limit {}s requested, but CLI primarily uses simulation count for MCTS.", seconds);
-                    // If MCTSStrategy has `with_time_limit`:
-                    // mcts_strategy = mcts_strategy.with_time_limit(seconds);
+                    mcts_strategy = mcts_strategy.with_time_limit(seconds);
                  }
                  Box::new(mcts_strategy)
             },
@@ -123,6 +436,13 @@ impl Tournament {
                  eprintln!("Warning: Unknown strategy name '{}', defaulting to Random.", strategy_name);
                  Box::new(RandomStrategy::new(opening_name, opening_moves)) // Default
             }
+        };
+        strategy.set_opening_mode(self.opening_mode);
+
+        // Optional decorator: log moves that exceed the slow-move budget
+        match self.slow_move_warning {
+            Some(budget) => Box::new(TimeLimited::new(strategy, budget)),
+            None => strategy,
         }
     }
 
@@ -138,6 +458,16 @@ impl Tournament {
         let mut s1_wins = 0;
         let mut s2_wins = 0;
         let mut draws = 0;
+        let mut s1_think_time = Duration::ZERO;
+        let mut s2_think_time = Duration::ZERO;
+        let mut s1_timeouts = 0;
+        let mut s2_timeouts = 0;
+        let mut s1_wins_as_p1 = 0;
+        let mut s2_wins_as_p1 = 0;
+        let mut games_played = 0;
+        let mut swaps = 0;
+        let mut transcripts = HashSet::new();
+        let mut settings = (String::new(), String::new());
 
         if display {
             println!("-> Running Match: {} vs {} (Opening: {})", strategy1_name, strategy2_name, opening_name);
@@ -152,85 +482,68 @@ impl Tournament {
                      (strategy2_name, strategy1_name, Player::Player1, Player::Player2)
                  };
 
+             // With symmetric openings, every other pair of games swaps the opening's sides
+             let mirrored = self.symmetric_openings && (game_num / 2) % 2 == 1;
+
              if display && self.games_per_match > 1 {
-                 println!("  - Game {}: {} (P1) vs {} (P2)", game_num + 1, first_strategy_type, second_strategy_type);
+                 let sides = if mirrored { " (opening sides swapped)" } else { "" };
+                 println!("  - Game {}: {} (P1) vs {} (P2){}", game_num + 1, first_strategy_type, second_strategy_type, sides);
              }
 
              // Create fresh strategies for each game to reset internal state (like opening counters)
-             let mut first_strategy = self.create_strategy(first_strategy_type, opening_name, first_player_enum);
-             let mut second_strategy = self.create_strategy(second_strategy_type, opening_name, second_player_enum);
-
-             let mut game = Quoridor::new(self.board_size, self.walls, None);
-             let mut move_count = 0;
-             let max_moves = 200; // Safeguard against infinite loops
-
-             loop {
-                 let current_player = game.active_player;
-                 let current_strategy = if current_player == first_player_enum {
-                     &mut first_strategy
-                 } else {
-                     &mut second_strategy
-                 };
-
-                 let move_result = current_strategy.choose_move(&game);
-
-                 if move_result.is_none() {
-                     if display { println!("    Game {}: {} ({}) cannot move, forfeits.", game_num + 1, current_strategy.name(), current_player.name()); }
-                     // The *other* player wins
-                     let winner_type = if current_player == first_player_enum { second_strategy_type } else { first_strategy_type };
-                     if winner_type == strategy1_name { s1_wins += 1; } else { s2_wins += 1; }
-                     break;
-                 }
-
-                 let move_str = move_result.unwrap();
-                 if display && move_count < 10 { // Display only first few moves
-                    println!("    Game {}: Turn {} ({}) plays {}", game_num + 1, move_count + 1, current_player.name(), move_str);
-                 }
-
-                 // Check for win *before* making the move on the board state
-                 let is_win = game.win_check(&move_str);
-
-                 // Apply the move
-                 let move_success = if move_str.len() >= 3 && (move_str.ends_with('h') || move_str.ends_with('v')) {
-                     game.add_wall(&move_str, false, true) // Perform checks
-                 } else {
-                     game.move_pawn(&move_str, true) // Perform checks
-                 };
-
-                 if !move_success {
-                     eprintln!("!!!! CRITICAL ERROR: Strategy {} chose illegal move {} !!!!", current_strategy.name(), move_str);
-                     // Award win to the other player
-                     let winner_type = if current_player == first_player_enum { second_strategy_type } else { first_strategy_type };
-                     if winner_type == strategy1_name { s1_wins += 1; } else { s2_wins += 1; }
-                     break; // Stop the game on illegal move
-                 }
-
-                 if is_win {
-                    if display { println!("    Game {}: {} ({}) wins with move {}.", game_num + 1, current_strategy.name(), current_player.name(), move_str); }
-                    let winning_strategy_name = if current_player == first_player_enum {
-                        first_strategy_type // The name assigned to the first player role in this game
-                    } else {
-                        second_strategy_type // The name assigned to the second player role in this game
-                    };
-
-                    // Compare the winning strategy's NAME to the original strategy1_name parameter
-                    if winning_strategy_name == strategy1_name { // <-- CORRECT COMPARISON
-                        s1_wins += 1;
-                    } else {
-                        s2_wins += 1;
-                    }
-                    break; // Exit game loop
+             let mut first_strategy = self.create_strategy_with_moves(first_strategy_type, opening_name, self.opening_line(opening_name, first_player_enum, mirrored));
+             let mut second_strategy = self.create_strategy_with_moves(second_strategy_type, opening_name, self.opening_line(opening_name, second_player_enum, mirrored));
+             let reseat = |first_move: &str| self.reseated_strategies(first_strategy_type, second_strategy_type, opening_name, mirrored, first_move);
+             if game_num == 0 {
+                 // Strategy1 takes the first seat in the first game
+                 settings = (first_strategy.describe(), second_strategy.describe());
+                 if display {
+                     println!("   {}\n   {}", settings.0, settings.1);
                  }
+             }
 
-                 move_count += 1;
-                 if move_count >= max_moves {
-                     if display { println!("    Game {}: Draw due to move limit ({} moves).", game_num + 1, max_moves); }
-                     draws += 1;
-                     break;
+             let outcome = self.play_game(&mut first_strategy, &mut second_strategy, game_num, display, Some(&reseat));
+             // The same moves with the strategies seated the other way round are a different game
+             if !transcripts.insert((first_strategy_type, outcome.swapped, outcome.transcript)) && self.skip_duplicate_games {
+                 if display { println!("    Game {}: repeats an earlier game, not counted.", game_num + 1); }
+                 continue;
+             }
+             games_played += 1;
+             swaps += usize::from(outcome.swapped);
+
+             // Map seats back to the original strategy1/strategy2 roles; after a
+             // pie-rule swap each strategy finished in the other seat
+             let starting_type_of = |player: Player| if player == first_player_enum { first_strategy_type } else { second_strategy_type };
+             let strategy_type_of = |player: Player| starting_type_of(if outcome.swapped { player.opponent() } else { player });
+             match outcome.winner {
+                 Some(winner) => {
+                     let as_p1 = usize::from(winner == Player::Player1);
+                     if strategy_type_of(winner) == strategy1_name {
+                         s1_wins += 1;
+                         s1_wins_as_p1 += as_p1;
+                     } else {
+                         s2_wins += 1;
+                         s2_wins_as_p1 += as_p1;
+                     }
                  }
-             } // End game loop
+                 None => draws += 1,
+             }
+             if let Some(loser) = outcome.timed_out {
+                 if strategy_type_of(loser) == strategy1_name { s1_timeouts += 1; } else { s2_timeouts += 1; }
+             }
+             for player in [Player::Player1, Player::Player2] {
+                 let think_time = outcome.think_time[player.number() - 1];
+                 if starting_type_of(player) == strategy1_name { s1_think_time += think_time; } else { s2_think_time += think_time; }
+             }
         } // End loop over games_per_match
 
+        if display {
+            println!("   {} distinct games out of {}", transcripts.len(), self.games_per_match);
+            if self.pie_rule {
+                println!("   {} of {} games swapped sides after the first move", swaps, games_played);
+            }
+        }
+
         TournamentResult {
             strategy1: strategy1_name.to_string(),
             strategy2: strategy2_name.to_string(),
@@ -238,55 +551,218 @@ impl Tournament {
             strategy1_wins: s1_wins,
             strategy2_wins: s2_wins,
             draws,
-            games_played: self.games_per_match,
+            games_played,
+            distinct_games: transcripts.len(),
+            strategy1_think_time: s1_think_time,
+            strategy2_think_time: s2_think_time,
+            strategy1_timeouts: s1_timeouts,
+            strategy2_timeouts: s2_timeouts,
+            strategy1_wins_as_p1: s1_wins_as_p1,
+            strategy2_wins_as_p1: s2_wins_as_p1,
+            swaps,
+            strategy1_settings: settings.0,
+            strategy2_settings: settings.1,
         }
     }
 
-    /// Prints detailed tournament configuration information
-    fn print_tournament_config(strategy_names: &[&str], opening_names: &[&str], display: bool) {
+    /// Plays `strategy1` against `strategy2` with every configured opening (see
+    /// `with_openings`) and adds the matches up into one result with the
+    /// opening "All". The per-opening matches are not kept in `results`.
+    pub fn run_head_to_head(&self, strategy1: &str, strategy2: &str, display: bool) -> TournamentResult {
+        let per_opening: Vec<TournamentResult> = TOURNAMENT_OPENINGS
+            .iter()
+            .filter(|opening| self.opening_filter.as_ref().is_none_or(|filter| filter.iter().any(|o| o == *opening)))
+            .map(|opening| self.run_match(strategy1, strategy2, opening, display))
+            .collect();
+        TournamentResult::combined(strategy1, strategy2, &per_opening)
+    }
+
+    /// Prints a head-to-head total from `run_head_to_head`.
+    pub fn print_head_to_head(result: &TournamentResult) {
+        let rate = |wins: usize| if result.games_played == 0 { 0.0 } else { wins as f64 * 100.0 / result.games_played as f64 };
+        println!("\n--- Head-to-head: {} vs {} ({} openings) ---", result.strategy1, result.strategy2, result.opening);
+        println!("  {:<25} {:>4} wins ({:.1}%)", result.strategy1, result.strategy1_wins, rate(result.strategy1_wins));
+        println!("  {:<25} {:>4} wins ({:.1}%)", result.strategy2, result.strategy2_wins, rate(result.strategy2_wins));
+        println!("  {:<25} {:>4} of {} games", "Draws", result.draws, result.games_played);
+    }
+
+    /// Plays one game between two strategies seated as Player1 and Player2.
+    /// Enforces the per-move time limit (a timeout loses the game) and
+    /// records the total thinking time of each side. With the pie rule on, the
+    /// strategies may change seats after the first move; the outcome's winner
+    /// and timeout are then seats as they finished. `reseat` then rebuilds
+    /// them with the opening lines of their new seats; without it they keep
+    /// the lines they were built with.
+    pub fn play_game(
+        &self,
+        first_strategy: &mut Box<dyn Strategy>,
+        second_strategy: &mut Box<dyn Strategy>,
+        game_num: usize,
+        display: bool,
+        reseat: Option<&Reseat>,
+    ) -> GameOutcome {
+        let mut game = Quoridor::new(self.board_size, self.walls, self.start_state.as_deref());
+        let mut record = GameRecord::new(self.board_size, self.walls, self.start_state.as_deref());
+        let mut think_time = [Duration::ZERO; 2];
+        let mut move_count = 0;
+        let mut swapped = false;
+        let max_moves = 200; // Safeguard against infinite loops
+
+        loop {
+            let current_player = game.active_player;
+            let current_strategy = if (current_player == Player::Player1) != swapped {
+                &mut *first_strategy
+            } else {
+                &mut *second_strategy
+            };
+
+            let had_deviated = current_strategy.opening_deviation().is_some();
+            let move_start = Instant::now();
+            let move_result = current_strategy.choose(&game);
+            let elapsed = move_start.elapsed();
+            if let (false, Some((index, book_move))) = (had_deviated, current_strategy.opening_deviation()) {
+                eprintln!("Warning: {} left its opening at move {}: {} is illegal", current_strategy.name(), index + 1, book_move);
+            }
+            think_time[(current_player.number() - 1) ^ usize::from(swapped)] += elapsed;
+
+            if let Some(limit) = self.move_time_limit {
+                if elapsed > limit + TIMEOUT_GRACE {
+                    if display { println!("    Game {}: {} ({}) exceeded the time limit ({:.2?} > {:.2?}), loses on time.", game_num + 1, current_strategy.name(), current_player.name(), elapsed, limit); }
+                    return GameOutcome { winner: Some(current_player.opponent()), timed_out: Some(current_player), think_time, transcript: record.transcript_hash(), swapped };
+                }
+            }
+
+            let move_str = match move_result {
+                ChooseResult::Move(move_str) => move_str,
+                ChooseResult::Resign => {
+                    if display { println!("    Game {}: {} ({}) resigns.", game_num + 1, current_strategy.name(), current_player.name()); }
+                    // The *other* player wins
+                    return GameOutcome { winner: Some(current_player.opponent()), timed_out: None, think_time, transcript: record.transcript_hash(), swapped };
+                }
+                ChooseResult::NoMove => {
+                    if display { println!("    Game {}: {} ({}) has no legal move, forfeits.", game_num + 1, current_strategy.name(), current_player.name()); }
+                    // The *other* player wins
+                    return GameOutcome { winner: Some(current_player.opponent()), timed_out: None, think_time, transcript: record.transcript_hash(), swapped };
+                }
+            };
+
+            if display && move_count < 10 { // Display only first few moves
+               println!("    Game {}: Turn {} ({}) plays {}", game_num + 1, move_count + 1, current_player.name(), move_str);
+            }
+
+            // Check for win *before* making the move on the board state
+            let is_win = game.win_check(&move_str);
+
+            // Apply the move
+            let move_success = game.apply_move(&move_str, true); // Perform checks
+
+            if !move_success {
+                eprintln!("!!!! CRITICAL ERROR: Strategy {} chose illegal move {} !!!!", current_strategy.name(), move_str);
+                eprintln!("     Position: {}", game.state_string);
+                if !game.previous_state.is_empty() {
+                    // Show the transition that led here, to help spot a desynced strategy
+                    let before = Quoridor::new(self.board_size, self.walls, Some(&game.previous_state));
+                    eprintln!("     Previous ply: {}", before.diff(&game));
+                }
+                // Award win to the other player
+                return GameOutcome { winner: Some(current_player.opponent()), timed_out: None, think_time, transcript: record.transcript_hash(), swapped };
+            }
+            record.push(&move_str);
+            if let Some(callback) = &self.progress_callback {
+                callback(move_count + 1);
+            }
+
+            if is_win {
+               if display { println!("    Game {}: {} ({}) wins with move {}.", game_num + 1, current_strategy.name(), current_player.name(), move_str); }
+               return GameOutcome { winner: Some(current_player), timed_out: None, think_time, transcript: record.transcript_hash(), swapped };
+            }
+
+            // Pie rule: Player2 may take over Player1's first move
+            if self.pie_rule && move_count == 0 && current_player == Player::Player1 && second_strategy.should_swap(&game) {
+                swapped = true;
+                if display { println!("    Game {}: {} swaps sides after {}.", game_num + 1, second_strategy.name(), move_str); }
+                if let Some(reseat) = reseat {
+                    (*second_strategy, *first_strategy) = reseat(&move_str);
+                }
+            }
+
+            move_count += 1;
+            if move_count >= max_moves {
+                if display { println!("    Game {}: Draw due to move limit ({} moves).", game_num + 1, max_moves); }
+                return GameOutcome { winner: None, timed_out: None, think_time, transcript: record.transcript_hash(), swapped };
+            }
+        } // End game loop
+    }
+
+    /// Runs a Swiss-system tournament: each round pairs strategies with similar
+    /// scores (avoiding rematches where possible) and plays one `run_match` per
+    /// pairing. Far fewer matches than a round-robin for large strategy pools.
+    /// Match results are stored for CSV output; returns the final standings,
+    /// highest score first.
+    pub fn run_swiss(
+        &mut self,
+        strategy_names: &[&str],
+        opening_name: &str,
+        rounds: usize,
+        display: bool,
+    ) -> Vec<SwissStanding> {
+        let mut standings: Vec<SwissStanding> = strategy_names.iter()
+            .map(|name| SwissStanding { strategy: name.to_string(), score: 0.0, opponents: Vec::new(), byes: 0 })
+            .collect();
+        let mut played: HashSet<(usize, usize)> = HashSet::new();
+
+        for round in 0..rounds {
+            let (pairings, bye) = swiss_pairings(&standings, &played);
+            if display { println!("--- Swiss round {} ---", round + 1); }
+
+            if let Some(idx) = bye {
+                if display { println!("  {} receives a bye", standings[idx].strategy); }
+                standings[idx].score += self.games_per_match as f64;
+                standings[idx].byes += 1;
+            }
+
+            for (a, b) in pairings {
+                let result = self.run_match(&standings[a].strategy, &standings[b].strategy, opening_name, display);
+                let half_draws = result.draws as f64 * 0.5;
+                standings[a].score += result.strategy1_wins as f64 + half_draws;
+                standings[b].score += result.strategy2_wins as f64 + half_draws;
+                let (name_a, name_b) = (standings[a].strategy.clone(), standings[b].strategy.clone());
+                standings[a].opponents.push(name_b);
+                standings[b].opponents.push(name_a);
+                played.insert((a.min(b), a.max(b)));
+                self.results.push(result);
+            }
+        }
+
+        standings.sort_by(|x, y| y.score.partial_cmp(&x.score).unwrap_or(std::cmp::Ordering::Equal));
+        println!("\n--- Swiss Standings ({} rounds) ---", rounds);
+        for (rank, standing) in standings.iter().enumerate() {
+            println!("{:>3}. {:<24} {:>6.1}  (byes: {})", rank + 1, standing.strategy, standing.score, standing.byes);
+        }
+        standings
+    }
+
+    /// Prints detailed tournament configuration information, with each
+    /// strategy's full settings (see `Strategy::describe`) as built for the
+    /// first seat of the first opening, the way `run_match` builds it.
+    fn print_tournament_config(&self, strategy_names: &[&str], opening_names: &[&str], display: bool) {
         println!("\n--- Tournament Configuration Details ---");
         
         // Print strategies with their variants
         println!("\nStrategies in tournament:");
+        let opening = opening_names.first().copied().unwrap_or("No Opening");
         for strat in strategy_names {
-            match *strat {
-                s if s.starts_with("Minimax") => {
-                    let depth_str = s.trim_start_matches("Minimax");
-                    let depth = depth_str.parse::<usize>().unwrap_or(1);
-                    println!("  - {} (depth: {})", s, depth);
-                },
-                s if s.starts_with("MCTS") => {
-                    if s.ends_with("sec") {
-                        let time_str = s.trim_start_matches("MCTS").trim_end_matches("sec");
-                        let seconds = time_str.parse::<f64>().unwrap_or(1.0);
-                        println!("  - {} (time limit: {} seconds)", s, seconds);
-                    } else if s.contains('k') {
-                        let sim_str = s.trim_start_matches("MCTS").replace("k", "000");
-                        let simulations = sim_str.parse::<usize>().unwrap_or(10000);
-                        println!("  - {} (simulations: {})", s, simulations);
-                    } else {
-                        println!("  - {} (default configuration)", s);
-                    }
-                },
-                s if s.starts_with("SimulatedAnnealing") => {
-                    let factor_str = s.trim_start_matches("SimulatedAnnealing");
-                    let factor = factor_str.parse::<f64>().unwrap_or(1.0);
-                    println!("  - {} (temperature factor: {})", s, factor);
-                },
-                s if s == "Defensive" => {
-                    println!("  - {} (wall preference: 0.7)", s);
-                },
-                s if s == "Balanced" => {
-                    println!("  - {} (defense weight: 0.5)", s);
-                },
-                _ => println!("  - {}", strat)
-            }
+            let strategy = self.create_strategy_with_moves(strat, opening, self.opening_line(opening, Player::Player1, false));
+            println!("  - {}", strategy.describe());
         }
         
         // Print openings
         println!("\nOpenings in tournament:");
         for opening in opening_names {
-            println!("  - {}", opening);
+            match openings::check_opening_walls(opening, self.walls, self.board_size) {
+                Ok(()) => println!("  - {}", opening),
+                Err(conflict) => println!("  - {} (warning: {})", opening, conflict),
+            }
         }
         
         // Print match calculation
@@ -305,6 +781,66 @@ impl Tournament {
         println!("-------------------------------------\n");
     }
 
+    /// Prints the configuration and any filters, then lists the matches
+    /// `run_tournament_parallel` would play.
+    fn scheduled_matches(&self, display: bool) -> Vec<MatchConfig> {
+        self.print_tournament_config(TOURNAMENT_STRATEGIES, TOURNAMENT_OPENINGS, display);
+
+        if let Some(openings) = &self.opening_filter {
+            println!("Opening filter: {}", openings.join(", "));
+        }
+        if let Some(strategy) = &self.only_strategy {
+            println!("Only matches involving: {}", strategy);
+        }
+        self.build_match_configs(TOURNAMENT_STRATEGIES, TOURNAMENT_OPENINGS, display)
+    }
+
+    /// Rough seconds per move for a strategy, for schedule estimates only:
+    /// time-limited MCTS uses its limit, searches scale with depth or budget,
+    /// and the simple heuristics are treated as instant. A per-move time
+    /// control caps the estimate.
+    fn estimated_move_seconds(&self, strategy_name: &str) -> f64 {
+        let (base, _) = split_preset(strategy_name);
+        let estimate = if let Some(seconds) = base.strip_prefix("MCTS").and_then(|s| s.strip_suffix("sec")) {
+            seconds.parse::<f64>().unwrap_or(1.0)
+        } else if base.starts_with("MCTS") {
+            self.mcts_time_limit_secs.unwrap_or(self.mcts_simulations_for_board(self.mcts_simulations) as f64 / 50_000.0)
+        } else if let Some(depth) = base.strip_prefix("Minimax") {
+            0.01 * 20f64.powi(depth.parse::<i32>().unwrap_or(1) - 1)
+        } else if let Some(factor) = base.strip_prefix("SimulatedAnnealing") {
+            0.05 * factor.parse::<f64>().unwrap_or(1.0)
+        } else {
+            0.001
+        };
+        self.move_time_limit.map_or(estimate, |limit| estimate.min(limit.as_secs_f64()))
+    }
+
+    /// Prints the schedule without playing it: the configuration, every
+    /// match, the match count and a rough wall-clock estimate spread over the
+    /// available threads. Returns the number of matches.
+    pub fn dry_run(&self, display: bool) -> usize {
+        let match_configs = self.scheduled_matches(display);
+        for (index, (strategy1, strategy2, opening, _)) in match_configs.iter().enumerate() {
+            println!("{:>4}. {} vs {} ({})", index + 1, strategy1, strategy2, opening);
+        }
+
+        // About 2 * board_size plies per game, half of them for each side
+        let moves_per_side = self.board_size as f64;
+        let total_seconds: f64 = match_configs
+            .iter()
+            .map(|(s1, s2, _, _)| (self.estimated_move_seconds(s1) + self.estimated_move_seconds(s2)) * moves_per_side * self.games_per_match as f64)
+            .sum();
+        let num_threads = thread::available_parallelism().map_or(4, |n| n.get());
+        println!(
+            "Total matches to run: {} ({} games), roughly {:.1?} on {} threads (dry run, nothing played)",
+            match_configs.len(),
+            match_configs.len() * self.games_per_match,
+            Duration::from_secs_f64(total_seconds / num_threads as f64),
+            num_threads
+        );
+        match_configs.len()
+    }
+
     /// Runs the full tournament, distributing matches across threads.
     pub fn run_tournament_parallel(&mut self, display: bool) {
         let start_time = Instant::now();
@@ -312,51 +848,17 @@ impl Tournament {
             "Starting parallel tournament ({}x{} board, {} walls, {} games/match)...",
             self.board_size, self.board_size, self.walls, self.games_per_match
         );
+        if let Some(limit) = self.move_time_limit {
+            println!("Time control: {:.2?} per move (timeouts lose the game)", limit);
+        }
+        if let Some(state) = &self.start_state {
+            println!("Start position: {}", state);
+        }
 
-        // --- Configuration ---
-        let strategy_names = vec![
-            // Basic
-            "Random",
-            "ShortestPath",
-            // Intermediate
-            "Defensive",
-            "Balanced",
-            "Adaptive",
-            "Mirror",
-            // Advanced / From Papers
-            "SimulatedAnnealing0.5", // From paper's experiments
-            "SimulatedAnnealing1.0",
-            "Minimax1", // Low depth for speed
-            "Minimax2", // Reference depth from paper
-            // MCTS (adjust simulation counts/time as needed)
-            "MCTS5sec",
-            "MCTS1sec", // 60k in paper's experiments
-        ];
-
-        let opening_names = vec![
-            "No Opening",
-            //"Sidewall Opening",
-            "Standard Opening",
-            //"Shiller Opening", 
-            //"Ala Opening",
-        ];
-        // --- End Configuration ---
-
-        // Print detailed configuration
-        Tournament::print_tournament_config(&strategy_names, &opening_names, display);
-
-        let mut match_configs = Vec::new();
-        for opening_name in &opening_names {
-            for i in 0..strategy_names.len() {
-                for j in (i + 1)..strategy_names.len() { // Avoid self-play and duplicate pairs
-                    match_configs.push((
-                        strategy_names[i].to_string(),
-                        strategy_names[j].to_string(),
-                        opening_name.to_string(),
-                        display,
-                    ));
-                }
-            }
+        let match_configs = self.scheduled_matches(display);
+        if match_configs.is_empty() {
+            eprintln!("Warning: No matches left after filtering; check --openings/--only-strategy names");
+            return;
         }
 
         let total_matches = match_configs.len();
@@ -406,11 +908,26 @@ impl Tournament {
         let games_per_match = self.games_per_match;
         let mcts_simulations = self.mcts_simulations;
         let mcts_time_limit_secs = self.mcts_time_limit_secs;
+        let move_time_limit_secs = self.move_time_limit.map_or(0.0, |limit| limit.as_secs_f64());
 
         // Create a read-only Arc of the Tournament config to share with threads
-        let tournament_config = Arc::new(Tournament::new(board_size, walls, games_per_match)
+        let mut thread_config = Tournament::new(board_size, walls, games_per_match)
             .set_mcts_simulations(mcts_simulations)
-            .set_mcts_time_limit(mcts_time_limit_secs.unwrap_or(0.0)));
+            .set_mcts_time_limit(mcts_time_limit_secs.unwrap_or(0.0))
+            .set_move_time_limit(move_time_limit_secs);
+        if let Some(state) = &self.start_state {
+            thread_config = thread_config.with_start_state(state.clone());
+        }
+        if let Some(budget) = self.slow_move_warning {
+            thread_config = thread_config.with_slow_move_warning(budget.as_secs_f64());
+        }
+        thread_config = thread_config
+            .with_mcts_board_scaling(self.scale_mcts_by_board)
+            .with_symmetric_openings(self.symmetric_openings)
+            .with_skip_duplicate_games(self.skip_duplicate_games)
+            .with_pie_rule(self.pie_rule)
+            .with_opening_mode(self.opening_mode);
+        let tournament_config = Arc::new(thread_config);
 
         // Create a clone of the main progress bar for threads to update
         let main_pb = Arc::new(main_pb);
@@ -490,6 +1007,35 @@ impl Tournament {
     }
 
 
+    /// How much each opening favours the first seat, from the collected results:
+    /// Player 1's score rate over every game played with it (a draw counts as half
+    /// a win, as in `run_swiss`) minus 0.5. Positive favours Player 1, negative
+    /// Player 2. Matches alternate seats, so strategy strength largely cancels out.
+    /// Sorted by the size of the bias, most unbalanced first, then by name.
+    pub fn side_bias(&self) -> Vec<(String, f64)> {
+        let mut totals: HashMap<&str, (f64, usize)> = HashMap::new();
+        for result in &self.results {
+            let entry = totals.entry(result.opening.as_str()).or_default();
+            entry.0 += (result.strategy1_wins_as_p1 + result.strategy2_wins_as_p1) as f64 + result.draws as f64 / 2.0;
+            entry.1 += result.games_played;
+        }
+        let mut bias: Vec<(String, f64)> = totals
+            .into_iter()
+            .filter(|(_, (_, games))| *games > 0)
+            .map(|(opening, (p1_score, games))| (opening.to_string(), p1_score / games as f64 - 0.5))
+            .collect();
+        bias.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()).then_with(|| a.0.cmp(&b.0)));
+        bias
+    }
+
+    /// Prints `side_bias` as a table, one opening per line.
+    pub fn print_side_bias(&self) {
+        println!("Side bias by opening (Player 1 score rate - 50%):");
+        for (opening, bias) in self.side_bias() {
+            println!("  {:<30} {:+6.1}%", opening, bias * 100.0);
+        }
+    }
+
     /// Writes the collected tournament results to a CSV file.
     pub fn write_results_to_csv(&self, filename: &str) -> std::io::Result<()> {
         println!("Writing results to {}...", filename);
@@ -510,6 +1056,13 @@ impl Tournament {
             "Draws",
             "Win %",   // Win percentage for 'Strategy'
             "Games Played",
+            "Distinct Games", // Games that did not repeat an earlier game move for move
+            "Think Time (s)", // Total thinking time for 'Strategy' across the match
+            "Timeouts",       // Games 'Strategy' lost on time
+            "Wins as P1",     // Side-specific wins for 'Strategy'
+            "Wins as P2",
+            "Swaps",          // Games where the pie rule swapped sides after the first move
+            "Settings",       // 'Strategy' as described by Strategy::describe
         ])?;
 
         // Write data rows for each match result
@@ -535,6 +1088,13 @@ impl Tournament {
                 &result.draws.to_string(),
                 &format!("{:.2}", win_percentage1),
                 &result.games_played.to_string(),
+                &result.distinct_games.to_string(),
+                &format!("{:.3}", result.strategy1_think_time.as_secs_f64()),
+                &result.strategy1_timeouts.to_string(),
+                &result.strategy1_wins_as_p1.to_string(),
+                &(result.strategy1_wins - result.strategy1_wins_as_p1).to_string(),
+                &result.swaps.to_string(),
+                &result.strategy1_settings,
             ])?;
 
             // Row for Strategy2 vs Strategy1
@@ -547,6 +1107,13 @@ impl Tournament {
                 &result.draws.to_string(),
                 &format!("{:.2}", win_percentage2),
                 &result.games_played.to_string(),
+                &result.distinct_games.to_string(),
+                &format!("{:.3}", result.strategy2_think_time.as_secs_f64()),
+                &result.strategy2_timeouts.to_string(),
+                &result.strategy2_wins_as_p1.to_string(),
+                &(result.strategy2_wins - result.strategy2_wins_as_p1).to_string(),
+                &result.swaps.to_string(),
+                &result.strategy2_settings,
             ])?;
         }
 
@@ -557,9 +1124,132 @@ impl Tournament {
 }
 
 
+/// Computes one Swiss round: strategies are ranked by score (ties keep pool
+/// order) and paired top-down with the nearest-ranked opponent they have not
+/// yet played, backtracking when a greedy choice leaves no valid completion.
+/// With an odd pool the lowest-ranked strategy with the fewest byes sits out.
+/// Rematches are only allowed if no rematch-free pairing exists.
+fn swiss_pairings(standings: &[SwissStanding], played: &HashSet<(usize, usize)>) -> (Vec<(usize, usize)>, Option<usize>) {
+    let mut order: Vec<usize> = (0..standings.len()).collect();
+    order.sort_by(|&a, &b| standings[b].score.partial_cmp(&standings[a].score).unwrap_or(std::cmp::Ordering::Equal));
+
+    let bye = if order.len() % 2 == 1 {
+        let min_byes = order.iter().map(|&i| standings[i].byes).min().unwrap_or(0);
+        let pos = order.iter().rposition(|&i| standings[i].byes == min_byes).unwrap_or(order.len() - 1);
+        Some(order.remove(pos))
+    } else {
+        None
+    };
+
+    let has_played = |a: usize, b: usize| played.contains(&(a.min(b), a.max(b)));
+    let mut pairings = Vec::new();
+    if !pair_remaining(&order, &has_played, &mut pairings) {
+        // Every complete pairing needs a rematch: fall back to pairing by rank
+        pairings = order.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+    }
+    (pairings, bye)
+}
+
+/// Recursive helper for `swiss_pairings`: pairs the first unpaired strategy
+/// with the best-ranked fresh opponent that still lets the rest be paired.
+fn pair_remaining(order: &[usize], has_played: &dyn Fn(usize, usize) -> bool, pairings: &mut Vec<(usize, usize)>) -> bool {
+    let Some((&first, rest)) = order.split_first() else { return true; };
+    for (i, &opponent) in rest.iter().enumerate() {
+        if has_played(first, opponent) { continue; }
+        let remaining: Vec<usize> = rest.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &idx)| idx).collect();
+        pairings.push((first, opponent));
+        if pair_remaining(&remaining, has_played, pairings) {
+            return true;
+        }
+        pairings.pop();
+    }
+    false
+}
+
+/// Splits a heuristic preset suffix off a strategy name:
+/// "Minimax2-C1" gives ("Minimax2", Some("C1")). Names without a known preset are returned whole.
+fn split_preset(strategy_name: &str) -> (&str, Option<&str>) {
+    match strategy_name.rsplit_once('-') {
+        Some((base, preset)) if EvalWeights::preset(preset).is_some() => (base, Some(preset)),
+        _ => (strategy_name, None),
+    }
+}
+
+// --- Command-Line Options ---
+
+/// Options accepted on the command line.
+#[derive(Debug, Default, PartialEq)]
+struct CliOptions {
+    start_state: Option<String>, // --start-state "<state string>"
+    openings: Option<Vec<String>>, // --openings "No Opening,Standard Opening"
+    only_strategy: Option<String>, // --only-strategy Minimax2
+    warn_slow_moves: Option<f64>, // --warn-slow-moves 0.5 (seconds)
+    symmetric_openings: bool, // --symmetric-openings
+    dry_run: bool, // --dry-run
+    skip_duplicate_games: bool, // --skip-duplicate-games
+    pie_rule: bool, // --pie-rule
+    opening_mode: Option<OpeningDeviation>, // --opening-mode resume|abandon|skip|report
+    head_to_head: Option<(String, String)>, // --head-to-head Minimax2,MCTS1sec
+}
+
+/// Parses command-line arguments (without the program name).
+/// Flags take their value as the next argument or after `=`, except the
+/// on/off switches `--symmetric-openings`, `--dry-run`, `--skip-duplicate-games`
+/// and `--pie-rule`.
+/// Unknown arguments are reported and ignored.
+fn parse_cli_args<I: Iterator<Item = String>>(mut args: I) -> CliOptions {
+    let mut options = CliOptions::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        let mut value = || {
+            let value = inline_value.clone().or_else(|| args.next());
+            if value.is_none() {
+                eprintln!("Warning: {} needs a value", flag);
+            }
+            value
+        };
+        match flag {
+            "--start-state" => if let Some(state) = value() { options.start_state = Some(state) },
+            "--openings" => if let Some(list) = value() {
+                // Comma-separated, e.g. --openings "No Opening,Standard Opening"
+                options.openings = Some(list.split(',').map(|o| o.trim().to_string()).filter(|o| !o.is_empty()).collect());
+            },
+            "--only-strategy" => if let Some(strategy) = value() { options.only_strategy = Some(strategy) },
+            "--symmetric-openings" => options.symmetric_openings = true,
+            "--dry-run" => options.dry_run = true,
+            "--skip-duplicate-games" => options.skip_duplicate_games = true,
+            "--pie-rule" => options.pie_rule = true,
+            "--opening-mode" => match value().as_deref() {
+                Some("resume") => options.opening_mode = Some(OpeningDeviation::Resume),
+                Some("abandon") => options.opening_mode = Some(OpeningDeviation::Abandon),
+                Some("skip") => options.opening_mode = Some(OpeningDeviation::Skip),
+                Some("report") => options.opening_mode = Some(OpeningDeviation::Report),
+                Some(mode) => eprintln!("Warning: Unknown opening mode '{}' (expected resume, abandon, skip or report)", mode),
+                None => {}
+            },
+            "--head-to-head" => match value().as_deref().and_then(|pair| pair.split_once(',')) {
+                Some((a, b)) => options.head_to_head = Some((a.trim().to_string(), b.trim().to_string())),
+                None => eprintln!("Warning: --head-to-head needs two strategies, e.g. Minimax2,MCTS1sec"),
+            },
+            "--warn-slow-moves" => match value().map(|secs| secs.parse::<f64>()) {
+                Some(Ok(seconds)) => options.warn_slow_moves = Some(seconds),
+                Some(Err(_)) => eprintln!("Warning: --warn-slow-moves needs a number of seconds"),
+                None => {}
+            },
+            _ => eprintln!("Warning: Ignoring unknown argument '{}'", arg),
+        }
+    }
+    options
+}
+
 // --- Main Application Logic ---
 
 fn main() {
+    let options = parse_cli_args(env::args().skip(1));
+
     // Check for debug environment variable
     let debug_enabled = env::var("QUORIDOR_DEBUG").map_or(false, |val| val == "1" || val.to_lowercase() == "true");
 
@@ -575,12 +1265,61 @@ fn main() {
         30, // Number of games per matchup (e.g., 50 games, 25 starting each side)
     );
 
+    // Start every game from a custom position, e.g. --start-state "e3 / / e4 e6 / 9 10 / 2"
+    if let Some(state) = options.start_state {
+        if let Err(player) = Quoridor::try_new(tournament.board_size, tournament.walls, Some(&state)) {
+            eprintln!("Error: --start-state leaves {} with no path to goal", player);
+            return;
+        }
+        tournament = tournament.with_start_state(state);
+    }
+    // Narrow the round-robin while iterating, e.g. --openings "No Opening" --only-strategy Minimax2
+    if let Some(openings) = options.openings {
+        tournament = tournament.with_openings(openings);
+    }
+    if let Some(strategy) = options.only_strategy {
+        tournament = tournament.with_only_strategy(strategy);
+    }
+    // Log strategies that take longer than this per move, e.g. --warn-slow-moves 0.5
+    if let Some(seconds) = options.warn_slow_moves {
+        tournament = tournament.with_slow_move_warning(seconds);
+    }
+    // Play each opening from both sides to cancel out lines that favour one seat
+    tournament = tournament.with_symmetric_openings(options.symmetric_openings);
+    // Count games that repeat an earlier one move for move only once
+    tournament = tournament.with_skip_duplicate_games(options.skip_duplicate_games);
+    // Let the second player take over a first move it judges too strong
+    tournament = tournament.with_pie_rule(options.pie_rule);
+    // Strict opening-book play, e.g. --opening-mode report to log every deviation
+    if let Some(mode) = options.opening_mode {
+        tournament = tournament.with_opening_mode(mode);
+    }
+
     // Optional: Configure MCTS parameters if needed globally
     // tournament = tournament.set_mcts_simulations(50000);
     // tournament = tournament.set_mcts_time_limit(1.0); // 1 second per move
+    // tournament = tournament.with_mcts_board_scaling(true); // Fairer MCTS across board sizes
+    // Optional: Per-move time control for all strategies (timeouts lose the game)
+    // tournament = tournament.set_move_time_limit(1.0);
+
+    // One pairing over every opening, added up, e.g. --head-to-head Minimax2,MCTS1sec
+    if let Some((strategy1, strategy2)) = options.head_to_head {
+        let result = tournament.run_head_to_head(&strategy1, &strategy2, debug_enabled);
+        Tournament::print_head_to_head(&result);
+        return;
+    }
+
+    // Only list the matches and an estimated run time, e.g. --dry-run
+    if options.dry_run {
+        tournament.dry_run(debug_enabled);
+        return;
+    }
 
     // Run the tournament using multiple threads
     tournament.run_tournament_parallel(debug_enabled);
+    tournament.print_side_bias();
+    // Alternative for large strategy pools: a Swiss-system event instead of a full round-robin
+    // tournament.run_swiss(&["Random", "ShortestPath", "Defensive", "Minimax2", "MCTS1sec"], "No Opening", 4, debug_enabled);
 
     // Define the output directory and filename
     let output_dir = "tournament_outputs";
@@ -593,4 +1332,464 @@ fn main() {
     }
 
      println!("--- Tournament Finished ---");
-}
\ No newline at end of file
+}
+
+// --- Tests ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quoridor_core::strategy::Resigner;
+
+    /// Test strategy that sleeps before delegating to ShortestPath.
+    struct SlowStrategy {
+        delay: Duration,
+        inner: ShortestPathStrategy,
+    }
+
+    impl Strategy for SlowStrategy {
+        fn name(&self) -> String {
+            "Slow".to_string()
+        }
+
+        fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+            thread::sleep(self.delay);
+            self.inner.choose_move(game)
+        }
+    }
+
+    fn slow(delay_ms: u64) -> Box<dyn Strategy> {
+        Box::new(SlowStrategy { delay: Duration::from_millis(delay_ms), inner: ShortestPathStrategy::new("", Vec::new()) })
+    }
+
+    #[test]
+    fn test_timeout_loses_game() {
+        let tournament = Tournament::new(9, 10, 1).set_move_time_limit(0.05);
+        let mut first: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+        let mut second = slow(300);
+
+        let outcome = tournament.play_game(&mut first, &mut second, 0, false, None);
+        assert_eq!(outcome.winner, Some(Player::Player1));
+        assert_eq!(outcome.timed_out, Some(Player::Player2));
+        assert!(outcome.think_time[1] >= Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_no_timeout_without_time_control() {
+        let tournament = Tournament::new(5, 3, 1);
+        let mut first = slow(1);
+        let mut second: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+
+        let outcome = tournament.play_game(&mut first, &mut second, 0, false, None);
+        assert_eq!(outcome.timed_out, None);
+        assert!(outcome.winner.is_some()); // Two pawn racers always produce a decisive game
+        assert!(outcome.think_time[0] > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_resignation_loses_game() {
+        let tournament = Tournament::new(9, 10, 1);
+        let mut first: Box<dyn Strategy> = Box::new(Resigner);
+        let mut second: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+
+        let outcome = tournament.play_game(&mut first, &mut second, 0, false, None);
+        assert_eq!(outcome.winner, Some(Player::Player2));
+        assert_eq!(outcome.timed_out, None);
+    }
+
+    #[test]
+    fn test_run_match_records_think_time() {
+        let tournament = Tournament::new(5, 3, 2).set_move_time_limit(1.0);
+        let result = tournament.run_match("ShortestPath", "Minimax1", "No Opening", false);
+        assert_eq!(result.games_played, 2);
+        assert_eq!(result.strategy1_wins + result.strategy2_wins + result.draws, 2);
+        assert_eq!(result.strategy1_timeouts + result.strategy2_timeouts, 0);
+        assert!(result.strategy2_think_time > Duration::ZERO);
+    }
+
+    #[test]
+    fn test_progress_callback_runs_once_per_ply() {
+        // No walls in hand, open files: P1 reaches a5 on its fourth move, the 7th ply
+        let plies = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&plies);
+        let tournament = Tournament::new(5, 3, 1)
+            .with_start_state(" / / a1 e5 / 0 0 / 1".to_string())
+            .with_progress_callback(move |ply| recorded.lock().unwrap().push(ply));
+        let mut first: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+        let mut second: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+
+        let outcome = tournament.play_game(&mut first, &mut second, 0, false, None);
+        assert_eq!(outcome.winner, Some(Player::Player1));
+        assert_eq!(*plies.lock().unwrap(), (1..=7).collect::<Vec<_>>());
+
+        // No callback by default
+        assert!(Tournament::new(5, 3, 1).progress_callback.is_none());
+    }
+
+    fn standing(name: &str, score: f64) -> SwissStanding {
+        SwissStanding { strategy: name.to_string(), score, opponents: Vec::new(), byes: 0 }
+    }
+
+    #[test]
+    fn test_swiss_pairs_by_score() {
+        let standings = vec![standing("A", 0.0), standing("B", 3.0), standing("C", 1.0), standing("D", 2.0)];
+        let (pairings, bye) = swiss_pairings(&standings, &HashSet::new());
+        assert_eq!(bye, None);
+        assert_eq!(pairings, vec![(1, 3), (2, 0)]); // B-D on top, C-A below
+    }
+
+    #[test]
+    fn test_swiss_backtracks_to_avoid_rematch() {
+        // Greedy would pair A-C then be stuck with the rematch B-D
+        let standings = vec![standing("A", 3.0), standing("B", 2.0), standing("C", 1.0), standing("D", 0.0)];
+        let played: HashSet<(usize, usize)> = [(0, 1), (1, 3)].into_iter().collect();
+        let (pairings, _) = swiss_pairings(&standings, &played);
+        assert_eq!(pairings, vec![(0, 3), (1, 2)]);
+    }
+
+    #[test]
+    fn test_run_swiss_no_repeats_and_scores_accumulate() {
+        let pool = ["Random", "ShortestPath", "Defensive", "Mirror", "Balanced"];
+        let games_per_match = 2;
+        let rounds = 3; // Greedy Swiss pairing of 5 never needs a rematch within 3 rounds
+        let mut tournament = Tournament::new(5, 3, games_per_match);
+        let standings = tournament.run_swiss(&pool, "No Opening", rounds, false);
+
+        // Two matches per round, one bye per round
+        assert_eq!(tournament.results.len(), 2 * rounds);
+        assert_eq!(standings.iter().map(|s| s.byes).sum::<usize>(), rounds);
+
+        // Nobody meets the same opponent twice
+        for s in &standings {
+            let unique: HashSet<&String> = s.opponents.iter().collect();
+            assert_eq!(unique.len(), s.opponents.len(), "{} had a rematch", s.strategy);
+            assert_eq!(s.opponents.len() + s.byes, rounds);
+        }
+
+        // Every game awards exactly one point, byes a full match's worth
+        let total: f64 = standings.iter().map(|s| s.score).sum();
+        let expected = (tournament.results.len() * games_per_match + rounds * games_per_match) as f64;
+        assert!((total - expected).abs() < 1e-9);
+
+        // Standings are sorted by score
+        assert!(standings.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    /// Test strategy that records the first position it is asked to move in.
+    struct Recorder {
+        first_state: Arc<Mutex<Option<String>>>,
+        inner: ShortestPathStrategy,
+    }
+
+    impl Strategy for Recorder {
+        fn name(&self) -> String {
+            "Recorder".to_string()
+        }
+
+        fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+            self.first_state.lock().unwrap().get_or_insert_with(|| game.state_string.clone());
+            self.inner.choose_move(game)
+        }
+    }
+
+    #[test]
+    fn test_games_begin_from_start_state() {
+        let start = "e3 / / e4 e6 / 9 10 / 1";
+        let tournament = Tournament::new(9, 10, 1).with_start_state(start.to_string());
+        let first_state = Arc::new(Mutex::new(None));
+        let mut first: Box<dyn Strategy> = Box::new(Recorder { first_state: Arc::clone(&first_state), inner: ShortestPathStrategy::new("", Vec::new()) });
+        let mut second: Box<dyn Strategy> = Box::new(ShortestPathStrategy::new("", Vec::new()));
+
+        tournament.play_game(&mut first, &mut second, 0, false, None);
+        let expected = Quoridor::new(9, 10, Some(start)).state_string;
+        assert_eq!(first_state.lock().unwrap().as_deref(), Some(expected.as_str()));
+    }
+
+    #[test]
+    fn test_parse_start_state_flag() {
+        let args = ["--start-state", "e3 / / e4 e6 / 9 10 / 1"].iter().map(|s| s.to_string());
+        assert_eq!(parse_cli_args(args).start_state.as_deref(), Some("e3 / / e4 e6 / 9 10 / 1"));
+        let args = ["--start-state= / / e2 e8 / 10 10 / 1"].iter().map(|s| s.to_string());
+        assert_eq!(parse_cli_args(args).start_state.as_deref(), Some(" / / e2 e8 / 10 10 / 1"));
+        assert_eq!(parse_cli_args(std::iter::empty()), CliOptions::default());
+    }
+
+    #[test]
+    fn test_match_configs_respect_filters() {
+        let strategies = ["Random", "ShortestPath", "Minimax1", "MCTS1sec"];
+        let openings = ["No Opening", "Standard Opening"];
+
+        // Unfiltered: every pair for every opening
+        let all = Tournament::new(9, 10, 1).build_match_configs(&strategies, &openings, false);
+        assert_eq!(all.len(), 6 * 2);
+
+        let filtered = Tournament::new(9, 10, 1)
+            .with_openings(vec!["No Opening".to_string()])
+            .with_only_strategy("Minimax1".to_string())
+            .build_match_configs(&strategies, &openings, false);
+        assert_eq!(filtered.len(), 3); // Minimax1 against each of the other three
+        for (s1, s2, opening, _) in &filtered {
+            assert_eq!(opening, "No Opening");
+            assert!(s1 == "Minimax1" || s2 == "Minimax1");
+        }
+
+        // An opening that isn't configured leaves nothing to run
+        let none = Tournament::new(9, 10, 1).with_openings(vec!["Ala Opening".to_string()]).build_match_configs(&strategies, &openings, false);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_counts_scheduled_matches() {
+        let strategies = TOURNAMENT_STRATEGIES.len();
+        let full = Tournament::new(9, 10, 1);
+        assert_eq!(full.dry_run(false), strategies * (strategies - 1) / 2 * TOURNAMENT_OPENINGS.len());
+
+        let narrowed = Tournament::new(9, 10, 1)
+            .with_openings(vec!["No Opening".to_string()])
+            .with_only_strategy("Minimax2".to_string());
+        assert_eq!(narrowed.dry_run(false), strategies - 1);
+        assert!(parse_cli_args(["--dry-run".to_string()].into_iter()).dry_run);
+
+        // Time-limited MCTS is costed at its limit, capped by the time control
+        assert_eq!(full.estimated_move_seconds("MCTS5sec"), 5.0);
+        assert_eq!(Tournament::new(9, 10, 1).set_move_time_limit(0.5).estimated_move_seconds("MCTS5sec"), 0.5);
+    }
+
+    #[test]
+    fn test_head_to_head_sums_openings() {
+        let result = |strategy1: &str, opening: &str, wins: (usize, usize), draws| TournamentResult {
+            strategy1: strategy1.to_string(),
+            strategy2: "B".to_string(),
+            opening: opening.to_string(),
+            strategy1_wins: wins.0,
+            strategy2_wins: wins.1,
+            draws,
+            games_played: wins.0 + wins.1 + draws,
+            distinct_games: 1,
+            strategy1_think_time: Duration::from_millis(10),
+            strategy2_think_time: Duration::from_millis(20),
+            strategy1_timeouts: 1,
+            strategy1_wins_as_p1: wins.0 / 2,
+            strategy2_wins_as_p1: wins.1 / 2,
+            swaps: 1,
+            ..Default::default()
+        };
+        let per_opening = [
+            result("A", "No Opening", (6, 3), 1),
+            result("A", "Standard Opening", (2, 7), 1),
+            result("C", "No Opening", (9, 0), 1), // Another pairing
+        ];
+        let total = TournamentResult::combined("A", "B", &per_opening);
+        assert_eq!(total.opening, "All");
+        assert_eq!((total.strategy1_wins, total.strategy2_wins, total.draws, total.games_played), (8, 10, 2, 20));
+        assert_eq!((total.strategy1_wins_as_p1, total.strategy2_wins_as_p1), (4, 4));
+        assert_eq!(total.strategy1_think_time, Duration::from_millis(20));
+        assert_eq!(total.strategy1_timeouts, 2);
+        assert_eq!(total.distinct_games, 2);
+        assert_eq!(total.swaps, 2);
+
+        // A real run plays each configured opening once
+        let tournament = Tournament::new(5, 3, 2).with_openings(vec!["No Opening".to_string()]);
+        let played = tournament.run_head_to_head("ShortestPath", "Random", false);
+        assert_eq!((played.opening.as_str(), played.games_played), ("All", 2));
+
+        let args = ["--head-to-head", "Minimax2, MCTS1sec"].iter().map(|s| s.to_string());
+        assert_eq!(parse_cli_args(args).head_to_head, Some(("Minimax2".to_string(), "MCTS1sec".to_string())));
+    }
+
+    #[test]
+    fn test_parse_filter_flags() {
+        let args = ["--openings", "No Opening, Standard Opening", "--only-strategy=Minimax2"].iter().map(|s| s.to_string());
+        let options = parse_cli_args(args);
+        assert_eq!(options.openings, Some(vec!["No Opening".to_string(), "Standard Opening".to_string()]));
+        assert_eq!(options.only_strategy.as_deref(), Some("Minimax2"));
+    }
+
+    #[test]
+    fn test_symmetric_openings_cancel_side_bias() {
+        // ShortestPath ignores a heuristic preset, so these are one deterministic
+        // player under two names. In the Rush Variation the P1 line wins the race.
+        let (s1, s2, opening) = ("ShortestPath", "ShortestPath-C1", "Rush Variation");
+        let plain = Tournament::new(9, 10, 4).run_match(s1, s2, opening, false);
+        assert_eq!((plain.strategy1_wins, plain.strategy2_wins), (2, 2));
+        assert_eq!(plain.strategy1_wins_as_p1 + plain.strategy2_wins_as_p1, 4); // Player 1's seat won every game
+
+        // Played from both sides, the line wins from either seat
+        let symmetric = Tournament::new(9, 10, 4).with_symmetric_openings(true).run_match(s1, s2, opening, false);
+        assert_eq!((symmetric.strategy1_wins, symmetric.strategy2_wins), (2, 2));
+        assert_eq!((symmetric.strategy1_wins_as_p1, symmetric.strategy2_wins_as_p1), (1, 1));
+
+        assert!(parse_cli_args(["--symmetric-openings".to_string()].into_iter()).symmetric_openings);
+    }
+
+    #[test]
+    fn test_results_carry_the_settings_that_played() {
+        let mut tournament = Tournament::new(5, 0, 2).set_move_time_limit(5.0);
+        let result = tournament.run_match("Minimax1", "Mirror", "No Opening", false);
+        assert!(result.strategy1_settings.contains("time limit: 5.00s"), "{}", result.strategy1_settings);
+        assert!(result.strategy2_settings.starts_with("Mirror (backup: Adaptive"), "{}", result.strategy2_settings);
+
+        tournament.results.push(result);
+        let path = std::env::temp_dir().join(format!("quoridor_settings_{}.csv", std::process::id()));
+        tournament.write_results_to_csv(path.to_str().unwrap()).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(csv.lines().next().unwrap().ends_with(",Settings"));
+        assert!(csv.contains("time limit: 5.00s"));
+        assert!(csv.contains("Mirror (backup: Adaptive"));
+    }
+
+    #[test]
+    fn test_deterministic_games_collapse_to_one_transcript() {
+        // The same deterministic strategy in both seats plays one game four times
+        let strategy = "ShortestPath";
+        let counted = Tournament::new(5, 0, 4).run_match(strategy, strategy, "No Opening", false);
+        assert_eq!((counted.games_played, counted.distinct_games), (4, 1));
+
+        let skipped = Tournament::new(5, 0, 4).with_skip_duplicate_games(true).run_match(strategy, strategy, "No Opening", false);
+        assert_eq!((skipped.games_played, skipped.distinct_games), (1, 1));
+        assert_eq!(skipped.strategy1_wins + skipped.strategy2_wins + skipped.draws, 1);
+
+        // Without walls Minimax1 and ShortestPath both run straight for goal, so the
+        // two seatings play the same moves, but as different games: one kept per seating
+        let (s1, s2) = ("Minimax1", "ShortestPath");
+        let skipped = Tournament::new(5, 0, 4).with_skip_duplicate_games(true).run_match(s1, s2, "No Opening", false);
+        assert_eq!((skipped.games_played, skipped.distinct_games), (2, 2));
+        assert_eq!((skipped.strategy1_wins, skipped.strategy2_wins), (1, 1));
+        assert!(parse_cli_args(["--skip-duplicate-games".to_string()].into_iter()).skip_duplicate_games);
+    }
+
+    #[test]
+    fn test_pie_rule_swaps_after_winning_first_move() {
+        // P1 steps to c4 and is one move from the goal while P2 needs three
+        let state = " / / c3 c5 / 0 0 / 1";
+        let tournament = Tournament::new(5, 0, 2).with_start_state(state.to_string()).with_pie_rule(true);
+        let mut first = tournament.create_strategy("ShortestPath", "No Opening", Player::Player1);
+        let mut second = tournament.create_strategy("ShortestPath", "No Opening", Player::Player2);
+        let outcome = tournament.play_game(&mut first, &mut second, 0, false, None);
+        assert!(outcome.swapped);
+        assert_eq!(outcome.winner, Some(Player::Player1)); // The seat the second strategy took over
+
+        // ShortestPath ignores a heuristic preset: one player under two names
+        let (s1, s2) = ("ShortestPath", "ShortestPath-C1");
+        let swapped = tournament.run_match(s1, s2, "No Opening", false);
+        assert_eq!(swapped.swaps, 2);
+        assert_eq!((swapped.strategy1_wins, swapped.strategy2_wins), (1, 1));
+        let plain = Tournament::new(5, 0, 2).with_start_state(state.to_string()).run_match(s1, s2, "No Opening", false);
+        assert_eq!(plain.swaps, 0);
+        assert!(parse_cli_args(["--pie-rule".to_string()].into_iter()).pie_rule);
+    }
+
+    #[test]
+    fn test_pie_rule_swap_rebuilds_opening_lines() {
+        // P1 on e7 is two steps from goal, so P2 takes over after P1's first book wall
+        let state = " / / e7 e9 / 10 10 / 1";
+        let opening = "Sidewall Opening"; // Walls only: c3h f3h for P1, c6h f6h for P2
+        let tournament = Tournament::new(9, 10, 1).with_start_state(state.to_string()).with_pie_rule(true);
+        let play = |reseat: Option<&Reseat>| {
+            let mut first = tournament.create_strategy("ShortestPath", opening, Player::Player1);
+            let mut second = tournament.create_strategy("ShortestPath", opening, Player::Player2);
+            tournament.play_game(&mut first, &mut second, 0, false, reseat)
+        };
+        let reseat = |first_move: &str| tournament.reseated_strategies("ShortestPath", "ShortestPath", opening, false, first_move);
+        let outcome = play(Some(&reseat));
+        assert!(outcome.swapped);
+
+        // Each seat finishes its own book, then both run for goal
+        let mut game = Quoridor::new(9, 10, Some(state));
+        let mut expected = GameRecord::new(9, 10, Some(state));
+        let mut runner = ShortestPathStrategy::new("", Vec::new());
+        for move_str in ["c3h", "c6h", "f3h", "f6h"] {
+            assert!(game.apply_move(move_str, true));
+            expected.push(move_str);
+        }
+        while game.winner().is_none() {
+            let move_str = runner.choose_move(&game).unwrap();
+            assert!(game.apply_move(&move_str, true));
+            expected.push(&move_str);
+        }
+        assert_eq!(outcome.transcript, expected.transcript_hash());
+
+        // Kept as built, the strategies would play the other seat's book walls
+        assert_ne!(play(None).transcript, outcome.transcript);
+    }
+
+    #[test]
+    fn test_opening_mode_reaches_strategies() {
+        let args = ["--opening-mode", "report"].iter().map(|s| s.to_string());
+        let mode = parse_cli_args(args).opening_mode.expect("a mode");
+        assert_eq!(mode, OpeningDeviation::Report);
+
+        // P1 has already left e1, so the book's first move "e2" is illegal
+        let game = Quoridor::new(9, 10, Some(" / / a1 e9 / 10 10 / 1"));
+        let tournament = Tournament::new(9, 10, 1).with_opening_mode(mode).with_slow_move_warning(10.0);
+        let mut strategy = tournament.create_strategy("Minimax1", "Standard Opening", Player::Player1);
+        assert!(strategy.choose_move(&game).is_some());
+        assert_eq!(strategy.opening_deviation(), Some((0, "e2".to_string())));
+    }
+
+    #[test]
+    fn test_side_bias_flags_first_seat_opening() {
+        let result = |opening: &str, strategy1_wins_as_p1, strategy2_wins_as_p1, draws| TournamentResult {
+            strategy1: "A".to_string(),
+            strategy2: "B".to_string(),
+            opening: opening.to_string(),
+            draws,
+            games_played: 10,
+            distinct_games: 10,
+            strategy1_wins_as_p1,
+            strategy2_wins_as_p1,
+            ..Default::default()
+        };
+        let mut tournament = Tournament::new(9, 10, 10);
+        tournament.results = vec![
+            result("Rush Variation", 5, 4, 0), // 18 of 20 games to Player 1
+            result("Rush Variation", 4, 5, 0),
+            result("Standard Opening", 3, 2, 0), // Even
+            result("Standard Opening", 2, 2, 2), // Draws count half
+            result("Ala Opening", 1, 2, 1),
+        ];
+        let bias = tournament.side_bias();
+        let names: Vec<&str> = bias.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Rush Variation", "Ala Opening", "Standard Opening"]);
+        assert!((bias[0].1 - 0.4).abs() < 1e-9);
+        assert!((bias[1].1 + 0.15).abs() < 1e-9);
+        assert!(bias[2].1.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_slow_move_warning_wraps_strategies() {
+        let args = ["--warn-slow-moves", "0.25"].iter().map(|s| s.to_string());
+        assert_eq!(parse_cli_args(args).warn_slow_moves, Some(0.25));
+
+        // The decorator keeps the inner name and still plays the same move
+        let tournament = Tournament::new(9, 10, 1).with_slow_move_warning(0.25);
+        let mut strategy = tournament.create_strategy("ShortestPath", "No Opening", Player::Player1);
+        assert_eq!(strategy.name(), "ShortestPath");
+        assert_eq!(strategy.choose_move(&Quoridor::new(9, 10, None)), Some("e2".to_string()));
+    }
+
+    #[test]
+    fn test_mcts_simulations_scale_with_board_area() {
+        let small = Tournament::new(5, 3, 1).with_mcts_board_scaling(true);
+        let standard = Tournament::new(9, 10, 1).with_mcts_board_scaling(true);
+        assert_eq!(small.mcts_simulations_for_board(10000), 3086);
+        assert_eq!(standard.mcts_simulations_for_board(10000), 10000);
+        assert_eq!(small.mcts_simulations_for_board(1), 1);
+
+        // Off by default, and visible in the strategy name when on
+        assert_eq!(Tournament::new(5, 3, 1).mcts_simulations_for_board(10000), 10000);
+        let strategy = small.create_strategy("MCTS10000", "No Opening", Player::Player1);
+        assert_eq!(strategy.name(), "MCTS3086");
+    }
+
+    #[test]
+    fn test_heuristic_presets_in_strategy_names() {
+        assert_eq!(split_preset("Minimax2-C1"), ("Minimax2", Some("C1")));
+        assert_eq!(split_preset("Minimax2"), ("Minimax2", None));
+        assert_eq!(split_preset("Minimax2-X"), ("Minimax2-X", None));
+
+        let tournament = Tournament::new(9, 10, 1);
+        assert_eq!(tournament.create_strategy("Minimax2-C1", "No Opening", Player::Player1).name(), "Minimax2-C1");
+        assert_eq!(tournament.create_strategy("SimulatedAnnealing1.0-C2", "No Opening", Player::Player1).name(), "SimulatedAnnealing1.0-C2");
+    }
+}
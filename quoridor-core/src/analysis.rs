@@ -0,0 +1,256 @@
+// --- File: quoridor-project/quoridor-core/src/analysis.rs ---
+
+//! Position analysis helpers for teaching and debugging UIs.
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use crate::strategy::MinimaxStrategy;
+use crate::types::Coord;
+use crate::utils::{classify_move, MoveKind};
+
+/// Searches the position with the strategy's evaluation settings and reports
+/// (depth, score, best move) for each requested depth, in the order given.
+///
+/// One iterative-deepening run up to the deepest requested depth is made, and
+/// each completed depth is snapshotted. Scores are from the side to move's
+/// perspective; `f64::INFINITY` means a forced win was found. Depth 0 is
+/// skipped, and a position with no legal moves yields an empty list.
+/// The strategy itself (opening book, search state) is not touched.
+pub fn evaluate_at_depths(game: &Quoridor, strategy: &MinimaxStrategy, depths: &[usize]) -> Vec<(usize, f64, String)> {
+    let Some(&max_depth) = depths.iter().max() else { return Vec::new(); };
+    if max_depth == 0 { return Vec::new(); }
+
+    let mut searcher = strategy.analysis_copy(max_depth);
+    let by_depth = searcher.deepening_scores(game, max_depth);
+
+    depths
+        .iter()
+        .filter(|&&depth| depth > 0)
+        .filter_map(|&depth| {
+            let (best_move, score) = by_depth.get(depth - 1)?;
+            Some((depth, *score, best_move.clone().unwrap_or_default()))
+        })
+        .collect()
+}
+
+/// The line the strategy expects to be played from this position, at most
+/// `depth` plies long, e.g. ["e2", "e8", "e3h"]. Each ply is searched to the
+/// remaining depth, so the first move matches a `depth`-ply search.
+/// A finished game, or depth 0, gives an empty line.
+/// The strategy itself (opening book, search state) is not touched.
+pub fn principal_variation(game: &Quoridor, strategy: &MinimaxStrategy, depth: usize) -> Vec<String> {
+    if depth == 0 { return Vec::new(); }
+    strategy.analysis_copy(depth).principal_variation(game, depth)
+}
+
+/// Smallest, largest and mean number of moves seen across positions.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MoveCountRange {
+    pub min: usize,
+    pub max: usize,
+    pub average: f64,
+}
+
+impl MoveCountRange {
+    fn from_counts(counts: &[usize]) -> Self {
+        if counts.is_empty() { return MoveCountRange::default(); }
+        MoveCountRange {
+            min: *counts.iter().min().unwrap(),
+            max: *counts.iter().max().unwrap(),
+            average: counts.iter().sum::<usize>() as f64 / counts.len() as f64,
+        }
+    }
+}
+
+/// Legal-move counts for the side to move in every position at one ply.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlyBranching {
+    /// Positions expanded at this ply (finished games are not counted).
+    pub positions: usize,
+    pub pawn: MoveCountRange,
+    pub wall: MoveCountRange,
+    pub total: MoveCountRange,
+}
+
+/// Per-ply branching of the game tree; `plies[0]` is the given position.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BranchingStats {
+    pub plies: Vec<PlyBranching>,
+}
+
+/// Expands every line from the position for `depth` plies and reports how
+/// many legal pawn and wall moves the side to move had at each ply, for
+/// sizing MCTS widening and minimax depth per board size. The tree grows by
+/// roughly the total count per ply, so keep `depth` at 2 or so on 9x9.
+/// Stops early if every line has finished.
+pub fn branching_stats(game: &Quoridor, depth: usize) -> BranchingStats {
+    let mut stats = BranchingStats::default();
+    let mut frontier = vec![game.clone()];
+
+    for ply in 0..depth {
+        frontier.retain(|position| position.winner().is_none());
+        if frontier.is_empty() { break; }
+
+        let (mut pawn, mut wall, mut total) = (Vec::new(), Vec::new(), Vec::new());
+        let mut next = Vec::new();
+        for position in &frontier {
+            let moves = position.all_legal_moves(position.active_player);
+            let walls = moves.iter().filter(|m| matches!(classify_move(m, position.size), MoveKind::Wall(..))).count();
+            pawn.push(moves.len() - walls);
+            wall.push(walls);
+            total.push(moves.len());
+
+            if ply + 1 < depth {
+                for move_str in &moves {
+                    let mut child = position.clone();
+                    if child.apply_move(move_str, false) {
+                        next.push(child);
+                    }
+                }
+            }
+        }
+
+        stats.plies.push(PlyBranching {
+            positions: frontier.len(),
+            pawn: MoveCountRange::from_counts(&pawn),
+            wall: MoveCountRange::from_counts(&wall),
+            total: MoveCountRange::from_counts(&total),
+        });
+        frontier = next;
+    }
+    stats
+}
+
+/// Most walls `best_wall_combo` will combine. Every extra wall multiplies
+/// the work by the number of legal walls (over 100 on 9x9).
+pub const MAX_COMBO_WALLS: usize = 2;
+
+/// Finds up to `k` walls (at most `MAX_COMBO_WALLS`) that together push
+/// `player` furthest from its goal, for puzzle generation. The walls are
+/// placed by the opponent, one after another, each legal where it is placed
+/// and drawn from the opponent's remaining walls. Returns the walls in
+/// alphabetical order with `player`'s distance afterwards. Fewer walls win
+/// ties, then the alphabetically first set; if no wall adds to the distance
+/// the set is empty and the distance is the current one.
+pub fn best_wall_combo(game: &Quoridor, player: Player, k: usize) -> (Vec<String>, usize) {
+    let placer = game.clone().with_first_player(player.opponent());
+    let mut best = (Vec::new(), game.distance_to_goal(player));
+    extend_wall_combo(&placer, player, k.min(MAX_COMBO_WALLS), None, &mut Vec::new(), &mut best);
+    best
+}
+
+/// Tries every legal wall after `after` (so each set is visited in one order
+/// only) on top of `placed`, keeping the best set seen in `best`.
+fn extend_wall_combo(game: &Quoridor, player: Player, walls_left: usize, after: Option<(Coord, char)>, placed: &mut Vec<String>, best: &mut (Vec<String>, usize)) {
+    if walls_left == 0 { return; }
+    let placer = game.active_player;
+    for wall in game.get_legal_walls_coords(placer) {
+        if after.is_some_and(|previous| wall <= previous) { continue; }
+        let mut next = game.clone();
+        if !next.add_wall_at(wall.0, wall.1, false) { continue; }
+
+        placed.push(format!("{}{}", game.coord_to_algebraic(wall.0), wall.1));
+        let distance = next.distance_to_goal(player);
+        let mut walls = placed.clone();
+        walls.sort();
+        if distance > best.1 || (distance == best.1 && (walls.len(), &walls) < (best.0.len(), &best.0)) {
+            *best = (walls, distance);
+        }
+        extend_wall_combo(&next.with_first_player(placer), player, walls_left - 1, Some(wall), placed, best);
+        placed.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deeper_search_sees_forced_win() {
+        // 5x5, no walls left: P1 on c3 reaches c5 in two moves and P2 (on a5,
+        // four steps from its goal) can't stop it. Depth 3 sees the win.
+        let game = Quoridor::new(5, 3, Some(" / / c3 a5 / 0 0 / 1"));
+        let strategy = MinimaxStrategy::new("", Vec::new(), 2);
+
+        let results = evaluate_at_depths(&game, &strategy, &[1, 2, 3]);
+        assert_eq!(results.iter().map(|r| r.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(results[0].1.is_finite());
+        assert_eq!(results[2].1, f64::INFINITY);
+        assert_eq!(results[2].2, "c4");
+    }
+
+    #[test]
+    fn test_requested_order_and_empty_requests() {
+        let game = Quoridor::mini();
+        let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+        let results = evaluate_at_depths(&game, &strategy, &[2, 0, 1]);
+        assert_eq!(results.iter().map(|r| r.0).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(results.iter().all(|r| !r.2.is_empty()));
+        assert!(evaluate_at_depths(&game, &strategy, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_principal_variation_follows_forced_win() {
+        let game = Quoridor::new(5, 3, Some(" / / c3 a5 / 0 0 / 1"));
+        let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+
+        let line = principal_variation(&game, &strategy, 3);
+        assert_eq!(line.len(), 3);
+        assert_eq!(line[0], "c4");
+        assert_eq!(line[2], "c5"); // The win ends the line
+        assert!(game.validate_sequence(&line).is_ok());
+
+        // Longer requests stop at the win; finished games have no line
+        assert_eq!(principal_variation(&game, &strategy, 5).len(), 3);
+        let over = Quoridor::new(5, 3, Some(" / / c5 a5 / 0 0 / 2"));
+        assert_eq!(over.winner(), Some(crate::player::Player::Player1));
+        assert!(principal_variation(&over, &strategy, 3).is_empty());
+    }
+
+    #[test]
+    fn test_branching_from_start() {
+        // 3 pawn steps and 64 + 64 walls from the 9x9 start
+        let stats = branching_stats(&Quoridor::standard(), 1);
+        assert_eq!(stats.plies.len(), 1);
+        let first = &stats.plies[0];
+        assert_eq!(first.positions, 1);
+        assert_eq!((first.pawn.min, first.pawn.max), (3, 3));
+        assert_eq!((first.wall.min, first.wall.max), (128, 128));
+        assert_eq!(first.total.average, 131.0);
+
+        // Every reply position is expanded at the next ply on a small board
+        let mini = Quoridor::mini();
+        let stats = branching_stats(&mini, 2);
+        assert_eq!(stats.plies[1].positions, mini.all_legal_moves(mini.active_player).len());
+        assert!(stats.plies[1].wall.max <= stats.plies[0].wall.max);
+        assert!(branching_stats(&mini, 0).plies.is_empty());
+    }
+
+    #[test]
+    fn test_best_wall_combo() {
+        // One wall: the same as the opponent's top-ranked blocking wall
+        let game = Quoridor::new(9, 10, Some("d4 / / e3 e7 / 10 10 / 1"));
+        let (walls, distance) = best_wall_combo(&game, Player::Player1, 1);
+        let expected = game.find_maximally_blocking_walls(Player::Player2, 1);
+        assert_eq!((walls[0].clone(), distance), expected[0]);
+        assert!(distance > game.distance_to_goal(Player::Player1));
+
+        // Two walls do at least as well, are placed legally, and use the opponent's hand
+        let mini = Quoridor::mini();
+        let (one, one_distance) = best_wall_combo(&mini, Player::Player2, 1);
+        let (two, two_distance) = best_wall_combo(&mini, Player::Player2, 5);
+        assert_eq!(one.len(), 1);
+        assert!(two.len() <= MAX_COMBO_WALLS);
+        assert!(two_distance >= one_distance);
+        let mut placed = mini.clone();
+        for wall in &two {
+            assert!(placed.is_wall_legal(wall), "{}", wall);
+            placed.apply_move(wall, true);
+            placed = placed.with_first_player(Player::Player1);
+        }
+        assert_eq!(placed.distance_to_goal(Player::Player2), two_distance);
+
+        let empty_handed = Quoridor::new(5, 3, Some(" / / c1 c5 / 0 3 / 1"));
+        assert_eq!(best_wall_combo(&empty_handed, Player::Player2, 2), (Vec::new(), 4));
+    }
+}
@@ -0,0 +1,284 @@
+// --- File: quoridor-project/quoridor-core/src/bitboard.rs ---
+
+//! Compact, graph-free board representation for move generation.
+//!
+//! Walls are stored as `u128` bitsets (one bit per square, index `row * size + col`),
+//! so boards up to 11x11 fit. Pawn move generation works directly on the bitsets;
+//! pathfinding (wall legality, distances) still goes through the graph in `Quoridor`.
+//! Enabled with the `bitboard` feature.
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use crate::types::Coord;
+use crate::utils::coord_to_algebraic;
+
+/// Largest board whose squares fit in a `u128`.
+pub const MAX_BITBOARD_SIZE: usize = 11;
+
+/// Bitset snapshot of the walls and pawns of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitBoard {
+    pub size: usize,
+    /// Reference squares of placed horizontal walls.
+    pub hwalls: u128,
+    /// Reference squares of placed vertical walls.
+    pub vwalls: u128,
+    /// Whether a pawn may jump the opponent, as in `RuleSet::allow_jumps`.
+    pub allow_jumps: bool,
+    // Derived edge masks used by move generation:
+    // bit i in `south` = the edge from square i to the square below it is blocked,
+    // bit i in `east` = the edge from square i to the square right of it is blocked.
+    south: u128,
+    east: u128,
+    pawns: [Coord; 2],
+}
+
+impl BitBoard {
+    /// Creates an empty board with the pawns on their usual start squares,
+    /// under the standard rules. Returns None if the board is too large for the bitsets.
+    pub fn new(size: usize) -> Option<Self> {
+        if !(2..=MAX_BITBOARD_SIZE).contains(&size) { return None; }
+        Some(BitBoard {
+            size,
+            hwalls: 0,
+            vwalls: 0,
+            allow_jumps: true,
+            south: 0,
+            east: 0,
+            pawns: [(size - 1, size / 2), (0, size / 2)],
+        })
+    }
+
+    /// Snapshots the walls, pawns and jump rule of a game. Returns None if the board is too large.
+    pub fn from_game(game: &Quoridor) -> Option<Self> {
+        let mut board = BitBoard::new(game.size)?;
+        board.allow_jumps = game.rules.allow_jumps;
+        for &coord in &game.hwall_positions {
+            board.add_wall(coord, 'h');
+        }
+        for &coord in &game.vwall_positions {
+            board.add_wall(coord, 'v');
+        }
+        for player in [Player::Player1, Player::Player2] {
+            if let Some(&pos) = game.pawn_positions.get(&player) {
+                board.set_pawn(player, pos);
+            }
+        }
+        Some(board)
+    }
+
+    fn bit(&self, (r, c): Coord) -> u128 {
+        1u128 << (r * self.size + c)
+    }
+
+    /// Records a wall and blocks the edges it covers, using the same geometry as
+    /// the graph (`get_blocked_edges_by_wall`). No legality checks are performed.
+    pub fn add_wall(&mut self, (r, c): Coord, orientation: char) {
+        if r >= self.size || c + 1 >= self.size { return; }
+        match orientation {
+            'h' if r > 0 => {
+                // Blocks (r-1, c) <=> (r, c) and (r-1, c+1) <=> (r, c+1)
+                self.hwalls |= self.bit((r, c));
+                self.south |= self.bit((r - 1, c)) | self.bit((r - 1, c + 1));
+            }
+            'v' => {
+                // Blocks (r, c) <=> (r, c+1) and, unless on the top row, (r-1, c) <=> (r-1, c+1)
+                self.vwalls |= self.bit((r, c));
+                self.east |= self.bit((r, c));
+                if r > 0 {
+                    self.east |= self.bit((r - 1, c));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves a player's pawn to `pos`.
+    pub fn set_pawn(&mut self, player: Player, pos: Coord) {
+        self.pawns[player.number() - 1] = pos;
+    }
+
+    /// Current square of a player's pawn.
+    pub fn pawn(&self, player: Player) -> Coord {
+        self.pawns[player.number() - 1]
+    }
+
+    /// Returns the square one step from `pos` in direction (dr, dc),
+    /// or None if that step leaves the board or crosses a wall.
+    fn step(&self, (r, c): Coord, (dr, dc): (i32, i32)) -> Option<Coord> {
+        match (dr, dc) {
+            (-1, 0) if r > 0 && self.south & self.bit((r - 1, c)) == 0 => Some((r - 1, c)),
+            (1, 0) if r + 1 < self.size && self.south & self.bit((r, c)) == 0 => Some((r + 1, c)),
+            (0, -1) if c > 0 && self.east & self.bit((r, c - 1)) == 0 => Some((r, c - 1)),
+            (0, 1) if c + 1 < self.size && self.east & self.bit((r, c)) == 0 => Some((r, c + 1)),
+            _ => None,
+        }
+    }
+
+    /// Destination squares of the player's legal pawn moves, including straight
+    /// and diagonal jumps over the opponent unless `allow_jumps` is off. Same
+    /// rules as `Quoridor::get_legal_moves`.
+    pub fn legal_move_coords(&self, player: Player) -> Vec<Coord> {
+        const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+        let own_pos = self.pawn(player);
+        let opponent_pos = self.pawn(player.opponent());
+        let mut moves = Vec::with_capacity(5);
+
+        for dir in DIRECTIONS {
+            let Some(target) = self.step(own_pos, dir) else { continue; };
+            if target != opponent_pos {
+                moves.push(target);
+                continue;
+            }
+
+            // Adjacent to opponent: no move that way at all if the variant forbids jumps
+            if !self.allow_jumps { continue; }
+
+            // Otherwise a straight jump if the square behind is open
+            if let Some(jump) = self.step(opponent_pos, dir) {
+                moves.push(jump);
+                continue;
+            }
+
+            // Otherwise the diagonal jumps sideways from the opponent
+            for side in [(dir.1, dir.0), (-dir.1, -dir.0)] {
+                if let Some(diagonal) = self.step(opponent_pos, side) {
+                    moves.push(diagonal);
+                }
+            }
+        }
+        moves
+    }
+
+    /// Legal pawn moves for the player in algebraic notation.
+    pub fn get_legal_moves(&self, player: Player) -> Vec<String> {
+        self.legal_move_coords(player)
+            .into_iter()
+            .map(|coord| coord_to_algebraic(coord, self.size))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::RuleSet;
+    use rand::rngs::StdRng;
+    use rand::prelude::*;
+    use std::collections::HashSet;
+    use std::time::Instant;
+
+    /// Plays random legal moves from the standard start and collects every position seen.
+    fn random_positions(seed: u64, games: usize, max_plies: usize) -> Vec<Quoridor> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut positions = Vec::new();
+        for _ in 0..games {
+            let mut game = Quoridor::standard();
+            for _ in 0..max_plies {
+                positions.push(game.clone());
+                let moves = game.all_legal_moves(game.active_player);
+                let Some(mv) = moves.choose(&mut rng) else { break; };
+                let moved = game.apply_move(mv, true);
+                if !moved || game.distance_to_goal(Player::Player1) == 0 || game.distance_to_goal(Player::Player2) == 0 {
+                    break;
+                }
+            }
+        }
+        positions
+    }
+
+    #[test]
+    fn test_rejects_oversized_boards() {
+        assert!(BitBoard::new(MAX_BITBOARD_SIZE).is_some());
+        assert!(BitBoard::new(MAX_BITBOARD_SIZE + 1).is_none());
+    }
+
+    #[test]
+    fn test_jumps_match_graph() {
+        // Straight jump, jump blocked by a wall (diagonals), and top-edge jump
+        for state in [" / / e5 e6 / 10 10 / 1", "e6 / / e5 e6 / 10 10 / 1", " / / e8 e9 / 10 10 / 1"] {
+            let game = Quoridor::new(9, 10, Some(state));
+            let board = BitBoard::from_game(&game).unwrap();
+            let expected: HashSet<String> = game.get_legal_moves(Player::Player1).into_iter().collect();
+            let moves: HashSet<String> = board.get_legal_moves(Player::Player1).into_iter().collect();
+            assert_eq!(moves, expected, "state {}", state);
+        }
+    }
+
+    #[test]
+    fn test_no_jump_rules_match_graph() {
+        let rules = RuleSet { allow_jumps: false };
+        for state in [" / / e5 e6 / 10 10 / 1", "e6 / / e5 e6 / 10 10 / 1", " / / e8 e9 / 10 10 / 1"] {
+            let game = Quoridor::new(9, 10, Some(state)).with_rules(rules);
+            let board = BitBoard::from_game(&game).unwrap();
+            assert!(!board.allow_jumps);
+            for player in [Player::Player1, Player::Player2] {
+                let expected: HashSet<String> = game.get_legal_moves(player).into_iter().collect();
+                let moves: HashSet<String> = board.get_legal_moves(player).into_iter().collect();
+                assert_eq!(moves, expected, "state {}", state);
+            }
+        }
+        for game in random_positions(5, 10, 60) {
+            let game = game.with_rules(rules);
+            let board = BitBoard::from_game(&game).unwrap();
+            for player in [Player::Player1, Player::Player2] {
+                let mut moves = board.get_legal_moves(player);
+                let mut expected = game.get_legal_moves(player);
+                moves.sort();
+                expected.sort();
+                assert_eq!(moves, expected, "state {}", game.state_string);
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_positions_match_graph() {
+        for game in random_positions(11, 20, 60) {
+            let board = BitBoard::from_game(&game).unwrap();
+            for player in [Player::Player1, Player::Player2] {
+                let mut moves = board.get_legal_moves(player);
+                let mut expected = game.get_legal_moves(player);
+                moves.sort();
+                expected.sort();
+                assert_eq!(moves, expected, "state {}", game.state_string);
+            }
+        }
+    }
+
+    /// Rough move-generation benchmark against the graph version.
+    /// Run with `cargo test --features bitboard --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_move_generation_against_graph() {
+        const ROUNDS: usize = 200;
+        let games = random_positions(3, 20, 60);
+        let boards: Vec<BitBoard> = games.iter().map(|g| BitBoard::from_game(g).unwrap()).collect();
+        let calls = ROUNDS * games.len() * 2;
+
+        let start = Instant::now();
+        let mut total = 0;
+        for _ in 0..ROUNDS {
+            for game in &games {
+                total += game.get_legal_moves(Player::Player1).len() + game.get_legal_moves(Player::Player2).len();
+            }
+        }
+        let graph_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut bit_total = 0;
+        for _ in 0..ROUNDS {
+            for board in &boards {
+                bit_total += board.get_legal_moves(Player::Player1).len() + board.get_legal_moves(Player::Player2).len();
+            }
+        }
+        let bit_time = start.elapsed();
+
+        assert_eq!(total, bit_total);
+        println!(
+            "{} calls: graph {:.0} ns/call, bitboard {:.0} ns/call",
+            calls,
+            graph_time.as_nanos() as f64 / calls as f64,
+            bit_time.as_nanos() as f64 / calls as f64,
+        );
+    }
+}
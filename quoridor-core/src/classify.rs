@@ -0,0 +1,108 @@
+// --- File: quoridor-project/quoridor-core/src/classify.rs ---
+
+//! Legal moves grouped by kind, for UIs that present them separately.
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use crate::utils::abs_diff;
+
+/// A player's legal moves in algebraic notation, bucketed by kind.
+/// Each bucket keeps the order of the generator it came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassifiedMoves {
+    /// One step towards the player's goal line.
+    pub forward: Vec<String>,
+    /// One step along the current row.
+    pub sideways: Vec<String>,
+    /// One step away from the goal line.
+    pub backward: Vec<String>,
+    /// Moves over the opponent's pawn, straight or diagonal.
+    pub jumps: Vec<String>,
+    /// Horizontal wall placements, e.g. "e3h".
+    pub horizontal_walls: Vec<String>,
+    /// Vertical wall placements, e.g. "e3v".
+    pub vertical_walls: Vec<String>,
+}
+
+impl ClassifiedMoves {
+    /// All pawn moves, in bucket order.
+    pub fn pawn_moves(&self) -> Vec<String> {
+        [&self.forward, &self.sideways, &self.backward, &self.jumps].into_iter().flatten().cloned().collect()
+    }
+
+    /// All wall placements, horizontal first.
+    pub fn wall_moves(&self) -> Vec<String> {
+        self.horizontal_walls.iter().chain(&self.vertical_walls).cloned().collect()
+    }
+}
+
+impl Quoridor {
+    /// Splits `player`'s legal moves into pawn steps by direction relative to
+    /// its goal line, jumps, and walls by orientation. Player 1 moves up the
+    /// board (towards row 0), Player 2 down.
+    pub fn classified_legal_moves(&self, player: Player) -> ClassifiedMoves {
+        let mut classified = ClassifiedMoves::default();
+        let (row, col) = self.pawn_positions[&player];
+        let forward_row = |r: usize| match player {
+            Player::Player1 => r + 1 == row,
+            Player::Player2 => r == row + 1,
+        };
+
+        for move_str in self.get_legal_moves(player) {
+            let (to_row, to_col) = self.algebraic_to_coord(&move_str);
+            let bucket = if abs_diff(to_row, row) + abs_diff(to_col, col) > 1 {
+                &mut classified.jumps
+            } else if to_row == row {
+                &mut classified.sideways
+            } else if forward_row(to_row) {
+                &mut classified.forward
+            } else {
+                &mut classified.backward
+            };
+            bucket.push(move_str);
+        }
+
+        for wall_move in self.get_legal_walls(player) {
+            if wall_move.ends_with('h') {
+                classified.horizontal_walls.push(wall_move);
+            } else {
+                classified.vertical_walls.push(wall_move);
+            }
+        }
+        classified
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starting_position() {
+        let game = Quoridor::standard();
+        let moves = game.classified_legal_moves(Player::Player1);
+        assert_eq!(moves.forward, vec!["e2"]);
+        let mut sideways = moves.sideways.clone();
+        sideways.sort();
+        assert_eq!(sideways, vec!["d1", "f1"]);
+        assert!(moves.backward.is_empty());
+        assert!(moves.jumps.is_empty());
+        assert_eq!(moves.horizontal_walls.len(), 64);
+        assert_eq!(moves.vertical_walls.len(), 64);
+
+        // Player 2 moves down the board
+        assert_eq!(game.classified_legal_moves(Player::Player2).forward, vec!["e8"]);
+    }
+
+    #[test]
+    fn test_jumps_and_backward_steps() {
+        // Pawns face each other on e4/e5 with P1 to move
+        let game = Quoridor::new(9, 10, Some(" / / e4 e5 / 10 10 / 1"));
+        let moves = game.classified_legal_moves(Player::Player1);
+        assert_eq!(moves.jumps, vec!["e6"]);
+        assert_eq!(moves.backward, vec!["e3"]);
+        assert!(moves.forward.is_empty()); // e5 is occupied
+        assert_eq!(moves.pawn_moves().len(), game.get_legal_moves(Player::Player1).len());
+        assert_eq!(moves.wall_moves().len(), game.get_legal_walls(Player::Player1).len());
+    }
+}
@@ -0,0 +1,163 @@
+// --- File: quoridor-project/quoridor-core/src/diff.rs ---
+
+//! Compares two positions on the same board, for debugging and logging.
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use crate::types::Coord;
+use std::fmt;
+
+/// What changed between two positions, in algebraic notation.
+/// Lists are sorted, so equal diffs compare equal.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionDiff {
+    /// Pawns that changed square: (player, from, to).
+    pub pawn_moves: Vec<(Player, String, String)>,
+    /// Walls present only in the second position, e.g. "e3h".
+    pub added_walls: Vec<String>,
+    /// Walls present only in the first position.
+    pub removed_walls: Vec<String>,
+    /// Change in walls in hand per player (only non-zero changes).
+    pub wall_count_changes: Vec<(Player, isize)>,
+}
+
+impl PositionDiff {
+    /// True if the two positions have the same pawns, walls and wall counts.
+    pub fn is_empty(&self) -> bool {
+        self.pawn_moves.is_empty()
+            && self.added_walls.is_empty()
+            && self.removed_walls.is_empty()
+            && self.wall_count_changes.is_empty()
+    }
+}
+
+impl fmt::Display for PositionDiff {
+    /// One line, e.g. "player1 e1->e2; +e3h; player1 walls -1".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no change");
+        }
+        let mut parts = Vec::new();
+        parts.extend(self.pawn_moves.iter().map(|(player, from, to)| format!("{} {}->{}", player, from, to)));
+        parts.extend(self.added_walls.iter().map(|wall| format!("+{}", wall)));
+        parts.extend(self.removed_walls.iter().map(|wall| format!("-{}", wall)));
+        parts.extend(self.wall_count_changes.iter().map(|(player, change)| format!("{} walls {:+}", player, change)));
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl Quoridor {
+    /// Reports what changed from this position to `other`: pawn moves, walls
+    /// added or removed, and changes in walls in hand.
+    /// Panics if the two games have different board sizes.
+    pub fn diff(&self, other: &Quoridor) -> PositionDiff {
+        assert_eq!(self.size, other.size, "Cannot diff positions on different board sizes");
+        let mut diff = PositionDiff::default();
+
+        for player in [Player::Player1, Player::Player2] {
+            let before = self.pawn_positions.get(&player);
+            let after = other.pawn_positions.get(&player);
+            if let (Some(&from), Some(&to)) = (before, after) {
+                if from != to {
+                    diff.pawn_moves.push((player, self.coord_to_algebraic(from), self.coord_to_algebraic(to)));
+                }
+            }
+
+            let count = |game: &Quoridor| game.walls_available.get(&player).copied().unwrap_or(0) as isize;
+            let change = count(other) - count(self);
+            if change != 0 {
+                diff.wall_count_changes.push((player, change));
+            }
+        }
+
+        for (orientation, before, after) in [
+            ('h', &self.hwall_positions, &other.hwall_positions),
+            ('v', &self.vwall_positions, &other.vwall_positions),
+        ] {
+            let wall_move = |&coord: &Coord| format!("{}{}", self.coord_to_algebraic(coord), orientation);
+            diff.added_walls.extend(after.difference(before).map(wall_move));
+            diff.removed_walls.extend(before.difference(after).map(wall_move));
+        }
+        diff.added_walls.sort();
+        diff.removed_walls.sort();
+        diff
+    }
+
+    /// Recovers the move that turns `before` into `after`, e.g. for reading
+    /// consecutive positions from a log. Returns None unless `after` is exactly
+    /// the result of one legal move by `before`'s side to move.
+    pub fn move_between(before: &Quoridor, after: &Quoridor) -> Option<String> {
+        if before.size != after.size { return None; }
+        let diff = before.diff(after);
+        let candidate = match (diff.pawn_moves.as_slice(), diff.added_walls.as_slice()) {
+            ([(player, _, to)], []) if *player == before.active_player => to.clone(),
+            ([], [wall]) => wall.clone(),
+            _ => return None,
+        };
+        if !before.all_legal_moves(before.active_player).contains(&candidate) { return None; }
+
+        // Replay it to rule out other differences (wall counts, side to move)
+        let mut replayed = before.clone();
+        let applied = replayed.apply_move(&candidate, true);
+        (applied && replayed.state_string == after.state_string).then_some(candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_around_wall_placement() {
+        let before = Quoridor::new(9, 10, Some(" / / e2 e8 / 10 10 / 1"));
+        let mut after = before.clone();
+        assert!(after.add_wall("e3h", false, true));
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.added_walls, vec!["e3h"]);
+        assert!(diff.removed_walls.is_empty());
+        assert!(diff.pawn_moves.is_empty());
+        assert_eq!(diff.wall_count_changes, vec![(Player::Player1, -1)]);
+        assert_eq!(diff.to_string(), "+e3h; player1 walls -1");
+
+        // Reversed, the wall is removed and the count goes back up
+        let back = after.diff(&before);
+        assert_eq!(back.removed_walls, vec!["e3h"]);
+        assert_eq!(back.wall_count_changes, vec![(Player::Player1, 1)]);
+    }
+
+    #[test]
+    fn test_diff_pawn_move_and_no_change() {
+        let before = Quoridor::standard();
+        let mut after = before.clone();
+        assert!(after.move_pawn("e2", true));
+        assert_eq!(before.diff(&after).to_string(), "player1 e1->e2");
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_move_between_pawn_move() {
+        let before = Quoridor::standard();
+        let mut after = before.clone();
+        assert!(after.move_pawn("e2", true));
+        assert_eq!(Quoridor::move_between(&before, &after), Some("e2".to_string()));
+
+        // Two moves apart, or no change, is not a single move
+        let mut later = after.clone();
+        assert!(later.move_pawn("e8", true));
+        assert_eq!(Quoridor::move_between(&before, &later), None);
+        assert_eq!(Quoridor::move_between(&before, &before), None);
+    }
+
+    #[test]
+    fn test_move_between_wall_move() {
+        let before = Quoridor::new(9, 10, Some(" / / e2 e8 / 10 10 / 2"));
+        let mut after = before.clone();
+        assert!(after.add_wall("d2v", false, true));
+        assert_eq!(Quoridor::move_between(&before, &after), Some("d2v".to_string()));
+
+        // The same wall without the wall count dropping is not a legal move
+        let tampered = Quoridor::new(9, 10, Some(" / d2 / e2 e8 / 10 10 / 1"));
+        assert_eq!(Quoridor::move_between(&before, &tampered), None);
+    }
+}
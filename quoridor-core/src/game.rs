@@ -5,12 +5,91 @@
 use petgraph::algo::dijkstra;
 use crate::types::Coord;
 use crate::player::Player;
-use crate::utils::{algebraic_to_coord, coord_to_algebraic};
-use crate::graph::{self, initialize_board_graph, get_blocked_edges_by_wall, check_wall_path_blocking, get_shortest_path_len}; // Use graph module
+use crate::utils::{algebraic_to_coord, classify_move, coord_to_algebraic, MoveKind};
+use crate::graph::{self, initialize_board_graph, build_neighbor_cache, NeighborList, get_blocked_edges_by_wall, check_wall_path_blocking, get_shortest_path_len}; // Use graph module
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map::Entry, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
 use petgraph::graph::{NodeIndex, UnGraph};
 
+/// Optional rule variations. The default is the standard game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleSet {
+    /// Pawns may jump over an adjacent opponent (straight, or diagonally when
+    /// the straight jump is blocked). When false, the opponent's square is
+    /// simply unavailable and there is no alternative move.
+    pub allow_jumps: bool,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet { allow_jumps: true }
+    }
+}
+
+/// Rough stage of the game, see `Quoridor::game_phase`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Opening,
+    Midgame,
+    Endgame,
+}
+
+/// What `Quoridor::best_wall_among` scores a wall by, for the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WallObjective {
+    /// How much the wall lengthens the opponent's shortest path.
+    BlockOpponent,
+    /// How little it lengthens our own (scored as minus our increase), for a
+    /// wall that spends a turn without costing us tempo.
+    ProtectSelf,
+    /// The opponent's increase minus our own.
+    NetSwing,
+}
+
+/// The moves played on a game, oldest first (see `Quoridor::move_history`).
+/// Stored as a list linked back from the latest move, so a clone shares every
+/// earlier move and copying a game mid-search costs one pointer however long
+/// it has run. The slice is only built when asked for.
+#[derive(Default)]
+struct MoveHistory {
+    last: Option<Arc<HistoryNode>>,
+    moves: OnceLock<Vec<String>>,
+}
+
+struct HistoryNode {
+    move_str: String,
+    previous: Option<Arc<HistoryNode>>,
+}
+
+impl Clone for MoveHistory {
+    fn clone(&self) -> Self {
+        // The built slice is left behind; searches rarely ask for it
+        MoveHistory { last: self.last.clone(), moves: OnceLock::new() }
+    }
+}
+
+impl MoveHistory {
+    fn push(&mut self, move_str: String) {
+        let previous = self.last.take();
+        self.last = Some(Arc::new(HistoryNode { move_str, previous }));
+        self.moves = OnceLock::new();
+    }
+
+    fn as_slice(&self) -> &[String] {
+        self.moves.get_or_init(|| {
+            let mut moves = Vec::new();
+            let mut node = self.last.as_deref();
+            while let Some(current) = node {
+                moves.push(current.move_str.clone());
+                node = current.previous.as_deref();
+            }
+            moves.reverse();
+            moves
+        })
+    }
+}
+
 /// Represents the state of a Quoridor game.
 #[derive(Clone)]
 pub struct Quoridor {
@@ -18,6 +97,9 @@ pub struct Quoridor {
     pub walls: usize, // Initial walls per player
     pub graph: UnGraph<Coord, ()>,
     pub node_indices: HashMap<Coord, NodeIndex>,
+    // Per-node neighbour cache (indexed by NodeIndex::index()), kept in sync with
+    // `graph` whenever a wall removes edges so move generation skips graph walks
+    pub neighbors: Vec<NeighborList>,
     // Store wall positions by the bottom-left coord they touch
     pub hwall_positions: HashSet<Coord>,
     pub vwall_positions: HashSet<Coord>,
@@ -29,22 +111,87 @@ pub struct Quoridor {
     pub state_string: String,
     pub previous_state: String, // State before the last move
     pub last_move: String,      // Last move made (algebraic notation)
+    pub rules: RuleSet,         // Variant rules (standard by default)
+    // Half-moves (pawn moves and walls) played since this instance was created;
+    // positions loaded from a state string start counting from 0
+    ply: usize,
+    // The moves behind `ply`, in order (see move_history)
+    history: MoveHistory,
 }
 
 impl Quoridor {
     /// Creates a new Quoridor game instance.
     /// `state_string`: Optional FEN-like string to load a specific state.
+    /// Panics on a malformed state string, including one whose walls leave a
+    /// player with no path to its goal; use `try_new` for state strings from
+    /// users or files.
     pub fn new(size: usize, walls: usize, state_string: Option<&str>) -> Self {
-        if size < 3 || size % 2 == 0 {
+        match Quoridor::try_new(size, walls, state_string) {
+            Ok(game) => game,
+            Err(player) => panic!("State string '{}' leaves {} with no path to goal", state_string.unwrap_or_default(), player),
+        }
+    }
+
+    /// Like `new`, but returns the walled-off player as an error when the state
+    /// string's walls leave a player with no path to its goal. Other malformed
+    /// state strings still panic, as in `new`.
+    pub fn try_new(size: usize, walls: usize, state_string: Option<&str>) -> Result<Self, Player> {
+        let mut game = Quoridor::empty(size, walls);
+        if let Some(state_str) = state_string {
+            game.parse_state_string(state_str)?;
+        }
+        Ok(game)
+    }
+
+    /// Like `new`, but loads a state string even if its walls leave a player
+    /// with no path to its goal, for tests of stuck or walled-off pawns.
+    /// Only built for tests or with the `testing` feature.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn new_unchecked(size: usize, walls: usize, state_string: &str) -> Self {
+        let mut game = Quoridor::empty(size, walls);
+        let _ = game.parse_state_string(state_string);
+        game
+    }
+
+    /// Like `try_new`, but also rejects state strings whose walls overlap, cross,
+    /// or hang off the board (strict parsing, for fuzzing and puzzle generation).
+    /// Returns a description of the first problem: the first wall that doesn't
+    /// fit, or the walled-off player.
+    pub fn try_new_strict(size: usize, walls: usize, state_string: &str) -> Result<Self, String> {
+        let mut game = Quoridor::empty(size, walls);
+        game.parse_state_string_strict(state_string)?;
+        Ok(game)
+    }
+
+    /// Checks a board configuration before construction, for callers (such as the
+    /// WASM bindings) where the panic in `new` would be unrecoverable.
+    /// The size must be odd and at least 3, and the two players' walls together
+    /// must fit on the (size - 1)² wall slots.
+    pub fn check_config(size: usize, walls: usize) -> Result<(), String> {
+        if size < 3 || size.is_multiple_of(2) {
+            return Err(format!("Board size must be an odd number >= 3, got {}", size));
+        }
+        let slots = (size - 1) * (size - 1);
+        if walls.saturating_mul(2) > slots {
+            return Err(format!("{} walls per player do not fit on a {}x{} board (at most {})", walls, size, size, slots / 2));
+        }
+        Ok(())
+    }
+
+    /// A game in the default starting position, before any state string is applied.
+    fn empty(size: usize, walls: usize) -> Self {
+        if size < 3 || size.is_multiple_of(2) {
             panic!("Board size must be an odd number >= 3");
         }
         let (graph, node_indices) = initialize_board_graph(size);
+        let neighbors = build_neighbor_cache(&graph);
 
         let mut game = Quoridor {
             size,
             walls,
             graph,
             node_indices,
+            neighbors,
             hwall_positions: HashSet::new(),
             vwall_positions: HashSet::new(),
             pawn_positions: HashMap::new(),
@@ -54,33 +201,130 @@ impl Quoridor {
             state_string: String::new(),
             previous_state: String::new(),
             last_move: "None".to_string(),
+            rules: RuleSet::default(),
+            ply: 0,
+            history: MoveHistory::default(),
         };
 
         // Define goal lines
         game.goal_positions.insert(Player::Player1, (0..size).map(|c| (0, c)).collect()); // Top row for P1
         game.goal_positions.insert(Player::Player2, (0..size).map(|c| (size - 1, c)).collect()); // Bottom row for P2
 
-        // Initialize state
-        if let Some(state_str) = state_string {
-            game.parse_state_string(state_str); // Load from string
-        } else {
-            // Default starting positions
-            let center = size / 2;
-            game.pawn_positions.insert(Player::Player1, (size - 1, center)); // P1 starts at bottom center
-            game.pawn_positions.insert(Player::Player2, (0, center));       // P2 starts at top center
-            game.walls_available.insert(Player::Player1, walls);
-            game.walls_available.insert(Player::Player2, walls);
-            game.active_player = Player::Player1;
-            game.update_state_string(true); // Generate initial state string
-        }
+        // Default starting positions (replaced when a state string is loaded)
+        let center = size / 2;
+        game.pawn_positions.insert(Player::Player1, (size - 1, center)); // P1 starts at bottom center
+        game.pawn_positions.insert(Player::Player2, (0, center));       // P2 starts at top center
+        game.walls_available.insert(Player::Player1, walls);
+        game.walls_available.insert(Player::Player2, walls);
+        game.active_player = Player::Player1;
+        game.update_state_string(true); // Generate initial state string
 
         game
     }
 
+    /// The standard game: 9x9 board, 10 walls per player.
+    pub fn standard() -> Self {
+        Quoridor::new(9, 10, None)
+    }
+
+    /// A 5x5 board with 3 walls per player, small enough for fast tests
+    /// and exhaustive search.
+    pub fn mini() -> Self {
+        Quoridor::new(5, 3, None)
+    }
+
+    /// The same position with the players' roles swapped: the board is flipped
+    /// top-bottom and pawns, walls in hand and the side to move are exchanged,
+    /// so Player 1's evaluation of the result is the negation of this one.
+    /// (Left-right mirroring keeps the roles and leaves evaluations unchanged.)
+    /// Flipping twice gives back the original position; the ply count restarts at 0.
+    pub fn flipped(&self) -> Quoridor {
+        let n = self.size;
+        // A wall's stored square is the lower-left one it touches, so walls
+        // reflect to row n - r rather than n - 1 - r like pawns
+        let flip_pawn = |(r, c): Coord| self.coord_to_algebraic((n - 1 - r, c));
+        let flip_walls = |positions: &HashSet<Coord>| positions.iter().map(|&(r, c)| self.coord_to_algebraic((n - r, c))).collect::<String>();
+        let state = format!(
+            "{} / {} / {} {} / {} {} / {}",
+            flip_walls(&self.hwall_positions), flip_walls(&self.vwall_positions),
+            flip_pawn(self.pawn_positions[&Player::Player2]), flip_pawn(self.pawn_positions[&Player::Player1]),
+            self.walls_available[&Player::Player2], self.walls_available[&Player::Player1],
+            self.active_player.opponent().number(),
+        );
+        Quoridor::new(n, self.walls, Some(&state)).with_rules(self.rules)
+    }
+
+    /// Reflects a wall through the centre of the board (a half turn), giving
+    /// the wall a symmetric opponent would place: e3h becomes d6h on 9x9.
+    /// A wall's stored square is the lower-left one of the four it touches,
+    /// and after a half turn that corner is the upper-right one, so both the
+    /// row and the file land one further than a pawn's (n - 1 - r, n - 1 - c).
+    /// The orientation is unchanged. Returns None when `coord` is not a wall
+    /// square (row 0 or the last file), since its mirror would be off the board.
+    pub fn mirror_wall(&self, (r, c): Coord, orientation: char) -> Option<(Coord, char)> {
+        let row = self.size.checked_sub(r).filter(|&row| row < self.size)?;
+        let col = self.size.checked_sub(2 + c)?;
+        Some(((row, col), orientation))
+    }
+
+    /// Plays this game under a rule variant, e.g.
+    /// `Quoridor::standard().with_rules(RuleSet { allow_jumps: false })`.
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Sets which player moves first, for variants and handicap setups, e.g.
+    /// `Quoridor::standard().with_first_player(Player::Player2)`.
+    pub fn with_first_player(mut self, player: Player) -> Self {
+        self.active_player = player;
+        self.update_state_string(true);
+        self
+    }
+
+    /// Starts the pawns on custom squares in algebraic notation instead of the
+    /// middle of the back rows, e.g. `with_start_squares("c1", "g9")` for a
+    /// tutorial setup. Fails if a square is not on the board or both pawns
+    /// would share one. Goal lines don't move: Player 1 still heads for the
+    /// top row and Player 2 for the bottom row.
+    pub fn with_start_squares(mut self, p1_square: &str, p2_square: &str) -> Result<Self, String> {
+        let square = |alg: &str| match classify_move(alg, self.size) {
+            MoveKind::Pawn(coord) => Ok(coord),
+            _ => Err(format!("'{}' is not a square on a {}x{} board", alg, self.size, self.size)),
+        };
+        let (p1, p2) = (square(p1_square)?, square(p2_square)?);
+        if p1 == p2 {
+            return Err(format!("both pawns cannot start on {}", p1_square));
+        }
+        self.pawn_positions.insert(Player::Player1, p1);
+        self.pawn_positions.insert(Player::Player2, p2);
+        self.update_state_string(true);
+        Ok(self)
+    }
+
+    /// Starts both pawns on their home rows but off the centre file, e.g.
+    /// `with_start_columns(2, 6)` puts Player 1 on c1 and Player 2 on g9 on 9x9.
+    /// Columns count from 0 (file a); `Quoridor::new` keeps both on `size / 2`.
+    /// Fails if a column is off the board. See `with_start_squares` for
+    /// starts off the home rows.
+    pub fn with_start_columns(mut self, p1_col: usize, p2_col: usize) -> Result<Self, String> {
+        for col in [p1_col, p2_col] {
+            if col >= self.size {
+                return Err(format!("column {} is off a {}x{} board", col, self.size, self.size));
+            }
+        }
+        self.pawn_positions.insert(Player::Player1, (self.size - 1, p1_col));
+        self.pawn_positions.insert(Player::Player2, (0, p2_col));
+        self.update_state_string(true);
+        Ok(self)
+    }
+
      /// Parses a state string (custom format) and configures the game.
      /// Format: "h_walls/v_walls/p1_pos p2_pos/p1_walls p2_walls/active_player"
      /// Example: "e3f4/b3d5/e1 e9/8 9/1"
-     fn parse_state_string(&mut self, state_string: &str) {
+     /// Walls are applied without checks, so the loaded position is checked for
+     /// reachability at the end; returns the first walled-off player.
+     fn parse_state_string(&mut self, state_string: &str) -> Result<(), Player> {
          println!("Parsing state string: {}", state_string);
          let parts: Vec<&str> = state_string.split('/').collect();
          if parts.len() != 5 {
@@ -97,6 +341,7 @@ impl Quoridor {
          self.hwall_positions.clear();
          self.vwall_positions.clear();
          let (new_graph, new_node_indices) = initialize_board_graph(self.size);
+         self.neighbors = build_neighbor_cache(&new_graph);
          self.graph = new_graph;
          self.node_indices = new_node_indices;
 
@@ -107,9 +352,9 @@ impl Quoridor {
              for i in (0..hwall_str.len()).step_by(2) {
                  if i + 2 <= hwall_str.len() {
                      let wall_pos_alg = &hwall_str[i..i + 2];
-                     let wall_move = format!("{}h", wall_pos_alg);
+                     let wall_coord = self.algebraic_to_coord(wall_pos_alg);
                      // Use add_wall internally, skipping checks but applying graph changes
-                     self.add_wall_internal(&wall_move, true);
+                     self.add_wall_internal(wall_coord, 'h', true);
                  } else {
                       eprintln!("Warning: Malformed horizontal wall segment '{}' in state string", hwall_str);
                  }
@@ -120,8 +365,8 @@ impl Quoridor {
              for i in (0..vwall_str.len()).step_by(2) {
                  if i + 2 <= vwall_str.len() {
                      let wall_pos_alg = &vwall_str[i..i + 2];
-                     let wall_move = format!("{}v", wall_pos_alg);
-                     self.add_wall_internal(&wall_move, true);
+                     let wall_coord = self.algebraic_to_coord(wall_pos_alg);
+                     self.add_wall_internal(wall_coord, 'v', true);
                  } else {
                      eprintln!("Warning: Malformed vertical wall segment '{}' in state string", vwall_str);
                  }
@@ -159,6 +404,48 @@ impl Quoridor {
                  self.active_player, self.pawn_positions[&Player::Player1], self.pawn_positions[&Player::Player2],
                  self.walls_available[&Player::Player1], self.walls_available[&Player::Player2]);
 
+         self.validate_reachability()
+     }
+
+    /// Checks that both pawns can still reach their goal lines.
+    /// Returns the first player (Player 1 before Player 2) that is walled off.
+    pub fn validate_reachability(&self) -> Result<(), Player> {
+        for player in [Player::Player1, Player::Player2] {
+            let goals = HashMap::from([(player, self.goal_positions[&player].clone())]);
+            if !check_wall_path_blocking(&self.graph, &self.node_indices, &self.pawn_positions, &goals) {
+                return Err(player);
+            }
+        }
+        Ok(())
+    }
+
+     /// Like `parse_state_string`, but first places the listed walls one at a time
+     /// (horizontal, then vertical, in the order written) on an empty board with
+     /// the geometric checks of a real placement, failing on the first wall that
+     /// doesn't fit. Path blocking is left to the reachability check.
+     fn parse_state_string_strict(&mut self, state_string: &str) -> Result<(), String> {
+         let mut fields = state_string.split('/').map(str::trim);
+         let (hwall_str, vwall_str) = (fields.next().unwrap_or(""), fields.next().unwrap_or(""));
+
+         let mut scratch = Quoridor::empty(self.size, self.walls);
+         for (walls_str, orientation) in [(hwall_str, 'h'), (vwall_str, 'v')] {
+             let squares = walls_str.as_bytes().chunks(2).map(|pair| String::from_utf8_lossy(pair).into_owned());
+             for square in squares {
+                 let wall_move = format!("{}{}", square, orientation);
+                 if square.len() != 2 {
+                     return Err(format!("malformed wall segment '{}'", square));
+                 }
+                 let wall_coord = scratch.algebraic_to_coord(&square);
+                 let on_board = get_blocked_edges_by_wall(wall_coord, orientation, self.size).is_some();
+                 if !on_board || !scratch.wall_fits(wall_coord, orientation) {
+                     return Err(format!("wall {} overlaps or crosses an earlier wall, or is off the board", wall_move));
+                 }
+                 scratch.add_wall_internal(wall_coord, orientation, true);
+             }
+         }
+
+         self.parse_state_string(state_string)
+             .map_err(|player| format!("{} has no path to goal", player))
      }
 
       /// Updates the canonical string representation of the game state.
@@ -209,68 +496,96 @@ impl Quoridor {
     }
 
     /// Returns a list of valid pawn moves for the given player in algebraic notation.
+    /// The order is stable: by board row from the top (rank 9 down to rank 1 on 9x9),
+    /// then by file, so callers that take the first move behave reproducibly.
      pub fn get_legal_moves(&self, player: Player) -> Vec<String> {
          let opponent = player.opponent();
          let Some(own_pos) = self.pawn_positions.get(&player) else { return Vec::new(); }; // Player not found
          let Some(opponent_pos) = self.pawn_positions.get(&opponent) else { return Vec::new(); }; // Opponent not found
-         let Some(own_node) = self.node_indices.get(own_pos) else { return Vec::new(); }; // Node not found
 
-         let mut legal_coords = HashSet::new(); // Use HashSet to avoid duplicates
+         // Convert coordinates to algebraic notation
+         self.pawn_destinations(*own_pos, *opponent_pos)
+             .iter()
+             .map(|&coord| self.coord_to_algebraic(coord))
+             .collect()
+     }
+
+    /// Number of legal pawn moves for the player (jumps included), a mobility
+    /// measure: a pawn boxed in by walls or the opponent scores low.
+    pub fn mobility(&self, player: Player) -> usize {
+        let (Some(&own_pos), Some(&opponent_pos)) = (
+            self.pawn_positions.get(&player),
+            self.pawn_positions.get(&player.opponent()),
+        ) else { return 0; };
+        self.pawn_destinations(own_pos, opponent_pos).len()
+    }
 
-         // Check direct neighbors
-         for neighbor_idx in self.graph.neighbors(*own_node) {
+    /// The player's legal pawn destinations (`get_legal_moves`) as a packed bit
+    /// grid of `size * size` bits, row-major from the top-left square (a9 on
+    /// 9x9): square (row, col) is bit `row * size + col`, stored in byte
+    /// `index / 8` at bit `index % 8` (least significant first).
+    pub fn legal_moves_mask(&self, player: Player) -> Vec<u8> {
+        let mut mask = vec![0u8; (self.size * self.size).div_ceil(8)];
+        for move_str in self.get_legal_moves(player) {
+            let (row, col) = self.algebraic_to_coord(&move_str);
+            let index = row * self.size + col;
+            mask[index / 8] |= 1 << (index % 8);
+        }
+        mask
+    }
+
+    /// Squares a pawn on `own_pos` could move to with the opponent on `opponent_pos`,
+    /// including straight and diagonal jumps. Shared by move generation and the
+    /// opponent-aware distance search.
+     fn pawn_destinations(&self, own_pos: Coord, opponent_pos: Coord) -> BTreeSet<Coord> {
+         let mut legal_coords = BTreeSet::new(); // Set avoids duplicates; ordered for a stable result
+         let Some(own_node) = self.node_indices.get(&own_pos) else { return legal_coords; }; // Node not found
+
+         // Check direct neighbors (from the cache, equivalent to graph.neighbors)
+         for &neighbor_idx in self.neighbors[own_node.index()].as_slice() {
              let neighbor_pos = self.graph[neighbor_idx];
 
-             if neighbor_pos == *opponent_pos {
-                 // Adjacent to opponent - check for jumps
-                 let Some(opponent_node) = self.node_indices.get(opponent_pos) else { continue; };
+             if neighbor_pos == opponent_pos {
+                 // Adjacent to opponent - check for jumps (unless the variant forbids them)
+                 if !self.rules.allow_jumps { continue; }
+                 let Some(opponent_node) = self.node_indices.get(&opponent_pos) else { continue; };
 
                  // --- Check straight jump ---
-                 // Calculate potential jump destination
+                 // Calculate potential jump destination, bounds-checked before converting
+                 // back to usize (an edge opponent puts it at -1 or size)
                  let jump_r = (own_pos.0 as i32) + 2 * (opponent_pos.0 as i32 - own_pos.0 as i32);
                  let jump_c = (own_pos.1 as i32) + 2 * (opponent_pos.1 as i32 - own_pos.1 as i32);
-
-                 // Check if jump is on board
-                 if jump_r >= 0 && jump_r < self.size as i32 && jump_c >= 0 && jump_c < self.size as i32 {
-                     let jump_pos = (jump_r as usize, jump_c as usize);
-                     if let Some(jump_node) = self.node_indices.get(&jump_pos) {
-                         // Check if path from opponent to jump spot is clear (no wall)
-                         if self.graph.contains_edge(*opponent_node, *jump_node) {
-                              legal_coords.insert(jump_pos);
-                              // If straight jump is possible, diagonal jumps are not considered (standard rules)
-                              continue; // Go to next neighbor
-                         }
-                     }
+                 let on_board = |v: i32| v >= 0 && v < self.size as i32;
+                 let jump_pos = if on_board(jump_r) && on_board(jump_c) { Some((jump_r as usize, jump_c as usize)) } else { None };
+
+                 // Straight jump needs the square behind the opponent to be free of walls
+                 let straight_jump = jump_pos.filter(|pos| {
+                     self.node_indices.get(pos).is_some_and(|jump_node| self.neighbors[opponent_node.index()].contains(*jump_node))
+                 });
+                 if let Some(jump_pos) = straight_jump {
+                     legal_coords.insert(jump_pos);
+                     // If straight jump is possible, diagonal jumps are not considered (standard rules)
+                     continue; // Go to next neighbor
                  }
 
-                 // --- No straight jump possible or blocked - check diagonal jumps ---
-                 // Check if opponent is blocked *behind* them (relative to player's jump direction)
-                  let jump_blocked = if let Some(jump_node) = self.node_indices.get(&(jump_r as usize, jump_c as usize)) {
-                      !self.graph.contains_edge(*opponent_node, *jump_node)
-                  } else {
-                      true // Off-board is considered blocked
-                  };
-
-
-                 if jump_blocked {
-                     // Check opponent's neighbors for valid DIAGONAL jump spots
-                     for op_neighbor_idx in self.graph.neighbors(*opponent_node) {
-                         let op_neighbor_pos = self.graph[op_neighbor_idx];
-                         // Must be adjacent to opponent, not where the jumping player came from,
-                         // reachable from the opponent, and diagonal to the jump direction.
-                         if op_neighbor_pos != *own_pos {
-                             // Calculate relative directions
-                             let jump_dr = opponent_pos.0 as i32 - own_pos.0 as i32;
-                             let jump_dc = opponent_pos.1 as i32 - own_pos.1 as i32;
-                             let move_dr = op_neighbor_pos.0 as i32 - opponent_pos.0 as i32;
-                             let move_dc = op_neighbor_pos.1 as i32 - opponent_pos.1 as i32;
-
-                             // Check for orthogonality (dot product == 0) and path existence
-                             if jump_dr * move_dr + jump_dc * move_dc == 0 {
-                                 // Ensure the path from opponent to this diagonal spot is clear
-                                 if self.graph.contains_edge(*opponent_node, op_neighbor_idx) {
-                                     legal_coords.insert(op_neighbor_pos);
-                                 }
+                 // --- No straight jump: the square behind is off-board or walled off ---
+                 // Check opponent's neighbors for valid DIAGONAL jump spots
+                 for &op_neighbor_idx in self.neighbors[opponent_node.index()].as_slice() {
+                     let op_neighbor_pos = self.graph[op_neighbor_idx];
+                     // Must be adjacent to opponent, not where the jumping player came from,
+                     // reachable from the opponent, and diagonal to the jump direction.
+                     if op_neighbor_pos != own_pos {
+                         // Calculate relative directions
+                         let jump_dr = opponent_pos.0 as i32 - own_pos.0 as i32;
+                         let jump_dc = opponent_pos.1 as i32 - own_pos.1 as i32;
+                         let move_dr = op_neighbor_pos.0 as i32 - opponent_pos.0 as i32;
+                         let move_dc = op_neighbor_pos.1 as i32 - opponent_pos.1 as i32;
+
+                         // Check for orthogonality (dot product == 0) and path existence
+                         if jump_dr * move_dr + jump_dc * move_dc == 0 {
+                             // Ensure the path from opponent to this diagonal spot is clear
+                             if self.neighbors[opponent_node.index()].contains(op_neighbor_idx) {
+                                 legal_coords.insert(op_neighbor_pos);
                              }
                          }
                      }
@@ -280,19 +595,62 @@ impl Quoridor {
                  legal_coords.insert(neighbor_pos);
              }
          }
-
-         // Convert coordinates to algebraic notation
-         legal_coords.iter().map(|&coord| self.coord_to_algebraic(coord)).collect()
+         legal_coords
      }
 
 
     /// Returns a list of valid wall placements for the given player in algebraic notation.
     /// Includes checks for availability, overlap, intersection, and path blocking.
+    /// The order is stable: by board row from the top, then by file, with the
+    /// horizontal wall before the vertical one at each square.
      pub fn get_legal_walls(&self, player: Player) -> Vec<String> {
+         self.get_legal_walls_coords(player)
+             .into_iter()
+             .map(|(coord, orientation)| format!("{}{}", self.coord_to_algebraic(coord), orientation))
+             .collect()
+     }
+
+    /// Whether the active player may place the wall `move_str` (e.g. "e3h"),
+    /// checking just that one wall instead of listing them all, e.g. for a wall
+    /// the cursor hovers over. Pawn moves and malformed or off-board notation
+    /// are not legal walls. Agrees with membership in `get_legal_walls`.
+    pub fn is_wall_legal(&self, move_str: &str) -> bool {
+        match classify_move(move_str, self.size) {
+            MoveKind::Wall(coord, orientation) => self.is_wall_placement_valid(self.active_player, coord, orientation),
+            _ => false,
+        }
+    }
+
+    /// Every wall on the board as (reference coord, orientation) pairs, sorted:
+    /// horizontal walls first, then vertical, each by row from the top and then
+    /// by file. Unlike iterating the position sets, the order is stable.
+    pub fn placed_walls(&self) -> Vec<(Coord, char)> {
+        let mut walls: Vec<(Coord, char)> = self.hwall_positions.iter().map(|&coord| (coord, 'h'))
+            .chain(self.vwall_positions.iter().map(|&coord| (coord, 'v')))
+            .collect();
+        walls.sort_by_key(|&(coord, orientation)| (orientation, coord));
+        walls
+    }
+
+    /// `placed_walls` in algebraic notation, e.g. ["e3h", "c5v"].
+    pub fn placed_wall_moves(&self) -> Vec<String> {
+        self.placed_walls()
+            .into_iter()
+            .map(|(coord, orientation)| format!("{}{}", self.coord_to_algebraic(coord), orientation))
+            .collect()
+    }
+
+    /// Returns the valid wall placements as (reference coord, orientation) pairs,
+    /// in the same order as `get_legal_walls`, so callers can skip re-parsing strings.
+     pub fn get_legal_walls_coords(&self, player: Player) -> Vec<(Coord, char)> {
         if self.walls_available[&player] == 0 {
              return Vec::new(); // No walls left
         }
 
+         // Walls that cut neither player's current shortest path can't disconnect anyone,
+         // so only walls crossing one of these edges need the full path search
+         let path_edges = self.shortest_path_edges();
+
          let mut legal_walls = Vec::new();
          // Iterate through potential *top-left* coords of wall placement areas
          // Horizontal walls: rows 1 to size-1, cols 0 to size-2
@@ -303,24 +661,96 @@ impl Quoridor {
              for c in 0..self.size - 1 { // Walls are 2 units wide/tall
 
                   // Check Horizontal Wall Possibility at (r, c) - blocking between row r-1 and r
-                  let h_wall_alg = self.coord_to_algebraic((r, c)); // Alg notation for bottom-left square
-                  let h_wall_move = format!("{}h", h_wall_alg);
-                 if self.is_wall_placement_valid(player, (r, c), 'h') {
-                      legal_walls.push(h_wall_move);
+                 if self.is_wall_placement_valid_with_paths(player, (r, c), 'h', path_edges.as_ref()) {
+                      legal_walls.push(((r, c), 'h'));
                   }
 
                   // Check Vertical Wall Possibility at (r, c) - blocking between col c and c+1
-                   // The algebraic notation refers to the square left of the wall
-                   let v_wall_alg = self.coord_to_algebraic((r,c)); // Alg notation uses bottom-left ref
-                   let v_wall_move = format!("{}v", v_wall_alg);
-                 if self.is_wall_placement_valid(player, (r, c), 'v') {
-                      legal_walls.push(v_wall_move);
+                 if self.is_wall_placement_valid_with_paths(player, (r, c), 'v', path_edges.as_ref()) {
+                      legal_walls.push(((r, c), 'v'));
                   }
              }
          }
          legal_walls
      }
 
+    /// Returns every legal move for the given player in one list:
+    /// pawn moves first, followed by wall placements.
+    pub fn all_legal_moves(&self, player: Player) -> Vec<String> {
+        let mut moves = self.get_legal_moves(player);
+        moves.extend(self.get_legal_walls(player));
+        moves
+    }
+
+    /// Returns one shortest path (a BFS over the current board, ignoring the
+    /// opponent pawn) from the player's square to its goal line, both ends included.
+    /// None if the player is cut off from its goal.
+    pub fn shortest_path_to_goal(&self, player: Player) -> Option<Vec<Coord>> {
+        let start = *self.pawn_positions.get(&player)?;
+        let goals = self.goal_positions.get(&player)?;
+        let start_node = *self.node_indices.get(&start)?;
+
+        let mut parent: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue = VecDeque::from([start_node]);
+        parent.insert(start_node, start_node);
+        while let Some(node) = queue.pop_front() {
+            if goals.contains(&self.graph[node]) {
+                // Walk the parent links back to the start
+                let mut path = vec![self.graph[node]];
+                let mut current = node;
+                while current != start_node {
+                    current = parent[&current];
+                    path.push(self.graph[current]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &neighbor in self.neighbors[node.index()].as_slice() {
+                if let Entry::Vacant(entry) = parent.entry(neighbor) {
+                    entry.insert(node);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        None
+    }
+
+    /// `shortest_path_to_goal` for Player 1 and Player 2, in that order, for
+    /// callers that weigh one player's route against the other's.
+    pub fn shortest_paths_both(&self) -> (Option<Vec<Coord>>, Option<Vec<Coord>>) {
+        (self.shortest_path_to_goal(Player::Player1), self.shortest_path_to_goal(Player::Player2))
+    }
+
+    /// Edges on both players' current shortest paths, each stored with its
+    /// smaller coord first. None if either player has no path.
+    fn shortest_path_edges(&self) -> Option<HashSet<(Coord, Coord)>> {
+        let (p1_path, p2_path) = self.shortest_paths_both();
+        let mut edges = HashSet::new();
+        for path in [p1_path?, p2_path?] {
+            edges.extend(path.windows(2).map(|step| (step[0].min(step[1]), step[0].max(step[1]))));
+        }
+        Some(edges)
+    }
+
+    /// Same answer as `is_wall_placement_valid`, but skips the path search when the
+    /// wall removes no edge in `path_edges` (see `shortest_path_edges`): both
+    /// players' shortest paths survive, so nobody can be cut off.
+     fn is_wall_placement_valid_with_paths(&self, player: Player, wall_coord: Coord, orientation: char, path_edges: Option<&HashSet<(Coord, Coord)>>) -> bool {
+        let Some(path_edges) = path_edges else { return self.is_wall_placement_valid(player, wall_coord, orientation); };
+        if self.walls_available[&player] == 0 || !self.wall_fits(wall_coord, orientation) { return false; }
+        let Some(edges) = get_blocked_edges_by_wall(wall_coord, orientation, self.size) else { return false; };
+
+        // Every edge the wall covers must still be open (as in the full check)
+        let edge_open = |(u, v): &(Coord, Coord)| match (self.node_indices.get(u), self.node_indices.get(v)) {
+            (Some(u_idx), Some(v_idx)) => self.neighbors[u_idx.index()].contains(*v_idx),
+            _ => false,
+        };
+        if !edges.iter().all(edge_open) { return false; }
+
+        let cuts_a_path = edges.iter().any(|&(u, v)| path_edges.contains(&(u.min(v), u.max(v))));
+        !cuts_a_path || self.is_wall_placement_valid(player, wall_coord, orientation)
+     }
+
     /// Internal helper to check if placing a specific wall is geometrically valid and doesn't block paths.
      /// `wall_coord`: The bottom-left coordinate the wall is adjacent to (above or left).
      fn is_wall_placement_valid(&self, player: Player, wall_coord: Coord, orientation: char) -> bool {
@@ -328,48 +758,44 @@ impl Quoridor {
         if self.walls_available[&player] == 0 { return false; }
 
         // 2. Check for overlaps and intersections
-        match orientation {
-            'h' => {
-                // Check direct overlap
-                if self.hwall_positions.contains(&wall_coord) { return false; }
-                // Check adjacent horizontal overlap (wall is 2 units long)
-                 if wall_coord.1 > 0 && self.hwall_positions.contains(&(wall_coord.0, wall_coord.1 - 1)) { return false;}
-                 if wall_coord.1 + 1 < self.size -1 && self.hwall_positions.contains(&(wall_coord.0, wall_coord.1 + 1)) { return false; }
-                // Check intersection with vertical wall at the same junction
-                 if self.vwall_positions.contains(&wall_coord) { return false; }
-                 // Need to also check intersection with vertical wall to the right
-                  if wall_coord.1 + 1 < self.size {
-                     if self.vwall_positions.contains(&(wall_coord.0, wall_coord.1 + 1)) { return false; }
-                  }
+        if !self.wall_fits(wall_coord, orientation) { return false; }
 
-            }
-            'v' => {
-                // Check direct overlap
-                if self.vwall_positions.contains(&wall_coord) { return false; }
-                // Check adjacent vertical overlap
-                 if wall_coord.0 > 1 && self.vwall_positions.contains(&(wall_coord.0 - 1, wall_coord.1)) { return false; }
-                 if wall_coord.0 + 1 < self.size && self.vwall_positions.contains(&(wall_coord.0 + 1, wall_coord.1)) { return false; }
-                // Check intersection with horizontal wall at the same junction
-                 if self.hwall_positions.contains(&wall_coord) { return false; }
-                // Need to also check intersection with horizontal wall below
-                if wall_coord.0 + 1 < self.size {
-                     if self.hwall_positions.contains(&(wall_coord.0+1, wall_coord.1)) { return false;}
-                }
-            }
+        // 3. Check path blocking using a temporary graph modification
+        self.wall_keeps_paths_open(wall_coord, orientation)
+     }
+
+    /// Checks that a wall lies fully on the board and doesn't overlap or cross a
+    /// wall already on the board. Both orientations use the same bounds: the
+    /// stored square's row in 1..size (so the wall's far end stays below the
+    /// top edge) and its column in 0..size-1. Walls may touch, end to end or in
+    /// a T, as in the standard rules.
+     fn wall_fits(&self, wall_coord: Coord, orientation: char) -> bool {
+        let (r, c) = wall_coord;
+        if r == 0 || r >= self.size || c + 1 >= self.size { return false; }
+
+        // A wall is two segments long, so it overlaps a parallel wall one step
+        // along its own line: left/right for 'h', up/down for 'v'
+        let (same, other, before, after) = match orientation {
+            'h' => (&self.hwall_positions, &self.vwall_positions, c.checked_sub(1).map(|c| (r, c)), (r, c + 1)),
+            'v' => (&self.vwall_positions, &self.hwall_positions, r.checked_sub(1).map(|r| (r, c)), (r + 1, c)),
             _ => return false, // Invalid orientation
+        };
+        if same.contains(&wall_coord) || same.contains(&after) || before.is_some_and(|coord| same.contains(&coord)) {
+            return false;
         }
+        // Crossing: a wall of the other orientation stored at the same square shares its centre
+        !other.contains(&wall_coord)
+     }
 
-
-        // 3. Check path blocking using a temporary graph modification
+    /// Checks that both players can still reach their goal line with the wall in place.
+     fn wall_keeps_paths_open(&self, wall_coord: Coord, orientation: char) -> bool {
         if let Some(edges_to_remove) = get_blocked_edges_by_wall(wall_coord, orientation, self.size) {
             let mut temp_graph = self.graph.clone();
-            let mut edges_removed_count = 0;
 
-             for (u_coord, v_coord) in edges_to_remove.iter().filter(|(u,_)| u.0 != usize::MAX) { // Filter out dummy edge for 'v' top row
+             for (u_coord, v_coord) in &edges_to_remove {
                  if let (Some(u_idx), Some(v_idx)) = (self.node_indices.get(u_coord), self.node_indices.get(v_coord)) {
                      if let Some(edge_ref) = temp_graph.find_edge(*u_idx, *v_idx) {
                          temp_graph.remove_edge(edge_ref);
-                         edges_removed_count += 1;
                      } else {
                           // If an expected edge doesn't exist, the placement is likely invalid due to another wall
                           return false;
@@ -380,18 +806,6 @@ impl Quoridor {
                  }
             }
 
-            // If no edges could be found/removed (e.g., blocked by existing walls), it might be invalid placement
-            if edges_removed_count == 0 {
-                 // Be careful here: sometimes only one edge exists near border
-                 if orientation == 'v' && wall_coord.0 == 0 {
-                     // Only one edge expected to be removed for top-row vertical wall
-                 } else if edges_removed_count < 2 { // Expect 2 edges usually
-                     // This might indicate placement conflict
-                    // return false; // Re-evaluate if this check is too strict
-                 }
-            }
-
-
             // Check if all players still have a path to their goal line
              check_wall_path_blocking(&temp_graph, &self.node_indices, &self.pawn_positions, &self.goal_positions)
 
@@ -402,10 +816,35 @@ impl Quoridor {
 
      /// Internal method to add a wall and update graph without checks or changing player state.
      /// Used during state parsing.
-     fn add_wall_internal(&mut self, wall_move: &str, is_initialising: bool) -> bool {
-         let Some(orientation) = wall_move.chars().last() else { return false; };
-         let Some(pos_alg) = wall_move.get(0..wall_move.len()-1) else { return false; };
-         let wall_coord = self.algebraic_to_coord(pos_alg);
+     fn add_wall_internal(&mut self, wall_coord: Coord, orientation: char, is_initialising: bool) -> bool {
+         // A real placement needs a wall in hand; refuse before touching the board
+         // rather than underflowing the counter (e.g. unchecked moves, crafted states)
+         if !is_initialising && self.walls_available.get(&self.active_player).is_none_or(|&walls| walls == 0) {
+             return false;
+         }
+
+         // Every edge the wall covers must still be in the graph. A missing edge
+         // means the wall is off the board or overlaps one already placed; refusing
+         // here keeps the wall sets and the graph in step (unchecked placements and
+         // state strings skip is_wall_placement_valid)
+         let Some(edges_to_remove) = get_blocked_edges_by_wall(wall_coord, orientation, self.size) else {
+             eprintln!("Warning: refusing wall {:?}{} (off the board or not h/v)", wall_coord, orientation);
+             return false;
+         };
+         let mut edge_indices = Vec::with_capacity(edges_to_remove.len());
+         for &(u_coord, v_coord) in &edges_to_remove {
+             let edge = self.node_indices.get(&u_coord)
+                 .zip(self.node_indices.get(&v_coord))
+                 .and_then(|(u_idx, v_idx)| self.graph.find_edge(*u_idx, *v_idx).map(|_| (*u_idx, *v_idx)));
+             let Some(edge) = edge else {
+                 eprintln!(
+                     "Warning: refusing wall {:?}{}: edge {:?}-{:?} is already blocked",
+                     wall_coord, orientation, u_coord, v_coord
+                 );
+                 return false;
+             };
+             edge_indices.push(edge);
+         }
 
          // Add to position sets
          match orientation {
@@ -415,20 +854,21 @@ impl Quoridor {
          }
 
          // Remove edges from graph
-         if let Some(edges_to_remove) = get_blocked_edges_by_wall(wall_coord, orientation, self.size) {
-             for (u_coord, v_coord) in edges_to_remove.iter().filter(|(u,_)| u.0 != usize::MAX) {
-                 if let (Some(u_idx), Some(v_idx)) = (self.node_indices.get(u_coord), self.node_indices.get(v_coord)) {
-                     if let Some(edge_ref) = self.graph.find_edge(*u_idx, *v_idx) {
-                         self.graph.remove_edge(edge_ref);
-                     }
-                 }
+         for (u_idx, v_idx) in edge_indices {
+             if let Some(edge_ref) = self.graph.find_edge(u_idx, v_idx) {
+                 self.graph.remove_edge(edge_ref);
+                 // Refresh the cached neighbours of both endpoints
+                 self.neighbors[u_idx.index()] = NeighborList::from_graph(&self.graph, u_idx);
+                 self.neighbors[v_idx.index()] = NeighborList::from_graph(&self.graph, v_idx);
              }
          }
          // Don't update player state if initializing
          if !is_initialising {
               self.previous_state = self.state_string.clone();
               *self.walls_available.get_mut(&self.active_player).unwrap() -= 1;
-              self.last_move = wall_move.to_string();
+              self.last_move = format!("{}{}", self.coord_to_algebraic(wall_coord), orientation);
+              self.history.push(self.last_move.clone());
+              self.ply += 1;
               self.update_state_string(false); // Switch player
          }
 
@@ -444,17 +884,43 @@ impl Quoridor {
          let Some(pos_alg) = wall_move.get(0..wall_move.len()-1) else { return false; };
          let wall_coord = self.algebraic_to_coord(pos_alg);
 
-         if check {
-             if !self.is_wall_placement_valid(self.active_player, wall_coord, orientation) {
+         if is_initialising {
+             // Setting up a position: the wall is not a turn, so nothing is spent
+             if check && !self.is_wall_placement_valid(self.active_player, wall_coord, orientation) {
                  return false; // Failed check
              }
+             return self.add_wall_internal(wall_coord, orientation, true);
+         }
+         self.add_wall_at(wall_coord, orientation, check)
+     }
+
+    /// Places a wall for the active player by its square and orientation ('h' or 'v'),
+    /// without going through algebraic notation: `add_wall_at((1, 4), 'h', true)` on
+    /// a 9x9 board is `add_wall("e8h", false, true)`. Pairs with `get_legal_walls_coords`.
+    /// `check`: If true, performs all legality checks.
+    pub fn add_wall_at(&mut self, wall_coord: Coord, orientation: char, check: bool) -> bool {
+         if orientation != 'h' && orientation != 'v' { return false; }
+
+         if check && !self.is_wall_placement_valid(self.active_player, wall_coord, orientation) {
+             return false; // Failed check
          }
 
          // --- Passed checks or checks skipped ---
          // Add wall to position sets and update graph (internal logic handles this)
-         self.add_wall_internal(wall_move, is_initialising)
+         self.add_wall_internal(wall_coord, orientation, false)
      }
 
+    /// Plays a pawn move or wall for the active player, telling them apart with
+    /// `utils::classify_move`, so "e10" on an 11x11 board is a pawn move.
+    /// Returns false for malformed or off-board notation instead of panicking.
+    /// `check`: If true, performs legality checks.
+    pub fn apply_move(&mut self, move_str: &str, check: bool) -> bool {
+        match classify_move(move_str, self.size) {
+            MoveKind::Pawn(_) => self.move_pawn(move_str, check),
+            MoveKind::Wall(coord, orientation) => self.add_wall_at(coord, orientation, check),
+            MoveKind::Invalid => false,
+        }
+    }
 
     /// Attempts to move the active player's pawn. Returns true if successful and legal.
      /// `check`: If true, performs legality checks.
@@ -476,13 +942,40 @@ impl Quoridor {
         // Update game state history and switch player
         self.previous_state = self.state_string.clone();
         self.last_move = move_alg.to_string();
+        self.history.push(self.last_move.clone());
+        self.ply += 1;
         self.update_state_string(false); // Switches active player
 
         true
     }
 
+    /// Checks that a transcript of moves can be played, in order, from this position.
+    /// Each move must be legal for the side to move, and no move may follow a win.
+    /// Returns the index and text of the first illegal move. `self` is left untouched;
+    /// pass `Quoridor::standard()` (or similar) to validate a game from its start.
+    pub fn validate_sequence(&self, moves: &[String]) -> Result<(), (usize, String)> {
+        let mut game = self.clone();
+        for (index, move_str) in moves.iter().enumerate() {
+            let game_over = game.winner().is_some();
+            // Checking against the generated list rejects malformed squares without panicking
+            if game_over || !game.all_legal_moves(game.active_player).contains(move_str) {
+                return Err((index, move_str.clone()));
+            }
+            if !game.apply_move(move_str, false) {
+                return Err((index, move_str.clone()));
+            }
+        }
+        Ok(())
+    }
+
     /// Checks if the move (represented by the destination coord) is a winning move for the *current* active player.
     pub fn win_check(&self, move_alg: &str) -> bool {
+        self.is_winning_move_for(self.active_player, move_alg)
+    }
+
+    /// Checks if the move would put `player`'s pawn on their goal line.
+    /// Does not check that the move is legal.
+    pub fn is_winning_move_for(&self, player: Player, move_alg: &str) -> bool {
         // --- CORRECTED LOGIC ---
         // A winning move must be a pawn move. Standard pawn moves in algebraic
         // notation (e.g., "e1", "a4") have a length of 2.
@@ -495,16 +988,26 @@ impl Quoridor {
 
         // If it might be a pawn move, proceed with the original check:
         let destination = self.algebraic_to_coord(move_alg);
-         if let Some(goal_line) = self.goal_positions.get(&self.active_player) {
+         if let Some(goal_line) = self.goal_positions.get(&player) {
              // Check if the destination coordinate is within the player's goal line
              goal_line.contains(&destination)
          } else {
              // This case should ideally not happen if goal_positions is always set up correctly.
-             eprintln!("Warning: Could not find goal line for player {:?} during win check.", self.active_player);
+             eprintln!("Warning: Could not find goal line for player {:?} during win check.", player);
              false
          }
     }
 
+    /// Returns a winning pawn move for the non-active player, if they could win
+    /// on their next turn (i.e. were the active player to pass).
+    /// Useful for UI hints and for strategies that want to block the threat.
+    pub fn opponent_can_win_next(&self) -> Option<String> {
+        let opponent = self.active_player.opponent();
+        self.get_legal_moves(opponent)
+            .into_iter()
+            .find(|move_str| self.is_winning_move_for(opponent, move_str))
+    }
+
     /// Calculates the shortest path distance for a player to their goal line.
      /// Returns 100 if no path exists (consistent with paper's heuristic needs).
     pub fn distance_to_goal(&self, player: Player) -> usize {
@@ -520,6 +1023,33 @@ impl Quoridor {
          }
     }
 
+    /// Like `distance_to_goal`, but treats the opponent pawn as an obstacle:
+    /// the player can never stop on its square and can only get past it by
+    /// jumping (straight, or diagonally when the straight jump is walled off).
+    /// The opponent is assumed to stay put. A jump can make this shorter than
+    /// `distance_to_goal`, and a boxed-in opponent in a corridor can make it longer.
+    /// Returns 100 if no path exists.
+    pub fn distance_to_goal_with_opponent(&self, player: Player) -> usize {
+        let (Some(&start), Some(&opponent_pos), Some(goal_coords)) = (
+            self.pawn_positions.get(&player),
+            self.pawn_positions.get(&player.opponent()),
+            self.goal_positions.get(&player),
+        ) else { return 100; };
+
+        // Breadth-first search over pawn moves with the opponent fixed in place
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some((pos, dist)) = queue.pop_front() {
+            if goal_coords.contains(&pos) { return dist; }
+            for next in self.pawn_destinations(pos, opponent_pos) {
+                if visited.insert(next) {
+                    queue.push_back((next, dist + 1));
+                }
+            }
+        }
+        100
+    }
+
      /// Calculates the minimum number of pawn moves required for the player to reach *any* square
      /// in the next row towards their goal. Returns 100 if stuck or already at goal line.
      /// (Based on f3/f4 feature from Mertens paper)
@@ -561,6 +1091,336 @@ impl Quoridor {
 
         if min_dist == usize::MAX { 100 } else { min_dist }
     }
+
+    /// The player whose pawn stands on its goal line, if the game is over.
+    pub fn winner(&self) -> Option<Player> {
+        [Player::Player1, Player::Player2].into_iter()
+            .find(|player| self.goal_positions[player].contains(&self.pawn_positions[player]))
+    }
+
+    /// With no walls left on either side the game is a pure race: returns the
+    /// player who gets home first, where the side to move wins equal distances.
+    /// None while either player still has walls, or if a pawn is walled off.
+    /// Pawn interactions (blocking, jumps) are ignored, as in `distance_to_goal`,
+    /// so pawns meeting head-on can still change the result by a tempo.
+    pub fn race_result(&self) -> Option<Player> {
+        if let Some(winner) = self.winner() { return Some(winner); }
+        if self.walls_available.values().any(|&walls| walls > 0) { return None; }
+
+        let mover = self.active_player;
+        let mover_dist = self.shortest_path_to_goal(mover)?.len() - 1;
+        let other_dist = self.shortest_path_to_goal(mover.opponent())?.len() - 1;
+        Some(if mover_dist <= other_dist { mover } else { mover.opponent() })
+    }
+
+    /// Number of half-moves (pawn moves or wall placements) played so far.
+    /// Loading a state string does not count as moves, so it starts at 0.
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Every move played so far, oldest first, e.g. ["e2", "e8", "e3h"];
+    /// the last one is `last_move`. Like `ply`, it starts empty, including
+    /// for positions loaded from a state string.
+    pub fn move_history(&self) -> &[String] {
+        self.history.as_slice()
+    }
+
+    /// Walls placed so far by both players, from the walls each has spent.
+    /// Positions loaded with more walls in hand than the game's initial count
+    /// count as zero spent for that player.
+    pub fn walls_placed(&self) -> usize {
+        self.walls_available.values().map(|&left| self.walls.saturating_sub(left)).sum()
+    }
+
+    /// Stage of the game from the ply count and walls spent: the opening lasts
+    /// `size` plies (about four moves each on 9x9) while no wall has been
+    /// placed; the endgame starts once three quarters of all walls are down
+    /// or after `4 * size` plies. Everything in between is the midgame.
+    pub fn game_phase(&self) -> Phase {
+        let total_walls = 2 * self.walls;
+        let placed = self.walls_placed();
+        if (total_walls > 0 && 4 * placed >= 3 * total_walls) || self.ply >= 4 * self.size {
+            Phase::Endgame
+        } else if placed == 0 && self.ply < self.size {
+            Phase::Opening
+        } else {
+            Phase::Midgame
+        }
+    }
+
+    /// Cheap signed "who is winning" score for an evaluation bar, roughly in [-1, 1]:
+    /// positive favours Player 1, negative Player 2. Based on the shortest-path
+    /// race (P2's distance minus P1's) plus a small bonus per wall in hand,
+    /// scaled by the board size.
+    pub fn advantage(&self) -> f64 {
+        const WALL_WEIGHT: f64 = 0.25; // A wall in hand is worth about a quarter of a step
+        let race = self.distance_to_goal(Player::Player2) as f64 - self.distance_to_goal(Player::Player1) as f64;
+        let walls = self.walls_available[&Player::Player1] as f64 - self.walls_available[&Player::Player2] as f64;
+        ((race + WALL_WEIGHT * walls) / self.size as f64).clamp(-1.0, 1.0)
+    }
+
+    /// How close the position is to its own central reflection (the board
+    /// turned 180 degrees with the players swapped), ignoring walls in hand:
+    /// 1.0 when it is identical, lower otherwise. The pawn pair counts as two
+    /// items and each placed wall as one; the score is the fraction of items
+    /// whose reflection is also on the board.
+    pub fn symmetry_score(&self) -> f64 {
+        let n = self.size;
+        let (p1, p2) = (self.pawn_positions[&Player::Player1], self.pawn_positions[&Player::Player2]);
+        let pawns_match = (n - 1 - p1.0, n - 1 - p1.1) == p2;
+
+        // A wall's stored square is the lower-left one it touches, so it
+        // reflects to (n - r, n - 2 - c)
+        let reflects = |walls: &HashSet<Coord>| walls.iter().filter(|&&(r, c)| c + 2 <= n && walls.contains(&(n - r, n - 2 - c))).count();
+        let matched = if pawns_match { 2 } else { 0 } + reflects(&self.hwall_positions) + reflects(&self.vwall_positions);
+        let total = 2 + self.hwall_positions.len() + self.vwall_positions.len();
+        matched as f64 / total as f64
+    }
+
+    /// Returns every square the player's pawn could reach in at most `n` steps,
+    /// including its current square. Breadth-first search over the current graph,
+    /// so walls are respected but the opponent pawn (and jumps over it) is ignored.
+    pub fn reachable_within(&self, player: Player, n: usize) -> HashSet<Coord> {
+        let mut reachable = HashSet::new();
+        let Some(start_coord) = self.pawn_positions.get(&player) else { return reachable; };
+        let Some(start_node) = self.node_indices.get(start_coord) else { return reachable; };
+
+        let mut queue = VecDeque::new();
+        reachable.insert(*start_coord);
+        queue.push_back((*start_node, 0));
+
+        while let Some((node, depth)) = queue.pop_front() {
+            if depth == n { continue; } // Don't expand past the depth limit
+            for &neighbor in self.neighbors[node.index()].as_slice() {
+                if reachable.insert(self.graph[neighbor]) {
+                    queue.push_back((neighbor, depth + 1));
+                }
+            }
+        }
+        reachable
+    }
+
+    /// Ranks the walls `opponent`'s rival could place by how many of `opponent`'s
+    /// legal pawn moves they take away (see `mobility`), as (wall move, moves
+    /// removed), most first, ties alphabetical. Walls that remove nothing are left
+    /// out. Unlike `find_maximally_blocking_walls`, this finds "trap" walls that
+    /// hem a pawn in without necessarily lengthening its path.
+    pub fn walls_reducing_opponent_mobility(&self, opponent: Player) -> Vec<(String, usize)> {
+        let mobility_now = self.mobility(opponent);
+        let mut ranked: Vec<(String, usize)> = self.get_legal_walls_coords(opponent.opponent())
+            .into_iter()
+            .filter_map(|(coord, orientation)| {
+                let mut next = self.clone();
+                if !next.add_wall_at(coord, orientation, false) { return None; }
+                let removed = mobility_now.saturating_sub(next.mobility(opponent));
+                (removed > 0).then(|| (format!("{}{}", self.coord_to_algebraic(coord), orientation), removed))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked
+    }
+
+    /// Shortest-path distance from every square to the player's goal line with the
+    /// current walls, indexed `[row][col]`; `None` marks squares walled off from
+    /// the goal. One breadth-first search outward from the whole goal row (every
+    /// step costs 1, so this is Dijkstra). Like `distance_to_goal`, the opponent
+    /// pawn is ignored.
+    pub fn distance_field(&self, player: Player) -> Vec<Vec<Option<usize>>> {
+        let mut field = vec![vec![None; self.size]; self.size];
+        let mut queue = VecDeque::new();
+        for &goal in self.goal_positions.get(&player).into_iter().flatten() {
+            field[goal.0][goal.1] = Some(0);
+            queue.push_back(goal);
+        }
+
+        while let Some(coord) = queue.pop_front() {
+            let Some(node) = self.node_indices.get(&coord) else { continue; };
+            let dist = field[coord.0][coord.1].unwrap_or(0);
+            for &neighbor in self.neighbors[node.index()].as_slice() {
+                let (r, c) = self.graph[neighbor];
+                if field[r][c].is_none() {
+                    field[r][c] = Some(dist + 1);
+                    queue.push_back((r, c));
+                }
+            }
+        }
+        field
+    }
+
+    /// Squares roughly as far from Player 1's goal as from Player 2's: those
+    /// whose two `distance_field` distances differ by at most `threshold`.
+    /// This is the frontier where wall battles tend to happen. Squares walled
+    /// off from either goal are left out; the result is in row-major order.
+    pub fn contested_squares(&self, threshold: usize) -> Vec<Coord> {
+        let p1_field = self.distance_field(Player::Player1);
+        let p2_field = self.distance_field(Player::Player2);
+        let mut contested = Vec::new();
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if let (Some(d1), Some(d2)) = (p1_field[row][col], p2_field[row][col]) {
+                    if d1.abs_diff(d2) <= threshold {
+                        contested.push((row, col));
+                    }
+                }
+            }
+        }
+        contested
+    }
+
+    /// Blocking pressure on `player`'s opponent: the number of steps on the
+    /// opponent's current shortest path (`shortest_path_to_goal`) with a wall
+    /// along a side of either square. Walls are not owned, so every placed wall
+    /// counts. A wall hugging the route scores before it blocks anything;
+    /// 0 if the opponent is cut off.
+    pub fn opponent_path_pressure(&self, player: Player) -> usize {
+        let Some(path) = self.shortest_path_to_goal(player.opponent()) else { return 0; };
+        let walled: HashSet<Coord> = self.placed_walls()
+            .into_iter()
+            .filter_map(|(coord, orientation)| get_blocked_edges_by_wall(coord, orientation, self.size))
+            .flatten()
+            .flat_map(|(a, b)| [a, b])
+            .collect();
+        path.windows(2).filter(|step| walled.contains(&step[0]) || walled.contains(&step[1])).count()
+    }
+
+    /// Choke points on the player's current shortest path: squares that, if
+    /// they were taken off the board, would leave the pawn with no way to its
+    /// goal line. A one-wide corridor or a single gap in a wall line shows up
+    /// here square by square. The pawn's own square is left out; the result is
+    /// in path order, and empty if the player is already cut off.
+    pub fn path_bottlenecks(&self, player: Player) -> Vec<Coord> {
+        let Some(path) = self.shortest_path_to_goal(player) else { return Vec::new(); };
+        path.into_iter()
+            .skip(1)
+            .filter(|&square| !self.reaches_goal_avoiding(player, square))
+            .collect()
+    }
+
+    /// True if exactly one shortest route leads from the player's pawn to its
+    /// goal line, so a wall anywhere on it forces a detour. Shortest paths are
+    /// counted with a breadth-first search that adds up, for each square, the
+    /// counts of its predecessors one step closer to the pawn. Like
+    /// `distance_to_goal`, the opponent pawn is ignored; a pawn cut off from
+    /// its goal has no path at all. A clear run straight up a file is unique too.
+    pub fn has_unique_shortest_path(&self, player: Player) -> bool {
+        let (Some(&start), Some(goals)) = (self.pawn_positions.get(&player), self.goal_positions.get(&player)) else { return false; };
+        let mut dist: HashMap<Coord, usize> = HashMap::from([(start, 0)]);
+        let mut paths: HashMap<Coord, u64> = HashMap::from([(start, 1)]);
+        let mut queue = VecDeque::from([start]);
+        let mut goal_dist = None;
+
+        while let Some(coord) = queue.pop_front() {
+            let d = dist[&coord];
+            if goal_dist.is_some_and(|goal_d| d >= goal_d) { break; } // Nothing beyond the nearest goal row matters
+            if goals.contains(&coord) {
+                goal_dist = Some(d);
+                continue;
+            }
+            let Some(node) = self.node_indices.get(&coord) else { continue; };
+            let count = paths[&coord];
+            for &neighbor in self.neighbors[node.index()].as_slice() {
+                let next = self.graph[neighbor];
+                match dist.get(&next) {
+                    None => {
+                        dist.insert(next, d + 1);
+                        paths.insert(next, count);
+                        queue.push_back(next);
+                    }
+                    Some(&next_d) if next_d == d + 1 => {
+                        let total = paths[&next].saturating_add(count);
+                        paths.insert(next, total);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        let Some(goal_d) = goal_dist else { return false; };
+        let total: u64 = goals.iter()
+            .filter(|goal| dist.get(goal) == Some(&goal_d))
+            .map(|goal| paths[goal])
+            .fold(0, u64::saturating_add);
+        total == 1
+    }
+
+    /// Breadth-first search from the player's pawn to its goal line that treats
+    /// `avoid` as missing. Like `distance_to_goal`, the opponent pawn is ignored.
+    fn reaches_goal_avoiding(&self, player: Player, avoid: Coord) -> bool {
+        let (Some(start), Some(goals)) = (self.pawn_positions.get(&player), self.goal_positions.get(&player)) else { return false; };
+        let mut seen = HashSet::from([*start, avoid]);
+        let mut queue = VecDeque::from([*start]);
+        while let Some(coord) = queue.pop_front() {
+            if goals.contains(&coord) { return true; }
+            let Some(node) = self.node_indices.get(&coord) else { continue; };
+            for &neighbor in self.neighbors[node.index()].as_slice() {
+                if seen.insert(self.graph[neighbor]) {
+                    queue.push_back(self.graph[neighbor]);
+                }
+            }
+        }
+        false
+    }
+
+    /// Ranks the player's legal walls by how far they push the opponent from goal.
+    /// Returns up to `k` of them as (wall move, opponent distance after placing it),
+    /// longest distance first; ties are broken alphabetically so the result is stable.
+    pub fn find_maximally_blocking_walls(&self, player: Player, k: usize) -> Vec<(String, usize)> {
+        let opponent = player.opponent();
+        let mut ranked: Vec<(String, usize)> = self.get_legal_walls_coords(player)
+            .into_iter()
+            .filter_map(|(coord, orientation)| {
+                let mut next = self.clone();
+                if !next.add_wall_at(coord, orientation, false) { return None; }
+                let dist = next.distance_to_goal(opponent);
+                Some((format!("{}{}", self.coord_to_algebraic(coord), orientation), dist))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// The active player's and the opponent's distances to goal after the
+    /// active player places `wall_move`, or None if it cannot be placed.
+    /// `check`: If true, the wall must be legal (see `add_wall`).
+    pub(crate) fn distances_after_wall(&self, wall_move: &str, check: bool) -> Option<(usize, usize)> {
+        let player = self.active_player;
+        let mut next = self.clone();
+        if !next.add_wall(wall_move, false, check) { return None; }
+        Some((next.distance_to_goal(player), next.distance_to_goal(player.opponent())))
+    }
+
+    /// Picks the best wall for the side to move from `candidates` (e.g. the
+    /// walls near a square the user pointed at), by placing each one and
+    /// comparing both players' distances to goal before and after. Returns the
+    /// wall and its score under `objective` (see `WallObjective`); ties go to
+    /// the earlier candidate. Illegal or malformed candidates are skipped, so
+    /// None means no candidate can be placed.
+    pub fn best_wall_among(&self, candidates: &[String], objective: WallObjective) -> Option<(String, f64)> {
+        let player = self.active_player;
+        let own_now = self.distance_to_goal(player);
+        let opponent_now = self.distance_to_goal(player.opponent());
+
+        let mut best: Option<(String, f64)> = None;
+        for wall_move in candidates {
+            if !matches!(classify_move(wall_move, self.size), MoveKind::Wall(..)) { continue; }
+            let Some((own_after, opponent_after)) = self.distances_after_wall(wall_move, true) else { continue; };
+            let own_increase = own_after.saturating_sub(own_now) as f64;
+            let opponent_increase = opponent_after.saturating_sub(opponent_now) as f64;
+            let score = match objective {
+                WallObjective::BlockOpponent => opponent_increase,
+                WallObjective::ProtectSelf => -own_increase,
+                WallObjective::NetSwing => opponent_increase - own_increase,
+            };
+            if best.as_ref().is_none_or(|(_, best_score)| score > *best_score) {
+                best = Some((wall_move.clone(), score));
+            }
+        }
+        best
+    }
 }
 
 // --- Tests for Game Logic ---
@@ -570,7 +1430,7 @@ mod game_tests {
 
     #[test]
     fn test_new_game() {
-        let game = Quoridor::new(9, 10, None);
+        let game = Quoridor::standard();
         assert_eq!(game.size, 9);
         assert_eq!(game.walls, 10);
         assert_eq!(game.pawn_positions[&Player::Player1], (8, 4));
@@ -582,7 +1442,7 @@ mod game_tests {
 
      #[test]
      fn test_pawn_move() {
-         let mut game = Quoridor::new(9, 10, None);
+         let mut game = Quoridor::standard();
          assert_eq!(game.active_player, Player::Player1);
          assert!(game.move_pawn("e2", true)); // P1 moves from e1 to e2
          assert_eq!(game.pawn_positions[&Player::Player1], (7, 4)); // (row 7, col 4)
@@ -601,7 +1461,7 @@ mod game_tests {
 
      #[test]
      fn test_illegal_pawn_move() {
-          let mut game = Quoridor::new(9, 10, None);
+          let mut game = Quoridor::standard();
           assert!(!game.move_pawn("e3", true)); // Cannot move 2 squares
           assert_eq!(game.pawn_positions[&Player::Player1], (8, 4)); // Position unchanged
           assert_eq!(game.active_player, Player::Player1); // Player unchanged
@@ -609,7 +1469,7 @@ mod game_tests {
 
      #[test]
       fn test_add_wall() {
-          let mut game = Quoridor::new(9, 10, None);
+          let mut game = Quoridor::standard();
           assert_eq!(game.walls_available[&Player::Player1], 10);
           assert!(game.add_wall("e8h", false, true)); // P1 places wall near P2 start
           assert_eq!(game.walls_available[&Player::Player1], 9);
@@ -640,7 +1500,7 @@ mod game_tests {
 
       #[test]
       fn test_wall_intersection() {
-          let mut game = Quoridor::new(9, 10, None);
+          let mut game = Quoridor::standard();
           assert!(game.add_wall("e5h", false, true)); // P1 places horizontal
           assert_eq!(game.active_player, Player::Player2);
           // P2 tries to place vertical intersecting wall
@@ -651,7 +1511,7 @@ mod game_tests {
 
        #[test]
        fn test_wall_overlap() {
-           let mut game = Quoridor::new(9, 10, None);
+           let mut game = Quoridor::standard();
            assert!(game.add_wall("e5h", false, true)); // P1 places horizontal
            assert_eq!(game.active_player, Player::Player2);
            // P2 tries to place overlapping horizontal wall
@@ -664,19 +1524,19 @@ mod game_tests {
 
       #[test]
       fn test_distance_goal() {
-           let game = Quoridor::new(9, 10, None);
+           let game = Quoridor::standard();
            assert_eq!(game.distance_to_goal(Player::Player1), 8); // e1 to row 0
            assert_eq!(game.distance_to_goal(Player::Player2), 8); // e9 to row 8
 
            // Add a wall and check again
-           let mut game_walled = Quoridor::new(9, 10, None);
+           let mut game_walled = Quoridor::standard();
            game_walled.add_wall("e2h", false, true); // Block direct path for P1 and P2
            assert!(game_walled.distance_to_goal(Player::Player1) > 8);
            assert!(game_walled.distance_to_goal(Player::Player2) > 8); // P2 also effected
       }
        #[test]
        fn test_win_check() {
-            let mut game = Quoridor::new(9, 10, None);
+            let mut game = Quoridor::standard();
             game.pawn_positions.insert(Player::Player1, (1, 4)); // P1 at e8
             game.active_player = Player::Player1;
             assert!(game.win_check("e9")); // Moving to e9 (row 0) is a win for P1
@@ -686,9 +1546,25 @@ mod game_tests {
             assert!(game.win_check("e1")); // Moving to e1 (row 8) is a win for P2
        }
 
+       #[test]
+       fn test_opponent_can_win_next() {
+            let game = Quoridor::standard();
+            assert_eq!(game.opponent_can_win_next(), None); // No threat at the start
+
+            // P2 on e2, one square from its goal row; P1 to move
+            let game = Quoridor::new(9, 10, Some(" / / e5 e2 / 10 10 / 1"));
+            assert_eq!(game.opponent_can_win_next(), Some("e1".to_string()));
+            assert!(game.is_winning_move_for(Player::Player2, "e1"));
+            assert!(!game.win_check("e1")); // Not a win for the active player
+
+            // A wall under P2 removes the threat
+            let game = Quoridor::new(9, 10, Some("d1 / / e5 e2 / 10 10 / 1"));
+            assert_eq!(game.opponent_can_win_next(), None);
+       }
+
         #[test]
         fn test_legal_moves_simple() {
-             let game = Quoridor::new(9, 10, None); // P1 at e1, P2 at e9
+             let game = Quoridor::standard(); // P1 at e1, P2 at e9
              let p1_moves = game.get_legal_moves(Player::Player1);
              assert_eq!(p1_moves.len(), 3); // d1, e2, f1
              assert!(p1_moves.contains(&"d1".to_string()));
@@ -718,6 +1594,24 @@ mod game_tests {
               assert!(!p1_moves.contains(&"f6".to_string())); // No diagonal
          }
 
+        #[test]
+        fn test_all_legal_moves_combines_pawn_and_wall_moves() {
+             let mut game = Quoridor::standard();
+             game.add_wall("e2h", false, true);
+             let player = game.active_player;
+
+             let pawn_moves = game.get_legal_moves(player);
+             let wall_moves = game.get_legal_walls(player);
+             let all_moves = game.all_legal_moves(player);
+
+             assert_eq!(all_moves.len(), pawn_moves.len() + wall_moves.len());
+             // Pawn moves come first (their internal order is not guaranteed), walls follow in order
+             let (pawn_part, wall_part) = all_moves.split_at(pawn_moves.len());
+             let pawn_set: HashSet<&String> = pawn_part.iter().collect();
+             assert_eq!(pawn_set, pawn_moves.iter().collect::<HashSet<_>>());
+             assert_eq!(wall_part, wall_moves.as_slice());
+        }
+
           #[test]
           fn test_legal_moves_jump_blocked() {
                // P1 at e5, P2 at e6, Wall at e6h (blocks jump to e7 and adds side)
@@ -733,4 +1627,877 @@ mod game_tests {
                assert!(p1_moves.contains(&"e4".to_string()));
                assert!(!p1_moves.contains(&"e7".to_string())); // Straight jump blocked
           }
+
+          #[test]
+          fn test_neighbor_cache_tracks_wall_placements() {
+               use rand::rngs::StdRng;
+               use rand::seq::SliceRandom;
+               use rand::SeedableRng;
+
+               let mut rng = StdRng::seed_from_u64(7);
+               let mut game = Quoridor::standard();
+               for _ in 0..16 {
+                    let walls = game.get_legal_walls(game.active_player);
+                    let Some(wall) = walls.choose(&mut rng) else { break; };
+                    assert!(game.add_wall(wall, false, true));
+
+                    // Cache agrees with the graph for every node
+                    for node in game.graph.node_indices() {
+                         let cached: HashSet<NodeIndex> = game.neighbors[node.index()].as_slice().iter().copied().collect();
+                         let actual: HashSet<NodeIndex> = game.graph.neighbors(node).collect();
+                         assert_eq!(cached, actual, "cache mismatch at {:?}", game.graph[node]);
+                    }
+
+                    // Pawn moves match those generated from a freshly built cache
+                    let mut rebuilt = game.clone();
+                    rebuilt.neighbors = build_neighbor_cache(&rebuilt.graph);
+                    for player in [Player::Player1, Player::Player2] {
+                         let moves: HashSet<String> = game.get_legal_moves(player).into_iter().collect();
+                         let expected: HashSet<String> = rebuilt.get_legal_moves(player).into_iter().collect();
+                         assert_eq!(moves, expected);
+                    }
+               }
+          }
+
+          #[test]
+          fn test_reachable_within_open_board() {
+               let game = Quoridor::standard();
+               // P1 on e1: n=0 is just e1; n=1 adds d1, f1, e2; n=2 adds c1, g1, d2, f2, e3
+               assert_eq!(game.reachable_within(Player::Player1, 0).len(), 1);
+               let one = game.reachable_within(Player::Player1, 1);
+               assert_eq!(one.len(), 4);
+               assert!(one.contains(&game.algebraic_to_coord("e2")));
+               let two = game.reachable_within(Player::Player1, 2);
+               assert_eq!(two.len(), 9);
+               assert!(two.contains(&game.algebraic_to_coord("e3")));
+               assert!(!two.contains(&game.algebraic_to_coord("e4")));
+          }
+
+          #[test]
+          fn test_reachable_within_respects_walls() {
+               let mut game = Quoridor::standard();
+               // Horizontal wall directly above e1/f1 (blocks e1-e2 and f1-f2)
+               assert!(game.add_wall("e1h", false, true));
+               let one = game.reachable_within(Player::Player1, 1);
+               assert_eq!(one.len(), 3); // e1, d1, f1
+               assert!(!one.contains(&game.algebraic_to_coord("e2")));
+          }
+
+          fn transcript(moves: &[&str]) -> Vec<String> {
+               moves.iter().map(|m| m.to_string()).collect()
+          }
+
+          #[test]
+          fn test_validate_legal_sequence() {
+               let game = Quoridor::standard();
+               let moves = transcript(&["e2", "e8", "e3", "e7", "a5h", "h6v", "e4", "d7"]);
+               assert_eq!(game.validate_sequence(&moves), Ok(()));
+               assert_eq!(game.validate_sequence(&[]), Ok(()));
+               assert_eq!(game.ply(), 0); // The original game is not modified
+          }
+
+          #[test]
+          fn test_validate_sequence_reports_first_illegal_move() {
+               let game = Quoridor::standard();
+               // b5h overlaps the a5h wall placed two plies earlier
+               let moves = transcript(&["e2", "e8", "a5h", "e7", "b5h", "e6", "z0"]);
+               assert_eq!(game.validate_sequence(&moves), Err((4, "b5h".to_string())));
+
+               // Malformed squares are reported rather than panicking
+               assert_eq!(game.validate_sequence(&transcript(&["e2", "z0"])), Err((1, "z0".to_string())));
+
+               // Nothing may follow a winning move
+               let near_goal = Quoridor::new(9, 10, Some(" / / e8 a5 / 10 10 / 1"));
+               assert_eq!(near_goal.validate_sequence(&transcript(&["e9", "a4"])), Err((1, "a4".to_string())));
+          }
+
+          #[test]
+          fn test_legal_move_order_is_fixed() {
+               let game = Quoridor::standard();
+               assert_eq!(game.get_legal_moves(Player::Player1), vec!["e2", "d1", "f1"]);
+               assert_eq!(game.get_legal_moves(Player::Player2), vec!["d9", "f9", "e8"]);
+               let walls = game.get_legal_walls(Player::Player1);
+               assert_eq!(walls[..4], ["a8h", "a8v", "b8h", "b8v"]);
+               assert_eq!(walls.last().map(String::as_str), Some("h1v"));
+
+               // Jump moves are ordered the same way (e6h walls off e7, so P1 jumps diagonally)
+               let jumps = Quoridor::new(9, 10, Some("e6 / / e5 e6 / 10 10 / 1"));
+               assert_eq!(jumps.get_legal_moves(Player::Player1), vec!["d6", "f6", "d5", "f5", "e4"]);
+          }
+
+          #[test]
+          fn test_opponent_blocking_corridor_lengthens_path() {
+               // 5x5: vertical walls b4, c4 and d4 make c4-c5 a one-wide corridor, and P2
+               // sits at its end on c5. Ignoring P2, c1 -> c5 is 4 steps; with P2 there
+               // P1 can't stop on c5 or jump past it, so it detours via the b-file.
+               let game = Quoridor::new(5, 3, Some(" / b4c4d4 / c1 c5 / 1 1 / 1"));
+               assert_eq!(game.distance_to_goal(Player::Player1), 4);
+               assert_eq!(game.distance_to_goal_with_opponent(Player::Player1), 5);
+          }
+
+          #[test]
+          fn test_jump_shortens_opponent_aware_path() {
+               let game = Quoridor::new(5, 3, Some(" / / c3 c4 / 3 3 / 1"));
+               assert_eq!(game.distance_to_goal(Player::Player1), 2);
+               assert_eq!(game.distance_to_goal_with_opponent(Player::Player1), 1); // Jump c3 -> c5
+          }
+
+          #[test]
+          fn test_jumps_can_be_disabled() {
+               let state = " / / e5 e6 / 10 10 / 1";
+               let standard = Quoridor::new(9, 10, Some(state));
+               assert_eq!(standard.get_legal_moves(Player::Player1), vec!["e7", "d5", "f5", "e4"]);
+
+               let no_jumps = Quoridor::new(9, 10, Some(state)).with_rules(RuleSet { allow_jumps: false });
+               assert_eq!(no_jumps.get_legal_moves(Player::Player1), vec!["d5", "f5", "e4"]);
+               assert!(!no_jumps.clone().move_pawn("e7", true));
+
+               // With the straight jump walled off, the diagonals are also gone
+               let walled = Quoridor::new(9, 10, Some("e6 / / e5 e6 / 10 10 / 1")).with_rules(RuleSet { allow_jumps: false });
+               assert_eq!(walled.get_legal_moves(Player::Player1), vec!["d5", "f5", "e4"]);
+          }
+
+          /// Legal walls via the full path search for every candidate (the pre-filter-free reference).
+          fn legal_walls_full_check(game: &Quoridor, player: Player) -> Vec<(Coord, char)> {
+               let mut walls = Vec::new();
+               for r in 1..game.size {
+                    for c in 0..game.size - 1 {
+                         for orientation in ['h', 'v'] {
+                              if game.is_wall_placement_valid(player, (r, c), orientation) {
+                                   walls.push(((r, c), orientation));
+                              }
+                         }
+                    }
+               }
+               walls
+          }
+
+          /// Positions from random play with a strong bias towards walls.
+          fn wall_heavy_positions(seed: u64, games: usize) -> Vec<Quoridor> {
+               wall_heavy_positions_on(9, 10, seed, games)
+          }
+
+          /// `wall_heavy_positions` on any board size and wall count.
+          fn wall_heavy_positions_on(size: usize, walls: usize, seed: u64, games: usize) -> Vec<Quoridor> {
+               use rand::prelude::*;
+               let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+               let mut positions = Vec::new();
+               for _ in 0..games {
+                    let mut game = Quoridor::new(size, walls, None);
+                    for _ in 0..40 {
+                         positions.push(game.clone());
+                         let player = game.active_player;
+                         let walls = game.get_legal_walls(player);
+                         let moved = if !walls.is_empty() && rng.gen_bool(0.7) {
+                              let wall = walls.choose(&mut rng).unwrap();
+                              game.add_wall(wall, false, true)
+                         } else {
+                              let moves = game.get_legal_moves(player);
+                              let Some(mv) = moves.choose(&mut rng) else { break; };
+                              game.move_pawn(mv, true)
+                         };
+                         if !moved || game.distance_to_goal(Player::Player1) == 0 || game.distance_to_goal(Player::Player2) == 0 { break; }
+                    }
+               }
+               positions
+          }
+
+          #[test]
+          fn test_shortest_path_to_goal() {
+               let game = Quoridor::standard();
+               let path = game.shortest_path_to_goal(Player::Player1).unwrap();
+               assert_eq!(path.len(), 9); // e1 plus 8 steps
+               assert_eq!(path[0], game.algebraic_to_coord("e1"));
+               assert_eq!(path[8].0, 0);
+               assert!(path.windows(2).all(|step| step[0].0.abs_diff(step[1].0) + step[0].1.abs_diff(step[1].1) == 1));
+          }
+
+          #[test]
+          fn test_best_wall_among_follows_objective() {
+               // P1 (to move) on c2, P2 on d3: c2h lengthens both paths by one,
+               // d2h only P2's, and d4h neither
+               let game = Quoridor::new(5, 3, Some(" / / c2 d3 / 3 3 / 1"));
+               let candidates: Vec<String> = ["d4h", "c2h", "d2h", "z9h", "c3"].iter().map(|s| s.to_string()).collect();
+               assert_eq!(game.best_wall_among(&candidates, WallObjective::NetSwing), Some(("d2h".to_string(), 1.0)));
+               assert_eq!(game.best_wall_among(&candidates, WallObjective::BlockOpponent), Some(("c2h".to_string(), 1.0)));
+               assert_eq!(game.best_wall_among(&candidates, WallObjective::ProtectSelf), Some(("d4h".to_string(), 0.0)));
+               assert_eq!(game.best_wall_among(&candidates[3..], WallObjective::NetSwing), None);
+          }
+
+          #[test]
+          fn test_wall_beside_route_adds_path_pressure() {
+               // P2 runs straight down the e-file; e5v lines e5 and e6 without blocking it
+               let open = Quoridor::standard();
+               let mut pressed = Quoridor::standard();
+               assert!(pressed.add_wall("e5v", false, true));
+               assert_eq!(open.opponent_path_pressure(Player::Player1), 0);
+               assert_eq!(pressed.opponent_path_pressure(Player::Player1), 3); // e7-e6, e6-e5, e5-e4
+               assert_eq!(pressed.distance_to_goal(Player::Player2), open.distance_to_goal(Player::Player2));
+          }
+
+          #[test]
+          fn test_shortest_paths_both_match_single_searches() {
+               let mut game = Quoridor::standard();
+               for wall in ["e2h", "d7v", "c5h"] {
+                    assert!(game.add_wall(wall, false, true));
+               }
+               let (p1_path, p2_path) = game.shortest_paths_both();
+               assert_eq!(p1_path, game.shortest_path_to_goal(Player::Player1));
+               assert_eq!(p2_path, game.shortest_path_to_goal(Player::Player2));
+               assert_eq!(p1_path.map(|path| path.len() - 1), Some(game.distance_to_goal(Player::Player1)));
+
+               // A walled-off player has no path, the other keeps theirs
+               let boxed = Quoridor::new_unchecked(5, 3, "a2 / a1 / a1 c5 / 3 3 / 1");
+               let (p1_path, p2_path) = boxed.shortest_paths_both();
+               assert_eq!(p1_path, None);
+               assert_eq!(p2_path, boxed.shortest_path_to_goal(Player::Player2));
+               assert!(p2_path.is_some());
+          }
+
+          #[test]
+          fn test_prefiltered_legal_walls_match_full_check() {
+               for game in wall_heavy_positions(5, 12) {
+                    for player in [Player::Player1, Player::Player2] {
+                         assert_eq!(game.get_legal_walls_coords(player), legal_walls_full_check(&game, player), "state {}", game.state_string);
+                    }
+               }
+          }
+
+          /// Rough wall-generation benchmark against the full per-wall path search.
+          /// Run with `cargo test --release -- --ignored --nocapture`.
+          #[test]
+          #[ignore]
+          fn bench_prefiltered_legal_walls() {
+               use std::time::Instant;
+               let positions = wall_heavy_positions(9, 10);
+
+               let start = Instant::now();
+               let full: usize = positions.iter().map(|g| legal_walls_full_check(g, g.active_player).len()).sum();
+               let full_time = start.elapsed();
+
+               let start = Instant::now();
+               let fast: usize = positions.iter().map(|g| g.get_legal_walls_coords(g.active_player).len()).sum();
+               let fast_time = start.elapsed();
+
+               assert_eq!(full, fast);
+               println!("{} positions: full check {:.2?}, pre-filtered {:.2?}", positions.len(), full_time, fast_time);
+          }
+
+          #[test]
+          fn test_wall_with_none_in_hand_fails_cleanly() {
+               let mut game = Quoridor::new(9, 10, Some(" / / e1 e9 / 0 10 / 1"));
+               let before = game.state_string.clone();
+               // Skip the legality checks to reach the internal bookkeeping directly
+               assert!(!game.add_wall("e3h", false, false));
+               assert_eq!(game.walls_available[&Player::Player1], 0);
+               assert!(game.hwall_positions.is_empty());
+               assert_eq!(game.state_string, before);
+               assert_eq!(game.ply(), 0);
+          }
+
+          #[test]
+          fn test_ply_counts_both_move_types() {
+               let mut game = Quoridor::standard();
+               assert_eq!(game.ply(), 0);
+               assert!(game.move_pawn("e2", true));
+               assert_eq!(game.ply(), 1);
+               assert!(game.add_wall("e3h", false, true));
+               assert_eq!(game.ply(), 2);
+
+               // Rejected moves don't count
+               assert!(!game.move_pawn("a1", true));
+               assert_eq!(game.ply(), 2);
+
+               // A new game starts over, as does loading a position
+               assert_eq!(Quoridor::standard().ply(), 0);
+               assert_eq!(Quoridor::new(9, 10, Some(&game.state_string)).ply(), 0);
+          }
+
+          #[test]
+          fn test_choke_point_wall_ranks_first() {
+               // 5x5 board: b3h and d3h close every file except a between ranks 3 and 4,
+               // so P2 on a5 runs straight down the a-file corridor (4 steps).
+               let game = Quoridor::new(5, 3, Some("b3d3 / / c1 a5 / 3 3 / 1"));
+               assert_eq!(game.distance_to_goal(Player::Player2), 4);
+
+               // a4h shuts the corridor entrance and forces a detour via the c-file
+               let ranked = game.find_maximally_blocking_walls(Player::Player1, 3);
+               assert_eq!(ranked.len(), 3);
+               assert_eq!(ranked[0], ("a4h".to_string(), 8));
+               assert!(ranked[1].1 < ranked[0].1);
+               assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+          }
+
+          #[test]
+          fn test_legal_wall_coords_match_strings() {
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("e3h", false, true));
+               let player = game.active_player;
+
+               let coords = game.get_legal_walls_coords(player);
+               let strings = game.get_legal_walls(player);
+               assert_eq!(coords.len(), strings.len());
+               for ((coord, orientation), wall) in coords.iter().zip(&strings) {
+                    assert_eq!(format!("{}{}", game.coord_to_algebraic(*coord), orientation), *wall);
+                    assert_eq!(game.algebraic_to_coord(&wall[..wall.len() - 1]), *coord);
+               }
+          }
+
+          #[test]
+          fn test_advantage() {
+               assert!(Quoridor::standard().advantage().abs() < 1e-9);
+
+               // Equal race with the pawns on different files
+               let mut game = Quoridor::new(9, 10, Some(" / / d1 e9 / 10 10 / 1"));
+               assert!(game.advantage().abs() < 1e-9);
+
+               // P1 walls off P2's straight path (e8h blocks e9-e8 and f9-f8)
+               assert!(game.add_wall("e8h", false, true));
+               assert!(game.advantage() > 0.0);
+               assert!(game.advantage() <= 1.0);
+          }
+
+          #[test]
+          fn test_blocked_edges_by_wall_lengths() {
+               assert_eq!(get_blocked_edges_by_wall((3, 3), 'h', 9).map(|e| e.len()), Some(2));
+               assert_eq!(get_blocked_edges_by_wall((3, 3), 'v', 9).map(|e| e.len()), Some(2));
+               assert_eq!(get_blocked_edges_by_wall((0, 3), 'v', 9), Some(vec![((0, 3), (0, 4))]));
+               assert_eq!(get_blocked_edges_by_wall((0, 3), 'h', 9), None); // Would lie above the board
+               assert_eq!(get_blocked_edges_by_wall((3, 8), 'v', 9), None); // Right edge
+               assert_eq!(get_blocked_edges_by_wall((3, 3), 'x', 9), None);
+          }
+
+          #[test]
+          fn test_top_row_vertical_wall_removes_one_edge() {
+               let full_edges = Quoridor::standard().graph.edge_count();
+
+               // Via the state string
+               let game = Quoridor::new(9, 10, Some(" / c9 / e1 e9 / 10 10 / 1"));
+               assert_eq!(game.graph.edge_count(), full_edges - 1);
+               let c9 = game.node_indices[&game.algebraic_to_coord("c9")];
+               let d9 = game.node_indices[&game.algebraic_to_coord("d9")];
+               assert!(game.graph.find_edge(c9, d9).is_none());
+               assert!(!game.neighbors[c9.index()].contains(d9));
+
+               // Via add_wall without checks
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("a9v", false, false));
+               assert_eq!(game.graph.edge_count(), full_edges - 1);
+               let a9 = game.node_indices[&game.algebraic_to_coord("a9")];
+               let b9 = game.node_indices[&game.algebraic_to_coord("b9")];
+               assert!(game.graph.find_edge(a9, b9).is_none());
+          }
+
+          #[test]
+          fn test_jump_over_opponent_on_top_row() {
+               // P1 on e8 faces P2 on e9: the straight jump would leave the board
+               let game = Quoridor::new(9, 10, Some(" / / e8 e9 / 10 10 / 1"));
+               let p1_moves: HashSet<String> = game.get_legal_moves(Player::Player1).into_iter().collect();
+               let expected: HashSet<String> = ["d8", "f8", "e7", "d9", "f9"].iter().map(|s| s.to_string()).collect();
+               assert_eq!(p1_moves, expected);
+          }
+
+          #[test]
+          fn test_jump_over_opponent_on_side_edge() {
+               // P1 on b5 faces P2 on a5 across the left edge: diagonals a6 and a4 only
+               let game = Quoridor::new(9, 10, Some(" / / b5 a5 / 10 10 / 1"));
+               let p1_moves: HashSet<String> = game.get_legal_moves(Player::Player1).into_iter().collect();
+               let expected: HashSet<String> = ["c5", "b6", "b4", "a6", "a4"].iter().map(|s| s.to_string()).collect();
+               assert_eq!(p1_moves, expected);
+          }
+
+          #[test]
+          fn test_no_diagonal_jump_when_straight_jump_open() {
+               // P2 on e2 faces P1 on e1 from above; jumping off the bottom edge gives diagonals,
+               // but with P1 on e3 instead the straight jump to e4 is open and diagonals are not offered
+               let edge = Quoridor::new(9, 10, Some(" / / e1 e2 / 10 10 / 2"));
+               let edge_moves: HashSet<String> = edge.get_legal_moves(Player::Player2).into_iter().collect();
+               assert!(edge_moves.contains("d1") && edge_moves.contains("f1"));
+
+               let open = Quoridor::new(9, 10, Some(" / / e3 e4 / 10 10 / 1"));
+               let open_moves: HashSet<String> = open.get_legal_moves(Player::Player1).into_iter().collect();
+               assert!(open_moves.contains("e5"));
+               assert!(!open_moves.contains("d4") && !open_moves.contains("f4"));
+          }
+
+          #[test]
+          fn test_standard_and_mini_constructors() {
+               let standard = Quoridor::standard();
+               assert_eq!((standard.size, standard.walls), (9, 10));
+               assert_eq!(standard.state_string, Quoridor::new(9, 10, None).state_string);
+
+               let mini = Quoridor::mini();
+               assert_eq!((mini.size, mini.walls), (5, 3));
+               assert_eq!(mini.pawn_positions[&Player::Player1], (4, 2));
+               assert_eq!(mini.walls_available[&Player::Player2], 3);
+          }
+
+          #[test]
+          fn test_wall_next_to_pawn_reduces_mobility() {
+               let mut game = Quoridor::standard();
+               assert_eq!(game.mobility(Player::Player1), 3); // e2, d1, f1
+               assert_eq!(game.mobility(Player::Player2), 3);
+
+               // e1h closes e1-e2, leaving only the sideways steps
+               assert!(game.add_wall("e1h", false, true));
+               assert_eq!(game.mobility(Player::Player1), 2);
+               assert_eq!(game.mobility(Player::Player2), 3);
+          }
+
+          #[test]
+          fn test_state_string_walling_off_a_player_is_rejected() {
+               // c1 boxed in by c1h above and b1v/c1v on either side
+               let result = Quoridor::try_new(5, 3, Some("c1 / b1c1 / c1 c5 / 3 3 / 1"));
+               assert_eq!(result.err(), Some(Player::Player1));
+
+               let open = Quoridor::try_new(5, 3, Some("c1 / b1 / c1 c5 / 3 3 / 1"));
+               assert!(open.is_ok_and(|game| game.validate_reachability().is_ok()));
+          }
+
+          #[test]
+          #[should_panic(expected = "no path to goal")]
+          fn test_new_refuses_walled_off_state() {
+               Quoridor::new(5, 3, Some("c1 / b1c1 / c1 c5 / 3 3 / 1"));
+          }
+
+          #[test]
+          fn test_flipping_twice_is_identity() {
+               let game = Quoridor::new(9, 10, Some("e3c7 / b5 / e4 d6 / 8 9 / 2"));
+               let flipped = game.flipped();
+               assert_eq!(flipped.pawn_positions[&Player::Player1], (5, 3)); // P2's d6 seen from the other side: d4
+               assert_eq!(flipped.walls_available[&Player::Player1], 9);
+               assert_eq!(flipped.active_player, Player::Player1);
+
+               let back = flipped.flipped();
+               assert_eq!(back.state_string, game.state_string);
+               assert!(game.diff(&back).is_empty());
+          }
+
+          #[test]
+          fn test_add_wall_at_matches_algebraic_wall() {
+               let mut by_coord = Quoridor::standard();
+               let mut by_name = Quoridor::standard();
+               assert!(by_coord.add_wall_at((1, 4), 'h', true));
+               assert!(by_name.add_wall("e8h", false, true));
+
+               assert_eq!(by_coord.state_string, by_name.state_string);
+               assert_eq!(by_coord.last_move, "e8h");
+               assert!(by_coord.diff(&by_name).is_empty());
+               assert!(!by_coord.add_wall_at((1, 4), 'x', false));
+          }
+
+          #[test]
+          fn test_check_config_rejects_bad_boards() {
+               assert!(Quoridor::check_config(9, 10).is_ok());
+               assert!(Quoridor::check_config(3, 2).is_ok());
+               assert!(Quoridor::check_config(8, 10).is_err()); // Even sizes panic in `new`
+               assert!(Quoridor::check_config(1, 0).is_err());
+               assert!(Quoridor::check_config(5, 9).is_err());
+               assert!(Quoridor::check_config(9, usize::MAX).is_err());
+          }
+
+          #[test]
+          fn test_strict_parse_rejects_overlapping_walls() {
+               // c3h covers c-d and d3h covers d-e: they overlap on d
+               let overlapping = "c3d3 / / e1 e9 / 8 10 / 1";
+               let err = Quoridor::try_new_strict(9, 10, overlapping).err().unwrap();
+               assert!(err.contains("d3h"), "{}", err);
+               assert!(Quoridor::try_new(9, 10, Some(overlapping)).is_ok()); // Lenient parsing loads it, skipping d3h
+
+               // Crossing walls share a midpoint
+               assert!(Quoridor::try_new_strict(9, 10, "c3 / c3 / e1 e9 / 8 10 / 1").is_err());
+
+               let fine = Quoridor::try_new_strict(9, 10, "c3e3 / a5 / e1 e9 / 7 10 / 1").unwrap();
+               assert_eq!(fine.state_string, Quoridor::new(9, 10, Some("c3e3 / a5 / e1 e9 / 7 10 / 1")).state_string);
+          }
+
+          #[test]
+          fn test_distance_field_matches_distance_to_goal() {
+               let game = Quoridor::new(9, 10, Some("d4e6 / c5 / e3 d7 / 7 9 / 1"));
+               for player in [Player::Player1, Player::Player2] {
+                    let field = game.distance_field(player);
+                    let (r, c) = game.pawn_positions[&player];
+                    assert_eq!(field[r][c], Some(game.distance_to_goal(player)));
+                    assert!(game.goal_positions[&player].iter().all(|&(gr, gc)| field[gr][gc] == Some(0)));
+               }
+
+               // c1, boxed in by c1h, b1v and c1v, is unreachable
+               let boxed = Quoridor::new(5, 3, Some("c1 / b1c1 / a5 c5 / 3 3 / 1"));
+               assert_eq!(boxed.distance_field(Player::Player1)[4][2], None);
+          }
+
+          #[test]
+          fn test_walls_pinning_pawn_to_edge_reduce_mobility() {
+               // P2 on a8 can step to a9, a7 and b8
+               let game = Quoridor::new(9, 10, Some(" / / e1 a8 / 10 10 / 1"));
+               assert_eq!(game.mobility(Player::Player2), 3);
+
+               let ranked = game.walls_reducing_opponent_mobility(Player::Player2);
+               let a8v = ranked.iter().find(|(wall, _)| wall == "a8v").expect("a8v cuts off b8");
+               assert_eq!(a8v.1, 1);
+               assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+               // It costs P2 a move option without lengthening its path at all
+               let mut after = game.clone();
+               assert!(after.add_wall("a8v", false, true));
+               assert_eq!(after.mobility(Player::Player2), 2);
+               assert_eq!(after.distance_to_goal(Player::Player2), game.distance_to_goal(Player::Player2));
+          }
+
+          #[test]
+          fn test_double_applied_wall_keeps_graph_in_step() {
+               let mut game = Quoridor::standard();
+               let full_edges = game.graph.edge_count();
+               assert!(game.add_wall("e3h", false, false));
+               assert!(!game.add_wall("e3h", false, false)); // Its edges are already gone
+               assert!(!game.add_wall("d3h", false, false)); // Shares e3h's e-file segment
+
+               // One wall in the set, its two edges removed, one wall spent
+               assert_eq!(game.hwall_positions.len(), 1);
+               assert_eq!(game.graph.edge_count(), full_edges - 2);
+               assert_eq!(game.walls_available[&Player::Player1], 9);
+               assert_eq!(game.active_player, Player::Player2);
+          }
+
+          #[test]
+          fn test_walls_placed_and_game_phase() {
+               let mut game = Quoridor::new(5, 2, None);
+               assert_eq!(game.walls_placed(), 0);
+               assert_eq!(game.game_phase(), Phase::Opening);
+
+               // Pawn moves alone stay in the opening until `size` plies
+               for mv in ["c2", "c4", "b2", "b4"] {
+                    assert!(game.move_pawn(mv, true));
+               }
+               assert_eq!(game.game_phase(), Phase::Opening);
+
+               // The first wall ends the opening
+               assert!(game.add_wall("d3h", false, true));
+               assert_eq!(game.walls_placed(), 1);
+               assert_eq!(game.game_phase(), Phase::Midgame);
+               assert!(!game.add_wall("d3h", false, true)); // Rejected walls aren't counted
+               assert!(game.add_wall("a1v", false, true));
+               assert_eq!(game.walls_placed(), 2);
+               assert_eq!(game.game_phase(), Phase::Midgame);
+
+               // Three of the four walls down is the endgame
+               assert!(game.add_wall("a3h", false, true));
+               assert_eq!(game.walls_placed(), 3);
+               assert_eq!(game.game_phase(), Phase::Endgame);
+
+               // Without walls, only the ply count moves the phase on
+               let mut race = Quoridor::new(5, 0, None);
+               for (ply, mv) in ["c2", "c4", "b2", "b4", "a2", "a4", "b2", "b4"].iter().enumerate() {
+                    assert!(race.move_pawn(mv, true));
+                    let expected = if ply + 1 < 5 { Phase::Opening } else { Phase::Midgame };
+                    assert_eq!(race.game_phase(), expected);
+               }
+          }
+
+          #[test]
+          fn test_contested_band_on_symmetric_start() {
+               let game = Quoridor::standard();
+               let rows = |threshold: usize| -> BTreeSet<usize> {
+                    game.contested_squares(threshold).into_iter().map(|(row, _)| row).collect()
+               };
+               // The middle row (rank 5) is equidistant; each step of threshold
+               // adds a row on both sides
+               assert_eq!(game.contested_squares(0).len(), 9);
+               assert_eq!(rows(0), BTreeSet::from([4]));
+               assert_eq!(rows(1), BTreeSet::from([4]));
+               assert_eq!(rows(2), BTreeSet::from([3, 4, 5]));
+
+               // A wall detour moves the band for the squares behind it
+               let walled = Quoridor::new(9, 10, Some("a4c4 / / e1 e9 / 8 10 / 2"));
+               assert!(!walled.contested_squares(0).contains(&(4, 0)));
+          }
+
+          #[test]
+          fn test_apply_move_on_two_digit_ranks() {
+               let mut game = Quoridor::new(11, 10, Some(" / / f9 f10 / 10 10 / 1"));
+               assert!(game.apply_move("e9", true)); // P1 steps sideways
+               assert!(game.apply_move("e10", true)); // P2 steps sideways on rank 10, a pawn move
+               assert_eq!(game.pawn_positions[&Player::Player2], (1, 4));
+               assert!(game.apply_move("b10h", true));
+               assert!(game.hwall_positions.contains(&(1, 1)));
+               assert!(!game.apply_move("e12", true)); // Off the board, rejected without panicking
+               assert!(!game.apply_move("", true));
+          }
+
+          #[test]
+          fn test_race_result_without_walls() {
+               // Both four steps from home: the side to move gets there first
+               let tied = Quoridor::new(9, 0, Some(" / / a5 i5 / 0 0 / 2"));
+               assert_eq!(tied.race_result(), Some(Player::Player2));
+               let tied_p1_to_move = Quoridor::new(9, 0, Some(" / / a5 i5 / 0 0 / 1"));
+               assert_eq!(tied_p1_to_move.race_result(), Some(Player::Player1));
+
+               // P1 two steps from home beats P2 five steps away, even with P2 to move
+               let lead = Quoridor::new(9, 0, Some(" / / a7 i6 / 0 0 / 2"));
+               assert_eq!(lead.race_result(), Some(Player::Player1));
+
+               // Not a race while anyone holds a wall
+               assert_eq!(Quoridor::new(9, 10, Some(" / / a7 i6 / 0 1 / 2")).race_result(), None);
+          }
+
+          #[test]
+          fn test_placed_walls_sorted_with_orientation() {
+               let mut game = Quoridor::standard();
+               assert!(game.placed_walls().is_empty());
+               assert!(game.add_wall("e3v", false, true));
+               assert!(game.add_wall("c5h", false, true));
+
+               // Horizontal first whatever the placement order
+               assert_eq!(game.placed_walls(), vec![((4, 2), 'h'), ((6, 4), 'v')]);
+               assert_eq!(game.placed_wall_moves(), vec!["c5h", "e3v"]);
+
+               // The same walls loaded from a state string list identically
+               let loaded = Quoridor::new(9, 10, Some(&game.state_string));
+               assert_eq!(loaded.placed_wall_moves(), game.placed_wall_moves());
+          }
+
+          #[test]
+          fn test_path_bottlenecks_in_wall_gap() {
+               // Horizontal walls across ranks 4/5 leave only the i-file gap
+               let game = Quoridor::new(9, 10, Some("a4c4e4g4 / / e1 e9 / 6 10 / 1"));
+               let gap = vec![game.algebraic_to_coord("i4"), game.algebraic_to_coord("i5")];
+               assert_eq!(game.path_bottlenecks(Player::Player1), gap);
+               let mut reversed = gap.clone();
+               reversed.reverse();
+               assert_eq!(game.path_bottlenecks(Player::Player2), reversed);
+
+               // On an open board every square has a way round it
+               assert!(Quoridor::standard().path_bottlenecks(Player::Player1).is_empty());
+          }
+
+          #[test]
+          fn test_symmetry_score() {
+               assert_eq!(Quoridor::standard().symmetry_score(), 1.0);
+
+               // A lone wall has no reflected partner: 2 of 3 items match
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("c3h", false, true));
+               assert!((game.symmetry_score() - 2.0 / 3.0).abs() < 1e-9);
+
+               // Its reflection through the centre (c3h -> f6h) restores symmetry
+               assert!(game.add_wall("f6h", false, true));
+               assert_eq!(game.symmetry_score(), 1.0);
+               let mut vertical = Quoridor::standard();
+               assert!(vertical.add_wall("b4v", false, true));
+               assert!(vertical.add_wall("g5v", false, true));
+               assert_eq!(vertical.symmetry_score(), 1.0);
+
+               // Pawns out of step count as two mismatches
+               assert!(game.move_pawn("e2", true));
+               assert!((game.symmetry_score() - 0.5).abs() < 1e-9);
+          }
+
+          #[test]
+          fn test_wall_legality_matches_reference_everywhere() {
+               let mut positions = wall_heavy_positions(11, 3);
+               for size in [3, 5, 7] {
+                    positions.extend(wall_heavy_positions_on(size, (size - 1) * (size - 1) / 2, size as u64, 6));
+               }
+               // A half-off vertical wall on the top row, which only state strings can place
+               positions.push(Quoridor::new(5, 3, Some(" / b5 / c1 c5 / 3 3 / 1")));
+
+               for game in &positions {
+                    let player = game.active_player;
+                    let mut expected = Vec::new();
+                    for r in 0..=game.size {
+                         for c in 0..=game.size {
+                              for orientation in ['h', 'v'] {
+                                   let legal = crate::reference::is_wall_legal(game, player, (r, c), orientation);
+                                   assert_eq!(
+                                        game.is_wall_placement_valid(player, (r, c), orientation), legal,
+                                        "{}{} ({:?}) in state {}", game.coord_to_algebraic((r, c)), orientation, (r, c), game.state_string
+                                   );
+                                   if legal { expected.push(((r, c), orientation)); }
+                              }
+                         }
+                    }
+                    // Same order as the generator: rows, then columns, h before v
+                    assert_eq!(game.get_legal_walls_coords(player), expected, "state {}", game.state_string);
+               }
+          }
+
+          #[test]
+          fn test_overlapping_walls_rejected_at_right_edge() {
+               // h5h covers files h and i, the last legal column; g5h would cover g and h
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("h5h", false, true));
+               assert!(!game.add_wall("g5h", false, true));
+               assert!(!game.add_wall("i5h", false, true)); // Would hang off the board
+
+               // And the other way round, with the edge wall placed second
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("g5h", false, true));
+               assert!(!game.add_wall("h5h", false, true));
+               assert!(game.add_wall("h4v", false, true)); // Runs down from the end of g5h: touching is fine
+          }
+
+          #[test]
+          fn test_walls_may_touch_end_to_middle() {
+               // d5h ends at the centre of e5v; e5v ends at the centre of e4h
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("e5v", false, true));
+               assert!(game.add_wall("d5h", false, true));
+               let mut game = Quoridor::standard();
+               assert!(game.add_wall("e4h", false, true));
+               assert!(game.add_wall("e5v", false, true));
+
+               // No wall may hang off the top edge when placed in play
+               assert!(!Quoridor::standard().add_wall("e9v", false, true));
+          }
+
+          #[test]
+          fn test_unique_shortest_path_through_corridor() {
+               // e3h in front of P1 leaves three equally short sidesteps to the d-file
+               let open = Quoridor::new(9, 10, Some("e3 / / e1 e9 / 9 10 / 2"));
+               assert!(!open.has_unique_shortest_path(Player::Player1));
+
+               // d1v walls off the sidesteps on ranks 1 and 2: only e1-e3, d3, up the d-file remains
+               let corridor = Quoridor::new(9, 10, Some("e3 / d1 / e1 e9 / 8 10 / 1"));
+               assert!(corridor.has_unique_shortest_path(Player::Player1));
+
+               // A clear straight run counts as one path as well
+               assert!(Quoridor::standard().has_unique_shortest_path(Player::Player1));
+          }
+
+          #[test]
+          fn test_custom_first_player_and_start_squares() {
+               let game = Quoridor::standard()
+                    .with_first_player(Player::Player2)
+                    .with_start_squares("c2", "c3")
+                    .unwrap();
+               assert_eq!(game.active_player, Player::Player2);
+               assert_eq!(game.state_string, " /  / c2 c3 / 10 10 / 2");
+
+               // P2 on c3 faces P1 on c2 and may jump it; goal lines are unchanged
+               let mut moves = game.get_legal_moves(Player::Player2);
+               moves.sort();
+               assert_eq!(moves, vec!["b3", "c1", "c4", "d3"]);
+               assert_eq!(game.distance_to_goal(Player::Player2), 2);
+               assert_eq!(game.all_legal_moves(game.active_player).len(), 4 + 128);
+
+               let mut played = game.clone();
+               assert!(played.apply_move("c1", true));
+               assert_eq!(played.active_player, Player::Player1);
+
+               assert!(Quoridor::standard().with_start_squares("e1", "e1").is_err());
+               assert!(Quoridor::standard().with_start_squares("j1", "e9").is_err());
+               assert!(Quoridor::standard().with_start_squares("e1", "e10").is_err());
+          }
+
+          #[test]
+          fn test_legal_moves_mask_matches_move_list() {
+               let positions = [
+                    Quoridor::standard(),
+                    Quoridor::new(9, 10, Some("e6 / / e5 e6 / 10 9 / 1")), // Diagonal jumps
+                    Quoridor::new(5, 3, Some(" / / a1 a2 / 3 3 / 2")), // Corner, 25 bits over 4 bytes
+               ];
+               for game in &positions {
+                    let player = game.active_player;
+                    let mask = game.legal_moves_mask(player);
+                    assert_eq!(mask.len(), (game.size * game.size).div_ceil(8));
+
+                    let mut from_mask = Vec::new();
+                    for index in 0..game.size * game.size {
+                         if mask[index / 8] & (1 << (index % 8)) != 0 {
+                              from_mask.push(game.coord_to_algebraic((index / game.size, index % game.size)));
+                         }
+                    }
+                    let mut expected = game.get_legal_moves(player);
+                    expected.sort();
+                    from_mask.sort();
+                    assert_eq!(from_mask, expected, "state {}", game.state_string);
+               }
+          }
+
+          #[test]
+          fn test_mirror_wall_blocks_reflected_edges() {
+               let game = Quoridor::standard();
+               let reflect = |(r, c): Coord| (game.size - 1 - r, game.size - 1 - c);
+               for wall_move in ["e3h", "a1h", "h8v", "c5v"] {
+                    let (coord, orientation) = match classify_move(wall_move, game.size) {
+                         MoveKind::Wall(coord, orientation) => (coord, orientation),
+                         _ => unreachable!(),
+                    };
+                    let (mirrored, mirrored_orientation) = game.mirror_wall(coord, orientation).unwrap();
+                    let mut expected: Vec<(Coord, Coord)> = get_blocked_edges_by_wall(coord, orientation, game.size).unwrap()
+                         .into_iter()
+                         .map(|(a, b)| (reflect(b), reflect(a)))
+                         .collect();
+                    let mut actual = get_blocked_edges_by_wall(mirrored, mirrored_orientation, game.size).unwrap();
+                    expected.sort();
+                    actual.sort();
+                    assert_eq!(actual, expected, "{}", wall_move);
+                    assert_eq!(game.mirror_wall(mirrored, mirrored_orientation), Some((coord, orientation)));
+               }
+               assert_eq!(game.mirror_wall(game.algebraic_to_coord("e3"), 'h'), Some((game.algebraic_to_coord("d6"), 'h')));
+
+               // Squares no wall can sit on have nothing to mirror to
+               assert_eq!(game.mirror_wall((4, game.size - 1), 'v'), None); // Last file
+               assert_eq!(game.mirror_wall((0, 4), 'h'), None); // Top row
+          }
+
+          #[test]
+          fn test_is_wall_legal_matches_legal_wall_list() {
+               // e3h placed: overlapping (d3h, f3h), crossing (e3v) and edge walls are out
+               let game = Quoridor::new(9, 10, Some("e3 / / e1 e9 / 9 10 / 2"));
+               let legal = game.get_legal_walls(game.active_player);
+               for wall_move in ["e3h", "d3h", "f3h", "e3v", "c3h", "e4v", "a1h", "h8v", "i5h", "a9v"] {
+                    assert_eq!(game.is_wall_legal(wall_move), legal.contains(&wall_move.to_string()), "{}", wall_move);
+               }
+               assert!(game.is_wall_legal("c3h"));
+               assert!(!game.is_wall_legal("e3v"));
+               assert!(!game.is_wall_legal("e8")); // A pawn move
+               assert!(!game.is_wall_legal("z3h"));
+
+               // Nothing is legal without walls in hand
+               let empty_handed = Quoridor::new(9, 10, Some(" / / e1 e9 / 10 0 / 2"));
+               assert!(!empty_handed.is_wall_legal("c3h"));
+          }
+
+          #[test]
+          fn test_move_history_lists_moves_in_order() {
+               let mut game = Quoridor::standard();
+               assert!(game.move_history().is_empty());
+               for move_str in ["e2", "e8", "e3h", "d8", "c3v"] {
+                    assert!(game.apply_move(move_str, true), "{}", move_str);
+               }
+               assert_eq!(game.move_history(), ["e2", "e8", "e3h", "d8", "c3v"]);
+               assert_eq!(game.move_history().len(), game.ply());
+               assert!(!game.apply_move("e3", true)); // Illegal moves leave no trace
+               assert_eq!(game.move_history().last().map(String::as_str), Some(game.last_move.as_str()));
+
+               // A clone shares the moves so far and then goes its own way
+               let mut branch = game.clone();
+               assert!(branch.apply_move("c8", true));
+               assert!(game.apply_move("e8", true));
+               assert_eq!(branch.move_history(), ["e2", "e8", "e3h", "d8", "c3v", "c8"]);
+               assert_eq!(game.move_history(), ["e2", "e8", "e3h", "d8", "c3v", "e8"]);
+
+               // Setup walls in a state string are not moves
+               assert!(Quoridor::new(9, 10, Some("e3 / / e1 e9 / 9 10 / 2")).move_history().is_empty());
+          }
+
+          #[test]
+          fn test_start_columns_off_centre() {
+               let game = Quoridor::standard().with_start_columns(2, 6).unwrap();
+               assert_eq!(game.pawn_positions[&Player::Player1], (8, 2));
+               assert_eq!(game.pawn_positions[&Player::Player2], (0, 6));
+               assert!(game.state_string.contains("c1 g9"));
+
+               let mut moves = game.get_legal_moves(Player::Player1);
+               moves.sort();
+               assert_eq!(moves, vec!["b1", "c2", "d1"]);
+               assert_eq!(game.distance_to_goal(Player::Player2), 8);
+
+               // The edge files work; off-board columns are rejected
+               let corner = Quoridor::mini().with_start_columns(0, 4).unwrap();
+               assert_eq!(corner.get_legal_moves(Player::Player1).len(), 2);
+               assert!(Quoridor::mini().with_start_columns(5, 0).is_err());
+               assert!(Quoridor::mini().with_start_columns(0, 9).is_err());
+          }
 }
@@ -48,21 +48,60 @@ pub(crate) fn initialize_board_graph(
     (graph, node_indices)
 }
 
-/// Returns the coordinates of the two edges a potential wall would block.
+/// Open neighbours of a single square. A grid square never has more than four,
+/// so the list is stored inline instead of allocating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NeighborList {
+    len: u8,
+    nodes: [NodeIndex; 4],
+}
+
+impl NeighborList {
+    /// Collects the current neighbours of `node` from the graph.
+    pub(crate) fn from_graph(graph: &UnGraph<Coord, ()>, node: NodeIndex) -> Self {
+        let mut list = NeighborList { len: 0, nodes: [NodeIndex::end(); 4] };
+        for neighbor in graph.neighbors(node) {
+            list.nodes[list.len as usize] = neighbor;
+            list.len += 1;
+        }
+        list
+    }
+
+    /// The neighbour node indices as a slice.
+    pub fn as_slice(&self) -> &[NodeIndex] {
+        &self.nodes[..self.len as usize]
+    }
+
+    /// Whether `node` is an open neighbour.
+    pub fn contains(&self, node: NodeIndex) -> bool {
+        self.as_slice().contains(&node)
+    }
+}
+
+/// Builds the neighbour cache for every node, indexed by `NodeIndex::index()`.
+pub(crate) fn build_neighbor_cache(graph: &UnGraph<Coord, ()>) -> Vec<NeighborList> {
+    graph.node_indices().map(|node| NeighborList::from_graph(graph, node)).collect()
+}
+
+/// Returns the edges a potential wall would block: two for an interior wall,
+/// one for a vertical wall on the top row (its upper half is off the board).
 /// `wall_coord` is the bottom-left-most coord the wall touches.
 pub(crate) fn get_blocked_edges_by_wall(
     wall_coord: Coord,
     orientation: char, // 'h' or 'v'
     size: usize,
-) -> Option<[(Coord, Coord); 2]> {
+) -> Option<Vec<(Coord, Coord)>> {
     let (r, c) = wall_coord;
+    if r >= size || c + 1 >= size {
+        return None; // Wall placement invalid near edge
+    }
 
     match orientation {
         'h' => {
             // Horizontal wall blocks vertical movement between (r-1, c) <=> (r, c)
             // and (r-1, c+1) <=> (r, c+1)
-            if r > 0 && c + 1 < size {
-                Some([((r - 1, c), (r, c)), ((r - 1, c + 1), (r, c + 1))])
+            if r > 0 {
+                Some(vec![((r - 1, c), (r, c)), ((r - 1, c + 1), (r, c + 1))])
             } else {
                 None // Wall placement invalid near edge
             }
@@ -70,13 +109,10 @@ pub(crate) fn get_blocked_edges_by_wall(
         'v' => {
             // Vertical wall blocks horizontal movement between (r, c) <=> (r, c+1)
             // and (r-1, c) <=> (r-1, c+1)
-            if r > 0 && c + 1 < size {
-                 Some([((r, c), (r, c + 1)), ((r - 1, c), (r - 1, c + 1))])
-            } else if r == 0 && c + 1 < size { // Special case for top edge
-                 Some([((r, c), (r, c + 1)), ((usize::MAX, usize::MAX), (usize::MAX, usize::MAX))]) // Indicate only one edge exists
-            }
-            else {
-                None // Wall placement invalid near edge
+            if r > 0 {
+                 Some(vec![((r, c), (r, c + 1)), ((r - 1, c), (r - 1, c + 1))])
+            } else {
+                 Some(vec![((r, c), (r, c + 1))]) // Top edge: only the lower half is on the board
             }
         }
         _ => None, // Invalid orientation
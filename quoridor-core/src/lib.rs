@@ -16,21 +16,33 @@ pub mod player;
 pub mod types;
 pub mod utils;
 pub mod graph;
+pub mod diff; // Position comparison (Quoridor::diff)
+pub mod classify; // Legal moves grouped by kind (Quoridor::classified_legal_moves)
+pub mod analysis; // Multi-depth evaluation, branching stats and wall combos for UIs, tuning and puzzles
+pub mod record; // Game records for replay (GameRecord)
 pub mod openings;
+#[cfg(any(test, feature = "testing"))]
+pub mod reference; // Graph-free move generation for cross-checking the generators in tests
 pub mod strategy; // This declares the strategy *directory* as a module
+#[cfg(feature = "bitboard")]
+pub mod bitboard; // Optional u128-bitset board for allocation-light move generation
 
 // Re-export the most commonly used types and traits for easier access
 // by consumers of this library.
-pub use game::Quoridor;
+pub use game::{Phase, Quoridor, RuleSet, WallObjective};
+pub use diff::PositionDiff;
+pub use classify::ClassifiedMoves;
+pub use record::GameRecord;
 pub use player::Player;
 pub use types::Coord;
-pub use strategy::Strategy;
+pub use strategy::{ChooseResult, EvalWeights, Strategy};
 
 // Re-export specific strategy implementations
 pub use strategy::{
     RandomStrategy,
     ShortestPathStrategy,
     DefensiveStrategy,
+    GreedyBlockStrategy,
     BalancedStrategy,
     AdaptiveStrategy,
     MinimaxStrategy,
@@ -3,6 +3,7 @@
 //! Defines opening move sequences for different strategies.
 
 use crate::player::Player;
+use crate::utils::{classify_move, coord_to_algebraic, MoveKind};
 
 /// Returns a vector of opening moves (in algebraic notation) for a given opening name and player.
 pub fn get_opening_moves(opening_name: &str, player: Player) -> Vec<String> {
@@ -63,4 +64,79 @@ pub fn get_opening_moves(opening_name: &str, player: Player) -> Vec<String> {
         // Default: No opening moves for unrecognized names
         _ => Vec::new(),
     }.into_iter().map(String::from).collect() // Convert &str to String
-}
\ No newline at end of file
+}
+
+/// The opponent's line of an opening, reflected top-bottom so that `player`
+/// can play it from its own side of a `board_size` board: Player 2's "e8"
+/// becomes "e2" for Player 1, and its "e6v" becomes "e3v". Playing both
+/// reflected lines swaps which side of the opening each player takes.
+/// Moves that are not on the board are left out.
+pub fn mirrored_opening_moves(opening_name: &str, player: Player, board_size: usize) -> Vec<String> {
+    get_opening_moves(opening_name, player.opponent())
+        .iter()
+        .filter_map(|move_str| match classify_move(move_str, board_size) {
+            // A wall's stored square is its lower-left one, so walls reflect one row further than pawns
+            MoveKind::Pawn((r, c)) => Some(coord_to_algebraic((board_size - 1 - r, c), board_size)),
+            MoveKind::Wall((r, c), orientation) => Some(format!("{}{}", coord_to_algebraic((board_size - r, c), board_size), orientation)),
+            MoveKind::Invalid => None,
+        })
+        .collect()
+}
+
+/// Number of walls `player`'s line of the opening places on a `board_size`
+/// board. Walls that fall off a smaller board are not counted.
+pub fn opening_wall_count(opening_name: &str, player: Player, board_size: usize) -> usize {
+    get_opening_moves(opening_name, player)
+        .iter()
+        .filter(|move_str| matches!(classify_move(move_str, board_size), MoveKind::Wall(..)))
+        .count()
+}
+
+/// Checks that neither line of the opening places more walls than a player
+/// starts with, since a line that runs out of walls breaks off mid-sequence.
+/// The error names the first line that does, e.g. the Ala Opening (four
+/// walls for Player 1) in a 3-wall game.
+pub fn check_opening_walls(opening_name: &str, walls_per_player: usize, board_size: usize) -> Result<(), String> {
+    for player in [Player::Player1, Player::Player2] {
+        let walls = opening_wall_count(opening_name, player, board_size);
+        if walls > walls_per_player {
+            return Err(format!(
+                "{}: Player {}'s line places {} walls but each player only has {}",
+                opening_name, player.number(), walls, walls_per_player
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrored_lines_swap_sides() {
+        // The Standard Opening's two lines mirror each other exactly
+        assert_eq!(mirrored_opening_moves("Standard Opening", Player::Player1, 9), get_opening_moves("Standard Opening", Player::Player1));
+        assert_eq!(mirrored_opening_moves("Standard Opening", Player::Player2, 9), get_opening_moves("Standard Opening", Player::Player2));
+
+        // Player 2's box wall next to Player 1's start lands next to Player 2's
+        assert_eq!(mirrored_opening_moves("Quick Box Variation", Player::Player1, 9), vec!["e2", "d8h"]);
+        assert_eq!(mirrored_opening_moves("Quick Box Variation", Player::Player2, 9), vec!["e8"]);
+    }
+
+    #[test]
+    fn test_opening_walls_checked_against_wall_count() {
+        assert_eq!(opening_wall_count("Ala Opening", Player::Player1, 9), 4);
+        assert_eq!(opening_wall_count("Ala Opening", Player::Player2, 9), 0);
+        assert_eq!(opening_wall_count("Ala Opening", Player::Player1, 11), 4);
+        // On 5x5 the f- and g-file walls are off the board
+        assert_eq!(opening_wall_count("Ala Opening", Player::Player1, 5), 2);
+
+        let conflict = check_opening_walls("Ala Opening", 3, 9).unwrap_err();
+        assert!(conflict.contains("Player 1's line places 4 walls"), "{}", conflict);
+        assert!(check_opening_walls("Ala Opening", 4, 9).is_ok());
+        assert!(check_opening_walls("Ala Opening", 3, 5).is_ok());
+        assert!(check_opening_walls("Standard Opening", 3, 9).is_ok());
+        assert!(check_opening_walls("No Opening", 0, 9).is_ok());
+    }
+}
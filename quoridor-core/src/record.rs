@@ -0,0 +1,163 @@
+// --- File: quoridor-project/quoridor-core/src/record.rs ---
+
+//! Move lists for replaying and reviewing games.
+
+use crate::game::Quoridor;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A game as its starting configuration plus the moves played, in order.
+/// Positions are rebuilt by replaying, so a record stays small however long
+/// the game runs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameRecord {
+    pub size: usize,
+    pub walls: usize,                 // Initial walls per player
+    pub start_state: Option<String>,  // State string the game started from (None = standard start)
+    pub moves: Vec<String>,           // Moves in algebraic notation, e.g. "e2", "a3h"
+}
+
+impl GameRecord {
+    /// Creates an empty record for a game started with `Quoridor::new(size, walls, start_state)`.
+    pub fn new(size: usize, walls: usize, start_state: Option<&str>) -> Self {
+        GameRecord {
+            size,
+            walls,
+            start_state: start_state.map(str::to_string),
+            moves: Vec::new(),
+        }
+    }
+
+    /// Appends a move. Legality is not checked here; see `position_at`.
+    pub fn push(&mut self, move_str: &str) {
+        self.moves.push(move_str.to_string());
+    }
+
+    /// Number of plies (pawn moves and walls) recorded.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Hash of the whole record (setup and every move), for spotting repeated
+    /// games: equal records hash equally. Only stable within one build, so
+    /// don't store it.
+    pub fn transcript_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The position before any recorded move.
+    pub fn initial_position(&self) -> Quoridor {
+        Quoridor::new(self.size, self.walls, self.start_state.as_deref())
+    }
+
+    /// The position after the first `ply` moves, replayed from the initial
+    /// configuration. `ply` is clamped to `len()`, and replay stops early at
+    /// the first move that is not legal in the position it is played in.
+    pub fn position_at(&self, ply: usize) -> Quoridor {
+        let mut game = self.initial_position();
+        for move_str in self.moves.iter().take(ply) {
+            if !game.apply_move(move_str, true) {
+                break;
+            }
+        }
+        game
+    }
+}
+
+/// Longest random game `play_random_game` will play before giving up.
+#[cfg(any(test, feature = "testing"))]
+pub const RANDOM_GAME_MAX_PLIES: usize = 1000;
+
+#[cfg(any(test, feature = "testing"))]
+impl Quoridor {
+    /// Plays uniformly random legal moves on the standard board until a pawn
+    /// reaches its goal or `RANDOM_GAME_MAX_PLIES` moves are played, and
+    /// returns the transcript. The same seed always gives the same game.
+    /// For property tests; only built for tests or with the `testing` feature.
+    pub fn play_random_game(seed: u64) -> GameRecord {
+        use rand::rngs::StdRng;
+        use rand::seq::SliceRandom;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Quoridor::standard();
+        let mut record = GameRecord::new(game.size, game.walls, None);
+        while game.winner().is_none() && record.len() < RANDOM_GAME_MAX_PLIES {
+            let moves = game.all_legal_moves(game.active_player);
+            let Some(move_str) = moves.choose(&mut rng) else { break };
+            assert!(game.apply_move(move_str, true), "generated move {} was rejected", move_str);
+            record.push(move_str);
+        }
+        record
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_at_ends() {
+        let mut played = Quoridor::standard();
+        let mut record = GameRecord::new(9, 10, None);
+        for move_str in ["e2", "e8", "e3h", "d8", "d2", "c3v"] {
+            let applied = played.apply_move(move_str, true);
+            assert!(applied, "{} should be legal", move_str);
+            record.push(move_str);
+        }
+        assert_eq!(record.len(), 6);
+
+        let start = record.position_at(0);
+        assert!(start.diff(&Quoridor::standard()).is_empty());
+        assert_eq!(start.state_string, Quoridor::standard().state_string);
+
+        let end = record.position_at(record.len());
+        assert!(end.diff(&played).is_empty());
+        assert_eq!(end.state_string, played.state_string);
+        assert_eq!(end.active_player, played.active_player);
+
+        // Past the end clamps to the final position
+        assert_eq!(record.position_at(100).state_string, played.state_string);
+    }
+
+    #[test]
+    fn test_position_at_from_start_state() {
+        let mut record = GameRecord::new(5, 3, Some(" / / c2 c4 / 3 3 / 1"));
+        record.push("c3");
+        assert_eq!(record.position_at(0).state_string, Quoridor::new(5, 3, Some(" / / c2 c4 / 3 3 / 1")).state_string);
+        assert_eq!(record.position_at(1).pawn_positions, Quoridor::new(5, 3, Some(" / / c3 c4 / 3 3 / 2")).pawn_positions);
+    }
+
+    #[test]
+    fn test_random_game_is_deterministic() {
+        assert_eq!(Quoridor::play_random_game(1), Quoridor::play_random_game(1));
+        assert_ne!(Quoridor::play_random_game(1), Quoridor::play_random_game(2));
+    }
+
+    #[test]
+    fn test_random_games_replay() {
+        for seed in 0..100 {
+            let record = Quoridor::play_random_game(seed);
+
+            // Every move is legal where it was played. Each position_at replays from
+            // the start, so every prefix is only compared for the first few seeds
+            let mut game = record.initial_position();
+            assert_eq!(record.position_at(0).state_string, game.state_string);
+            for (ply, move_str) in record.moves.iter().enumerate() {
+                assert!(game.apply_move(move_str, true), "seed {}: {} is illegal", seed, move_str);
+                if seed < 3 {
+                    assert_eq!(record.position_at(ply + 1).state_string, game.state_string, "seed {}: ply {}", seed, ply + 1);
+                }
+            }
+            assert_eq!(record.position_at(record.len()).state_string, game.state_string);
+            assert_eq!(Quoridor::new(9, 10, Some(&game.state_string)).state_string, game.state_string);
+            assert!(game.winner().is_some() || record.len() == RANDOM_GAME_MAX_PLIES);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// --- File: quoridor-project/quoridor-core/src/reference.rs ---
+
+//! Graph-free move generation for cross-checking `Quoridor`'s generators.
+//!
+//! Everything here works directly off `hwall_positions`/`vwall_positions`
+//! with plain sets and a flood fill, sharing no code with the petgraph board,
+//! the neighbour cache or `wall_fits`. It is slow and only meant for tests:
+//! any disagreement with `get_legal_moves`/`get_legal_walls` is a bug in one
+//! of the two. Only built for tests or with the `testing` feature.
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use crate::types::Coord;
+use std::collections::HashSet;
+
+/// Squares blocked by walls, as the lower/right square of each blocked edge.
+struct Blocked {
+    size: usize,
+    /// `(r, c)` blocks the step between (r - 1, c) and (r, c).
+    horizontal: HashSet<Coord>,
+    /// `(r, c)` blocks the step between (r, c) and (r, c + 1).
+    vertical: HashSet<Coord>,
+}
+
+impl Blocked {
+    fn new(game: &Quoridor) -> Self {
+        let mut blocked = Blocked { size: game.size, horizontal: HashSet::new(), vertical: HashSet::new() };
+        for &coord in &game.hwall_positions {
+            blocked.add(coord, 'h');
+        }
+        for &coord in &game.vwall_positions {
+            blocked.add(coord, 'v');
+        }
+        blocked
+    }
+
+    /// The edge segments a wall covers: horizontal wall (r, c) separates rows
+    /// r - 1 and r on files c and c + 1; vertical wall (r, c) separates files
+    /// c and c + 1 on rows r and r - 1 (only row 0 for a half wall on the top edge).
+    fn segments((r, c): Coord, orientation: char) -> Vec<Coord> {
+        match (orientation, r) {
+            ('h', _) => vec![(r, c), (r, c + 1)],
+            (_, 0) => vec![(r, c)],
+            _ => vec![(r, c), (r - 1, c)],
+        }
+    }
+
+    /// Marks the wall's segments, returning false if any was already blocked.
+    fn add(&mut self, coord: Coord, orientation: char) -> bool {
+        let set = if orientation == 'h' { &mut self.horizontal } else { &mut self.vertical };
+        let mut fresh = true;
+        for segment in Blocked::segments(coord, orientation) {
+            fresh &= set.insert(segment);
+        }
+        fresh
+    }
+
+    /// The square one step from `from` by (dr, dc), if it is on the board and
+    /// no wall is in the way.
+    fn step(&self, (r, c): Coord, (dr, dc): (i32, i32)) -> Option<Coord> {
+        let (nr, nc) = (r as i32 + dr, c as i32 + dc);
+        if nr < 0 || nc < 0 || nr >= self.size as i32 || nc >= self.size as i32 {
+            return None;
+        }
+        let to = (nr as usize, nc as usize);
+        let wall = match (dr, dc) {
+            (-1, 0) => self.horizontal.contains(&(r, c)),
+            (1, 0) => self.horizontal.contains(&to),
+            (0, -1) => self.vertical.contains(&to),
+            _ => self.vertical.contains(&(r, c)),
+        };
+        (!wall).then_some(to)
+    }
+
+    fn reaches_row(&self, start: Coord, goal_row: usize) -> bool {
+        let mut seen = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(square) = stack.pop() {
+            if square.0 == goal_row {
+                return true;
+            }
+            for dir in DIRECTIONS {
+                if let Some(next) = self.step(square, dir) {
+                    if seen.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// `player`'s legal pawn destinations, in the same order as `get_legal_moves`
+/// (by row from the top, then by file). Follows `game.rules` for jumps.
+pub fn legal_pawn_moves(game: &Quoridor, player: Player) -> Vec<String> {
+    let blocked = Blocked::new(game);
+    let own = game.pawn_positions[&player];
+    let opponent = game.pawn_positions[&player.opponent()];
+
+    let mut destinations = Vec::new();
+    for dir in DIRECTIONS {
+        let Some(next) = blocked.step(own, dir) else { continue };
+        if next != opponent {
+            destinations.push(next);
+        } else if game.rules.allow_jumps {
+            // Straight over the opponent, or to either side of it if that is blocked
+            match blocked.step(opponent, dir) {
+                Some(behind) => destinations.push(behind),
+                None => destinations.extend([(dir.1, dir.0), (-dir.1, -dir.0)].into_iter().filter_map(|side| blocked.step(opponent, side))),
+            }
+        }
+    }
+    destinations.sort();
+    destinations.dedup();
+    destinations.into_iter().map(|coord| game.coord_to_algebraic(coord)).collect()
+}
+
+/// Whether `player` may place the wall: walls in hand, on the board, no
+/// shared segment with a placed wall, no crossing at the same centre, and both
+/// pawns can still reach their goal rows.
+pub fn is_wall_legal(game: &Quoridor, player: Player, (r, c): Coord, orientation: char) -> bool {
+    if game.walls_available[&player] == 0 || r == 0 || r >= game.size || c + 1 >= game.size {
+        return false;
+    }
+    let crossing = match orientation {
+        'h' => &game.vwall_positions,
+        'v' => &game.hwall_positions,
+        _ => return false,
+    };
+    let mut blocked = Blocked::new(game);
+    if crossing.contains(&(r, c)) || !blocked.add((r, c), orientation) {
+        return false;
+    }
+    blocked.reaches_row(game.pawn_positions[&Player::Player1], 0)
+        && blocked.reaches_row(game.pawn_positions[&Player::Player2], game.size - 1)
+}
+
+/// `player`'s legal wall placements, in the same order as `get_legal_walls`
+/// (by row from the top, then by file, horizontal before vertical).
+pub fn legal_walls(game: &Quoridor, player: Player) -> Vec<String> {
+    let mut walls = Vec::new();
+    for r in 0..=game.size {
+        for c in 0..=game.size {
+            for orientation in ['h', 'v'] {
+                if is_wall_legal(game, player, (r, c), orientation) {
+                    walls.push(format!("{}{}", game.coord_to_algebraic((r, c)), orientation));
+                }
+            }
+        }
+    }
+    walls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::RuleSet;
+
+    #[test]
+    fn test_reference_agrees_with_graph_generators() {
+        let no_jumps = RuleSet { allow_jumps: false };
+        for seed in 0..6 {
+            let record = Quoridor::play_random_game(seed);
+            for ply in (0..=record.len()).step_by(8) {
+                let game = record.position_at(ply);
+                let variant = game.clone().with_rules(no_jumps);
+                for player in [Player::Player1, Player::Player2] {
+                    assert_eq!(legal_pawn_moves(&game, player), game.get_legal_moves(player), "seed {} ply {}: {}", seed, ply, game.state_string);
+                    assert_eq!(legal_pawn_moves(&variant, player), variant.get_legal_moves(player), "no jumps, seed {} ply {}: {}", seed, ply, game.state_string);
+                    assert_eq!(legal_walls(&game, player), game.get_legal_walls(player), "seed {} ply {}: {}", seed, ply, game.state_string);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_diagonal_jumps_around_blocked_opponent() {
+        // P2 on e5 with a wall behind it: P1 on e4 jumps diagonally instead
+        let game = Quoridor::new(9, 10, Some("e5 / / e4 e5 / 10 9 / 1"));
+        assert_eq!(legal_pawn_moves(&game, Player::Player1), vec!["d5", "f5", "d4", "f4", "e3"]);
+        assert_eq!(legal_pawn_moves(&game, Player::Player1), game.get_legal_moves(Player::Player1));
+    }
+}
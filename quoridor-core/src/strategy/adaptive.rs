@@ -2,7 +2,7 @@
 
 use crate::game::Quoridor;
 use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::{Strategy, DefensiveStrategy, ShortestPathStrategy};
 
 pub struct AdaptiveStrategy {
@@ -15,7 +15,7 @@ pub struct AdaptiveStrategy {
 impl AdaptiveStrategy {
     pub fn new(opening_name: &str, opening_moves: Vec<String>) -> Self {
          // Inner strategies don't need opening info directly
-        let defensive_strategy = DefensiveStrategy::new("", Vec::new(), 0.7); // Example preference
+        let defensive_strategy = DefensiveStrategy::new("", Vec::new(), 0.7, 0.0); // Example preference
         let offensive_strategy = ShortestPathStrategy::new("", Vec::new());
 
         AdaptiveStrategy {
@@ -31,6 +31,26 @@ impl Strategy for AdaptiveStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        format!(
+            "{} (offense when distance <= opponent's: {}; otherwise: {})",
+            self.name(), self.offensive_strategy.describe(), self.defensive_strategy.describe()
+        )
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
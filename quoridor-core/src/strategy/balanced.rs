@@ -1,7 +1,7 @@
 // --- File: quoridor-project/quoridor-core/src/strategy/balanced.rs ---
 
 use crate::game::Quoridor;
-use crate::strategy::base::QuoridorStrategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::{Strategy, DefensiveStrategy, ShortestPathStrategy};
 use rand::prelude::*;
 
@@ -16,7 +16,7 @@ pub struct BalancedStrategy {
 impl BalancedStrategy {
     pub fn new(opening_name: &str, opening_moves: Vec<String>, defense_weight: f64) -> Self {
          // Inner strategies don't need opening info directly
-        let defensive_strategy = DefensiveStrategy::new("", Vec::new(), 1.0); // Use preference 1.0 inside
+        let defensive_strategy = DefensiveStrategy::new("", Vec::new(), 1.0, 0.0); // Use preference 1.0 inside
         let offensive_strategy = ShortestPathStrategy::new("", Vec::new());
 
         BalancedStrategy {
@@ -26,6 +26,11 @@ impl BalancedStrategy {
             offensive_strategy,
         }
     }
+
+    /// Sets the probability (0 to 1) of playing a defensive wall on each move.
+    pub fn set_defense_weight(&mut self, defense_weight: f64) {
+        self.defense_weight = defense_weight;
+    }
 }
 
 impl Strategy for BalancedStrategy {
@@ -34,6 +39,32 @@ impl Strategy for BalancedStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        format!("{} (defense weight: {})", self.name(), self.defense_weight)
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
+    /// Tunable: "defense_weight" (see `set_defense_weight`).
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        match name {
+            "defense_weight" => self.set_defense_weight(value),
+            _ => return false,
+        }
+        true
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -53,4 +84,22 @@ impl Strategy for BalancedStrategy {
             self.offensive_strategy.choose_move(game)
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defense_weight_setter_switches_between_walls_and_pawn_moves() {
+        // P1 on c2, P2 on d3: the defensive side walls with c2h, the offensive side steps to c3
+        let game = Quoridor::new(5, 3, Some(" / / c2 d3 / 3 3 / 1"));
+        let mut strategy = BalancedStrategy::new("", Vec::new(), 0.0);
+        assert_eq!(strategy.choose_move(&game), Some("c3".to_string()));
+
+        strategy.set_defense_weight(1.0);
+        assert_eq!(strategy.choose_move(&game), Some("c2h".to_string()));
+        assert!(strategy.set_param("defense_weight", 0.0));
+        assert_eq!(strategy.choose_move(&game), Some("c3".to_string()));
+    }
+}
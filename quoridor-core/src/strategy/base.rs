@@ -5,11 +5,38 @@
 
 use crate::game::Quoridor;
 
+/// What `try_opening_move` does when the next move of the opening line is
+/// illegal in the current position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpeningDeviation {
+    /// Step past the illegal move, let the strategy choose this turn, and carry
+    /// on with the line next turn (the default). Later moves may assume the
+    /// skipped one was played.
+    #[default]
+    Resume,
+    /// Drop the rest of the line and let the strategy choose.
+    Abandon,
+    /// Skip the illegal move and play the next move of the line that is legal.
+    /// Later moves may assume the skipped one was played, so the result can
+    /// differ from the book.
+    Skip,
+    /// Drop the rest of the line like `Abandon`, but keep the move in
+    /// `deviation` for the caller to log, for strict opening-book play.
+    Report,
+}
+
 /// A base struct for strategies, handling opening moves and naming.
+///
+/// Opening contract: the strategy the game talks to (the outermost one) owns
+/// the opening and consumes it through `try_opening_move`. Composite strategies
+/// (Defensive, Balanced, Adaptive) build their inner strategies with no opening
+/// moves, so inner strategies never advance an opening counter of their own.
 pub struct QuoridorStrategy {
     pub name: String, // Made public for access in strategy implementations
     pub opening_moves: Vec<String>, // Made public
     pub move_counter: usize, // Made public
+    pub opening_mode: OpeningDeviation, // Reaction to an illegal opening move
+    pub deviation: Option<(usize, String)>, // Index and text of the illegal move, in Report mode
 }
 
 impl QuoridorStrategy {
@@ -26,36 +53,228 @@ impl QuoridorStrategy {
             name: full_name,
             opening_moves,
             move_counter: 0,
+            opening_mode: OpeningDeviation::default(),
+            deviation: None,
         }
     }
 
+    /// Sets what happens when the next opening move is illegal (see `OpeningDeviation`).
+    pub fn with_opening_mode(mut self, mode: OpeningDeviation) -> Self {
+        self.opening_mode = mode;
+        self
+    }
+
+    /// The first legal move for the side to move (pawn moves before walls), for
+    /// strategies whose own logic comes up empty. Strategies return None only
+    /// when there is no legal move at all, so a None is never a silent forfeit.
+    pub fn last_resort_move(game: &Quoridor) -> Option<String> {
+        game.all_legal_moves(game.active_player).into_iter().next()
+    }
+
     /// Attempts to return the next opening move if available and legal.
-    /// Increments the internal move counter.
+    /// Increments the internal move counter. If the next opening move is illegal
+    /// the position has left the book: by default it is stepped past and the line
+    /// resumes next turn, see `opening_mode` for the alternatives.
     pub fn try_opening_move(&mut self, game: &Quoridor) -> Option<String> {
-        if self.move_counter < self.opening_moves.len() {
-            let move_str = self.opening_moves[self.move_counter].clone();
-            // Crucially, check if the opening move is actually legal in the *current* position
-            let legal_pawn = game.get_legal_moves(game.active_player);
-            let legal_walls = game.get_legal_walls(game.active_player); // Already checks walls_available
+        if self.move_counter >= self.opening_moves.len() {
+            return None; // No more opening moves
+        }
+        // Crucially, check if the opening move is actually legal in the *current* position
+        let legal_pawn = game.get_legal_moves(game.active_player);
+        let legal_walls = game.get_legal_walls(game.active_player); // Already checks walls_available
 
+        while self.move_counter < self.opening_moves.len() {
+            let move_str = self.opening_moves[self.move_counter].clone();
             if legal_pawn.contains(&move_str) || legal_walls.contains(&move_str) {
                 self.move_counter += 1; // Only increment if legal and used
                 // println!("Using opening move #{}: {} for {}", self.move_counter, move_str, game.active_player);
                 return Some(move_str);
-            } else {
-                 // println!("Skipping illegal opening move #{}: {} for {}", self.move_counter + 1, move_str, game.active_player);
-                 // Don't increment counter, let the main strategy logic take over
-                 // Or potentially advance counter anyway if opening assumes perfect play? Depends on design.
-                 // For robustness, let's not increment if illegal. The strategy will choose a valid move instead.
-                 self.move_counter +=1; // Let's assume we should advance past illegal opening moves
-                 return None; // Let the main strategy choose
+            }
+            match self.opening_mode {
+                 OpeningDeviation::Resume => {
+                     self.move_counter += 1; // Pick the line up again next turn
+                     return None;
+                 }
+                 OpeningDeviation::Skip => self.move_counter += 1, // Try the next one
+                 OpeningDeviation::Abandon | OpeningDeviation::Report => {
+                     // Later opening moves assume this one was played, so resuming the
+                     // line after a deviation would emit a garbled sequence: drop it
+                     if self.opening_mode == OpeningDeviation::Report {
+                         self.deviation = Some((self.move_counter, move_str));
+                     }
+                     self.move_counter = self.opening_moves.len();
+                 }
             }
         }
-        None // No more opening moves
+        None // Left the book; let the main strategy choose
     }
 
-     /// Resets the opening move counter.
+     /// Resets the opening move counter and forgets any reported deviation.
      pub fn reset(&mut self) {
          self.move_counter = 0;
+         self.deviation = None;
      }
-}
\ No newline at end of file
+
+    /// Snapshot of the opening progress: the move counter as 8 little-endian bytes.
+    pub fn save_state(&self) -> Vec<u8> {
+        (self.move_counter as u64).to_le_bytes().to_vec()
+    }
+
+    /// Restores a `save_state` snapshot. Returns false, leaving the counter
+    /// alone, if the bytes are malformed or point past the end of this
+    /// strategy's opening line.
+    pub fn load_state(&mut self, state: &[u8]) -> bool {
+        let Ok(bytes) = <[u8; 8]>::try_from(state) else { return false; };
+        let Ok(counter) = usize::try_from(u64::from_le_bytes(bytes)) else { return false; };
+        if counter > self.opening_moves.len() { return false; }
+        self.move_counter = counter;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openings::get_opening_moves;
+    use crate::player::Player;
+    use crate::strategy::{AdaptiveStrategy, BalancedStrategy, DefensiveStrategy, MCTSStrategy, MinimaxStrategy, Strategy};
+
+    /// Builds a strategy from an opening name and that side's opening moves.
+    type MakeStrategy = fn(&str, Vec<String>) -> Box<dyn Strategy>;
+
+    /// Plays both sides with the given strategy constructor and returns the
+    /// first `plies` moves each side emitted.
+    fn play_opening(make: MakeStrategy, opening: &str, plies: usize) -> (Vec<String>, Vec<String>) {
+        let mut game = Quoridor::standard();
+        let mut p1 = make(opening, get_opening_moves(opening, Player::Player1));
+        let mut p2 = make(opening, get_opening_moves(opening, Player::Player2));
+        let (mut p1_moves, mut p2_moves) = (Vec::new(), Vec::new());
+        for _ in 0..plies {
+            let (strategy, moves) = if game.active_player == Player::Player1 { (&mut p1, &mut p1_moves) } else { (&mut p2, &mut p2_moves) };
+            let move_str = strategy.choose_move(&game).expect("a move");
+            let moved = game.apply_move(&move_str, true);
+            assert!(moved, "illegal move {}", move_str);
+            moves.push(move_str);
+        }
+        (p1_moves, p2_moves)
+    }
+
+    #[test]
+    fn test_composite_strategies_play_opening_in_order() {
+        let opening = "Standard Opening";
+        let expected_p1 = get_opening_moves(opening, Player::Player1);
+        let expected_p2 = get_opening_moves(opening, Player::Player2);
+        let constructors: [MakeStrategy; 3] = [
+            |name, moves| Box::new(DefensiveStrategy::new(name, moves, 0.7, 0.0)),
+            |name, moves| Box::new(BalancedStrategy::new(name, moves, 0.5)),
+            |name, moves| Box::new(AdaptiveStrategy::new(name, moves)),
+        ];
+        for make in constructors {
+            let (p1_moves, p2_moves) = play_opening(make, opening, 8);
+            assert_eq!(p1_moves, expected_p1);
+            assert_eq!(p2_moves, expected_p2);
+        }
+    }
+
+    #[test]
+    fn test_illegal_opening_move_abandons_the_line() {
+        // P1 has already left e1, so "e2" is not a legal first step: the rest is dropped
+        let game = Quoridor::new(9, 10, Some(" / / a1 e9 / 10 10 / 1"));
+        let line = vec!["e2".to_string(), "a2".to_string()];
+        let mut base = QuoridorStrategy::new("Test", "Standard Opening", line.clone()).with_opening_mode(OpeningDeviation::Abandon);
+        assert_eq!(base.try_opening_move(&game), None);
+        assert_eq!(base.try_opening_move(&game), None); // "a2" would be legal, but the line was left
+
+        // By default the line picks up again on the next turn
+        let mut base = QuoridorStrategy::new("Test", "Standard Opening", line);
+        assert_eq!(base.opening_mode, OpeningDeviation::Resume);
+        assert_eq!(base.try_opening_move(&game), None);
+        assert_eq!(base.try_opening_move(&game).as_deref(), Some("a2"));
+    }
+
+    #[test]
+    fn test_opening_modes_handle_illegal_second_move() {
+        // After e2 and e8, the book's "e9" is no pawn move for P1; "e3" would be
+        let line = vec!["e2".to_string(), "e9".to_string(), "e3".to_string()];
+        let mut game = Quoridor::standard();
+        let make = |mode| QuoridorStrategy::new("Test", "Book", line.clone()).with_opening_mode(mode);
+        let mut modes = [make(OpeningDeviation::Abandon), make(OpeningDeviation::Skip), make(OpeningDeviation::Report)];
+        for base in &mut modes {
+            assert_eq!(base.try_opening_move(&game).as_deref(), Some("e2"));
+        }
+        assert!(game.apply_move("e2", true) && game.apply_move("e8", true));
+
+        let [abandon, skip, report] = &mut modes;
+        assert_eq!(abandon.try_opening_move(&game), None);
+        assert_eq!(abandon.deviation, None);
+        assert_eq!(skip.try_opening_move(&game).as_deref(), Some("e3"));
+        assert_eq!(report.try_opening_move(&game), None);
+        assert_eq!(report.deviation, Some((1, "e9".to_string())));
+        assert_eq!(report.try_opening_move(&game), None); // The line stays dropped
+
+        report.reset();
+        assert_eq!(report.deviation, None);
+    }
+
+    #[test]
+    fn test_opening_mode_through_strategy() {
+        // As above, through a real strategy: "e9" is no move for P1 after e2 e8
+        let line = vec!["e2".to_string(), "e9".to_string(), "e3".to_string()];
+        let mut game = Quoridor::standard();
+        let make = |mode| {
+            let mut strategy: Box<dyn Strategy> = Box::new(MinimaxStrategy::new("Book", line.clone(), 1));
+            assert!(strategy.set_opening_mode(mode));
+            strategy
+        };
+        let (mut skip, mut report) = (make(OpeningDeviation::Skip), make(OpeningDeviation::Report));
+        assert_eq!(skip.choose_move(&game).as_deref(), Some("e2"));
+        assert_eq!(report.choose_move(&game).as_deref(), Some("e2"));
+        assert!(game.apply_move("e2", true) && game.apply_move("e8", true));
+
+        assert_eq!(skip.choose_move(&game).as_deref(), Some("e3"));
+        assert_eq!(skip.opening_deviation(), None);
+        assert!(report.choose_move(&game).is_some()); // Searched instead
+        assert_eq!(report.opening_deviation(), Some((1, "e9".to_string())));
+    }
+
+    #[test]
+    fn test_saved_counter_resumes_opening_line() {
+        let opening = "Standard Opening";
+        let expected = get_opening_moves(opening, Player::Player1);
+
+        // Play two opening moves, then hand the state to a fresh strategy
+        let mut original = MCTSStrategy::new(opening, expected.clone(), 10);
+        let mut game = Quoridor::standard();
+        for reply in ["e8", "e7"] {
+            assert!(game.apply_move(&original.choose_move(&game).unwrap(), true));
+            assert!(game.apply_move(reply, true));
+        }
+        let saved = original.save_state().expect("MCTS saves its opening progress");
+
+        let mut resumed = MCTSStrategy::new(opening, expected.clone(), 10);
+        assert!(resumed.load_state(&saved));
+        assert_eq!(resumed.choose_move(&game), Some(expected[2].clone()));
+        assert_eq!(original.choose_move(&game), Some(expected[2].clone()));
+
+        // Malformed or out-of-range snapshots are refused
+        let mut base = QuoridorStrategy::new("Test", opening, expected.clone());
+        assert!(!base.load_state(&[1, 2, 3]));
+        assert!(!base.load_state(&(expected.len() as u64 + 1).to_le_bytes()));
+        assert!(base.load_state(&2u64.to_le_bytes()));
+        assert_eq!(base.move_counter, 2);
+        assert_eq!(base.save_state(), 2u64.to_le_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_reset_restarts_opening_line() {
+        let opening = "Standard Opening";
+        let mut strategy = AdaptiveStrategy::new(opening, get_opening_moves(opening, Player::Player1));
+        let game = Quoridor::standard();
+        let first = strategy.choose_move(&game);
+        assert_eq!(first.as_deref(), Some("e2"));
+
+        // Without a reset the same position would get the line's second move
+        strategy.reset();
+        assert_eq!(strategy.choose_move(&game), first);
+    }
+}
@@ -2,27 +2,45 @@
 
 use crate::game::Quoridor;
 use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::{Strategy, ShortestPathStrategy}; // Import base and ShortestPath
 use rand::prelude::*;
 
 pub struct DefensiveStrategy {
     base: QuoridorStrategy,
     wall_preference: f64, // Probability to prefer placing a wall
+    self_penalty_weight: f64, // How much a wall lengthening our own path counts against it
+    max_self_increase: usize, // Walls lengthening our own path by more than this are skipped
     // Internal strategy for pawn movement when not placing a wall
     offensive_strategy: ShortestPathStrategy,
 }
 
 impl DefensiveStrategy {
-    pub fn new(opening_name: &str, opening_moves: Vec<String>, wall_preference: f64) -> Self {
+    /// `self_penalty_weight`: walls are scored as
+    /// `opponent_increase - own_increase * self_penalty_weight`.
+    /// A weight of 0 ignores the wall's effect on our own path.
+    pub fn new(opening_name: &str, opening_moves: Vec<String>, wall_preference: f64, self_penalty_weight: f64) -> Self {
         // Ensure the offensive strategy doesn't use openings itself
         let offensive_strategy = ShortestPathStrategy::new("", Vec::new());
         DefensiveStrategy {
             base: QuoridorStrategy::new("Defensive", opening_name, opening_moves),
             wall_preference,
+            self_penalty_weight,
+            max_self_increase: usize::MAX,
             offensive_strategy,
         }
     }
+
+    /// Sets the probability (0 to 1) of looking for a blocking wall on each move.
+    pub fn set_wall_preference(&mut self, wall_preference: f64) {
+        self.wall_preference = wall_preference;
+    }
+
+    /// Never place a wall that lengthens our own shortest path by more than `cap`.
+    pub fn with_max_self_increase(mut self, cap: usize) -> Self {
+        self.max_self_increase = cap;
+        self
+    }
 }
 
 impl Strategy for DefensiveStrategy {
@@ -32,6 +50,36 @@ impl Strategy for DefensiveStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        let max_increase = if self.max_self_increase == usize::MAX { "none".to_string() } else { self.max_self_increase.to_string() };
+        format!(
+            "{} (wall preference: {}, self penalty: {}, max self increase: {})",
+            self.name(), self.wall_preference, self.self_penalty_weight, max_increase
+        )
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
+    /// Tunable: "wall_preference" (see `set_wall_preference`).
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        match name {
+            "wall_preference" => self.set_wall_preference(value),
+            _ => return false,
+        }
+        true
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -47,19 +95,23 @@ impl Strategy for DefensiveStrategy {
         // Decide whether to consider placing a wall
         if !legal_wall_moves.is_empty() && rng.gen::<f64>() < self.wall_preference {
             let current_opponent_distance = game.distance_to_goal(opponent);
+            let current_own_distance = game.distance_to_goal(player);
             let mut best_blocking_wall: Option<String> = None;
-            let mut max_distance_increase = 0; // Find wall that hinders opponent most
+            let mut best_score = 0.0; // Find wall with the best net effect; must be positive
 
             for wall_move in &legal_wall_moves {
-                let mut temp_game = game.clone();
-                // Use internal add_wall without checks, assuming get_legal_walls was correct
-                if temp_game.add_wall(wall_move, false, false) {
-                     let new_opponent_distance = temp_game.distance_to_goal(opponent);
+                // Place without checks, assuming get_legal_walls was correct
+                if let Some((new_own_distance, new_opponent_distance)) = game.distances_after_wall(wall_move, false) {
                      // Ensure opponent is not completely blocked (handled by get_legal_walls check)
                      if new_opponent_distance > current_opponent_distance {
                          let increase = new_opponent_distance.saturating_sub(current_opponent_distance);
-                         if increase > max_distance_increase {
-                              max_distance_increase = increase;
+                         let own_increase = new_own_distance.saturating_sub(current_own_distance);
+                         if own_increase > self.max_self_increase {
+                             continue; // Hurts our own path too much
+                         }
+                         let score = increase as f64 - own_increase as f64 * self.self_penalty_weight;
+                         if score > best_score {
+                              best_score = score;
                               best_blocking_wall = Some(wall_move.clone());
                          }
                      }
@@ -76,4 +128,53 @@ impl Strategy for DefensiveStrategy {
         // If not placing a wall (or no good wall found), use the offensive strategy for pawn movement
         self.offensive_strategy.choose_move(game)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // P1 (to move) on c2, P2 on d3. "c2h" lengthens both paths by one,
+    // "d2h" lengthens only P2's.
+    const SHARED_LANE_STATE: &str = " / / c2 d3 / 3 3 / 1";
+
+    #[test]
+    fn test_naive_scoring_picks_wall_that_also_blocks_self() {
+        let game = Quoridor::new(5, 3, Some(SHARED_LANE_STATE));
+        let mut strategy = DefensiveStrategy::new("", Vec::new(), 1.0, 0.0);
+        assert_eq!(strategy.choose_move(&game), Some("c2h".to_string()));
+    }
+
+    #[test]
+    fn test_self_penalty_avoids_wall_that_blocks_self() {
+        let game = Quoridor::new(5, 3, Some(SHARED_LANE_STATE));
+        let mut strategy = DefensiveStrategy::new("", Vec::new(), 1.0, 1.0);
+        let chosen = strategy.choose_move(&game).unwrap();
+        assert_eq!(chosen, "d2h");
+
+        let mut after = game.clone();
+        assert!(after.add_wall(&chosen, false, true));
+        assert_eq!(after.distance_to_goal(Player::Player1), game.distance_to_goal(Player::Player1));
+        assert!(after.distance_to_goal(Player::Player2) > game.distance_to_goal(Player::Player2));
+    }
+
+    #[test]
+    fn test_max_self_increase_skips_self_blocking_walls() {
+        let game = Quoridor::new(5, 3, Some(SHARED_LANE_STATE));
+        let mut strategy = DefensiveStrategy::new("", Vec::new(), 1.0, 0.0).with_max_self_increase(0);
+        assert_eq!(strategy.choose_move(&game), Some("d2h".to_string()));
+    }
+
+    #[test]
+    fn test_wall_preference_setter_switches_to_pawn_moves() {
+        let game = Quoridor::new(5, 3, Some(SHARED_LANE_STATE));
+        let mut strategy = DefensiveStrategy::new("", Vec::new(), 1.0, 0.0);
+        assert_eq!(strategy.choose_move(&game), Some("c2h".to_string()));
+
+        strategy.set_wall_preference(0.0);
+        assert_eq!(strategy.choose_move(&game), Some("c3".to_string()));
+        assert!(strategy.set_param("wall_preference", 1.0));
+        assert_eq!(strategy.choose_move(&game), Some("c2h".to_string()));
+        assert!(!strategy.set_param("exploration", 1.0));
+    }
+}
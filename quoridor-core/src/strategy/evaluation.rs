@@ -0,0 +1,206 @@
+// --- File: quoridor-project/quoridor-core/src/strategy/evaluation.rs ---
+
+//! Heuristic weights shared by the search-based strategies (Minimax, Simulated Annealing).
+
+use crate::game::Quoridor;
+use crate::player::Player;
+use std::fmt;
+
+/// Weights of the static evaluation terms.
+/// The defaults are the Mertens paper weights for strategy C3, with the
+/// wall-reserve term switched off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalWeights {
+    /// f2: position difference (distance-to-goal race)
+    pub w2: f64,
+    /// f3: attack, the inverse of moves to the next row
+    pub w3: f64,
+    /// f4: defence, how many moves the opponent needs to reach its next row
+    pub w4: f64,
+    /// f5: walls in hand (own minus opponent's); 0 ignores wall reserves
+    pub w5: f64,
+    /// f6: mobility, legal pawn moves (own minus opponent's); 0 ignores mobility
+    pub w6: f64,
+    /// f7: path pressure, walls along the opponent's route (own pressure minus
+    /// the opponent's); 0 ignores it
+    pub w7: f64,
+}
+
+impl Default for EvalWeights {
+    fn default() -> Self {
+        EvalWeights {
+            w2: 0.6001,
+            w3: 14.45,
+            w4: 6.52,
+            w5: 0.0,
+            w6: 0.0,
+            w7: 0.0,
+        }
+    }
+}
+
+/// All six weights, e.g. "w2=0.6001 w3=14.45 w4=6.52 w5=0 w6=0 w7=0".
+impl fmt::Display for EvalWeights {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "w2={} w3={} w4={} w5={} w6={} w7={}", self.w2, self.w3, self.w4, self.w5, self.w6, self.w7)
+    }
+}
+
+impl EvalWeights {
+    /// Mertens heuristic C1: position difference only (f2).
+    pub fn c1() -> Self {
+        EvalWeights { w3: 0.0, w4: 0.0, ..EvalWeights::c3() }
+    }
+
+    /// Mertens heuristic C2: position difference and attack (f2 + f3).
+    pub fn c2() -> Self {
+        EvalWeights { w4: 0.0, ..EvalWeights::c3() }
+    }
+
+    /// Mertens heuristic C3: f2 + f3 - f4, the default weights.
+    /// C1 and C2 keep C3's weights for the terms they use.
+    pub fn c3() -> Self {
+        EvalWeights::default()
+    }
+
+    /// Looks up a preset by its name in strategy names: "C1", "C2" or "C3".
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "C1" => Some(EvalWeights::c1()),
+            "C2" => Some(EvalWeights::c2()),
+            "C3" => Some(EvalWeights::c3()),
+            _ => None,
+        }
+    }
+
+    /// Sets the wall-reserve weight, so spending a wall has to buy more than
+    /// `w5` worth of the other terms.
+    pub fn with_wall_weight(mut self, w5: f64) -> Self {
+        self.w5 = w5;
+        self
+    }
+
+    /// Sets the mobility weight, rewarding positions that keep pawn moves open.
+    pub fn with_mobility_weight(mut self, w6: f64) -> Self {
+        self.w6 = w6;
+        self
+    }
+
+    /// Sets the path-pressure weight, rewarding walls placed along the
+    /// opponent's shortest route before they block it.
+    pub fn with_pressure_weight(mut self, w7: f64) -> Self {
+        self.w7 = w7;
+        self
+    }
+
+    /// Static evaluation of a position, always from Player 1's perspective:
+    /// positive favours Player 1, negative favours Player 2, regardless of
+    /// `active_player`.
+    ///
+    /// The terms are applied symmetrically, so mirroring the board left-right
+    /// leaves the score unchanged and swapping the two players' roles negates it.
+    pub fn evaluate(&self, game: &Quoridor) -> f64 {
+        let p1_dist = game.distance_to_goal(Player::Player1) as f64;
+        let p2_dist = game.distance_to_goal(Player::Player2) as f64;
+
+        // f2: Position difference (P2 further = good for P1)
+        let f2_pos_diff = p2_dist - p1_dist;
+
+        // f3: Moves to the next row, inverted so that fewer moves scores higher
+        let attack = |moves_next: f64| if moves_next == 0.0 { 100.0 } else { 1.0 / (moves_next + 0.1) }; // Avoid div by zero
+        let p1_moves_next = game.moves_to_next_row(Player::Player1) as f64;
+        let p2_moves_next = game.moves_to_next_row(Player::Player2) as f64;
+        let f3_attack_diff = attack(p1_moves_next) - attack(p2_moves_next);
+
+        // f4: Opponent slowness (P2 needing more moves = good for P1)
+        let f4_defense_diff = p2_moves_next - p1_moves_next;
+
+        let mut score = self.w2 * f2_pos_diff + self.w3 * f3_attack_diff + self.w4 * f4_defense_diff + self.w5 * wall_reserve_diff(game);
+
+        // f6: Mobility difference, skipped when unweighted since it costs a move generation
+        if self.w6 != 0.0 {
+            score += self.w6 * mobility_diff(game);
+        }
+        // f7: Path pressure difference, skipped when unweighted like f6
+        if self.w7 != 0.0 {
+            score += self.w7 * pressure_diff(game);
+        }
+        score
+    }
+}
+
+/// f5: Player 1's walls in hand minus Player 2's.
+pub(crate) fn wall_reserve_diff(game: &Quoridor) -> f64 {
+    game.walls_available[&Player::Player1] as f64 - game.walls_available[&Player::Player2] as f64
+}
+
+/// f6: Player 1's legal pawn moves minus Player 2's.
+pub(crate) fn mobility_diff(game: &Quoridor) -> f64 {
+    game.mobility(Player::Player1) as f64 - game.mobility(Player::Player2) as f64
+}
+
+/// f7: pressure on Player 2's route minus pressure on Player 1's.
+pub(crate) fn pressure_diff(game: &Quoridor) -> f64 {
+    game.opponent_path_pressure(Player::Player1) as f64 - game.opponent_path_pressure(Player::Player2) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wall_weight_prefers_walls_in_hand() {
+        // Same pawns and walls on the board, only the reserves differ
+        let more_walls = Quoridor::new(9, 10, Some("e3 / / e2 e8 / 9 8 / 2"));
+        let fewer_walls = Quoridor::new(9, 10, Some("e3 / / e2 e8 / 7 8 / 2"));
+
+        // Default weights ignore reserves
+        let default = EvalWeights::default();
+        assert_eq!(default.evaluate(&more_walls), default.evaluate(&fewer_walls));
+
+        let weighted = EvalWeights::default().with_wall_weight(0.5);
+        assert!(weighted.evaluate(&more_walls) > weighted.evaluate(&fewer_walls));
+        assert!((weighted.evaluate(&more_walls) - weighted.evaluate(&fewer_walls) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mobility_weight_penalises_boxed_in_pawn() {
+        let open = Quoridor::new(9, 10, Some(" / / e5 e8 / 9 10 / 2"));
+        let boxed = Quoridor::new(9, 10, Some("d4 / d5 / e5 e8 / 8 10 / 2"));
+        assert_eq!(open.mobility(Player::Player1) - boxed.mobility(Player::Player1), 2);
+
+        let default = EvalWeights::default();
+        let mobile = EvalWeights::default().with_mobility_weight(1.0);
+        let mobility_term = |game: &Quoridor| mobile.evaluate(game) - default.evaluate(game);
+        assert!((mobility_term(&open) - mobility_term(&boxed) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_pressure_weight_rewards_wall_beside_route() {
+        // Same distances: e5v lines P2's e-file route without lengthening it,
+        // and P1 runs up the a-file well away from it
+        let open = Quoridor::new(9, 10, Some(" / / a1 e9 / 10 10 / 1"));
+        let pressed = Quoridor::new(9, 10, Some(" / e5 / a1 e9 / 9 10 / 2"));
+
+        let default = EvalWeights::default();
+        let pressure = EvalWeights::default().with_pressure_weight(1.0);
+        let pressure_term = |game: &Quoridor| pressure.evaluate(game) - default.evaluate(game);
+        assert_eq!(pressure_term(&open), 0.0);
+        assert!((pressure_term(&pressed) - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_presets_score_differently() {
+        // e4h in front of P1 makes it two moves from the next row, P2 only one
+        let game = Quoridor::new(9, 10, Some("e4 / / e4 e6 / 9 10 / 1"));
+        let scores: Vec<f64> = [EvalWeights::c1(), EvalWeights::c2(), EvalWeights::c3()]
+            .iter()
+            .map(|weights| weights.evaluate(&game))
+            .collect();
+        assert!((scores[0] - scores[1]).abs() > 1e-6);
+        assert!((scores[1] - scores[2]).abs() > 1e-6);
+        assert!((scores[0] - scores[2]).abs() > 1e-6);
+        assert_eq!(EvalWeights::preset("C2"), Some(EvalWeights::c2()));
+        assert_eq!(EvalWeights::preset("C4"), None);
+    }
+}
@@ -0,0 +1,126 @@
+// --- File: quoridor-project/quoridor-core/src/strategy/greedy_block.rs ---
+
+use crate::game::Quoridor;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
+use crate::strategy::{Strategy, ShortestPathStrategy};
+
+/// Deterministic baseline that weighs one pawn step against one wall each turn.
+///
+/// Advancing is worth the drop in our own shortest-path distance; a wall is
+/// worth how much it lengthens the opponent's path minus how much it lengthens
+/// ours. The wall is placed only when its net swing is strictly larger, so
+/// ties and an empty hand of walls fall back to advancing. Unlike Defensive,
+/// there is no randomness: the same position always gets the same move.
+pub struct GreedyBlockStrategy {
+    base: QuoridorStrategy,
+    // Internal strategy for choosing the pawn step
+    offensive_strategy: ShortestPathStrategy,
+}
+
+impl GreedyBlockStrategy {
+    pub fn new(opening_name: &str, opening_moves: Vec<String>) -> Self {
+        GreedyBlockStrategy {
+            base: QuoridorStrategy::new("GreedyBlock", opening_name, opening_moves),
+            offensive_strategy: ShortestPathStrategy::new("", Vec::new()),
+        }
+    }
+}
+
+impl Strategy for GreedyBlockStrategy {
+    fn name(&self) -> String {
+        self.base.name.clone()
+    }
+
+    fn describe(&self) -> String {
+        format!("{} (wall when its swing beats the step, else {})", self.name(), self.offensive_strategy.describe())
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
+    fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+        // Try opening move first
+        if let Some(opening_move) = self.base.try_opening_move(game) {
+            return Some(opening_move);
+        }
+
+        let player = game.active_player;
+        let opponent = player.opponent();
+        let own_distance = game.distance_to_goal(player);
+        let opponent_distance = game.distance_to_goal(opponent);
+
+        // Swing from advancing: how much closer the best pawn step brings us
+        let advance = self.offensive_strategy.choose_move(game);
+        let advance_swing = advance.as_ref().map_or(isize::MIN, |move_str| {
+            let mut temp_game = game.clone();
+            if !temp_game.move_pawn(move_str, false) { return isize::MIN; }
+            own_distance as isize - temp_game.distance_to_goal(player) as isize
+        });
+
+        // Swing from the best wall: opponent's extra distance minus our own.
+        // Walls come in a fixed board-scan order and the first of equally good ones is kept.
+        let mut best_wall: Option<(isize, String)> = None;
+        for (coord, orientation) in game.get_legal_walls_coords(player) {
+            let mut temp_game = game.clone();
+            if !temp_game.add_wall_at(coord, orientation, false) { continue; }
+            let opponent_increase = temp_game.distance_to_goal(opponent) as isize - opponent_distance as isize;
+            let own_increase = temp_game.distance_to_goal(player) as isize - own_distance as isize;
+            let swing = opponent_increase - own_increase;
+            if best_wall.as_ref().is_none_or(|(best_swing, _)| swing > *best_swing) {
+                best_wall = Some((swing, format!("{}{}", game.coord_to_algebraic(coord), orientation)));
+            }
+        }
+
+        match best_wall {
+            Some((wall_swing, wall_move)) if wall_swing > advance_swing => Some(wall_move),
+            _ => advance,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn test_blocks_choke_point_when_ahead_on_walls() {
+        // A wall line at ranks 5/6 leaves P2 (on b7) only the g-i gap to get through
+        let game = Quoridor::new(9, 10, Some("a5c5e5 / / e1 b7 / 7 1 / 1"));
+        let mut strategy = GreedyBlockStrategy::new("", Vec::new());
+        let chosen = strategy.choose_move(&game).unwrap();
+        assert!(chosen.ends_with('h') || chosen.ends_with('v'), "expected a wall, got {}", chosen);
+
+        let mut after = game.clone();
+        assert!(after.add_wall(&chosen, false, true));
+        assert_eq!(after.distance_to_goal(Player::Player1), game.distance_to_goal(Player::Player1));
+        assert!(after.distance_to_goal(Player::Player2) >= game.distance_to_goal(Player::Player2) + 2);
+    }
+
+    #[test]
+    fn test_advances_without_walls_or_worthwhile_blocks() {
+        let mut strategy = GreedyBlockStrategy::new("", Vec::new());
+        // Opening position: no wall gains more than the one step of advancing
+        assert_eq!(strategy.choose_move(&Quoridor::standard()), Some("e2".to_string()));
+
+        let no_walls = Quoridor::new(9, 10, Some("a5c5e5 / / e1 b7 / 0 1 / 1"));
+        assert_eq!(strategy.choose_move(&no_walls), Some("e2".to_string()));
+    }
+
+    #[test]
+    fn test_describe_names_the_step_strategy() {
+        let description = GreedyBlockStrategy::new("", Vec::new()).describe();
+        assert!(description.starts_with("GreedyBlock ("));
+        assert!(description.contains("ShortestPath"), "{}", description);
+    }
+}
@@ -0,0 +1,184 @@
+// --- File: quoridor-project/quoridor-core/src/strategy/logging.rs ---
+
+//! Strategy decorator that reports every decision to a log sink.
+
+use crate::game::Quoridor;
+use crate::graph::get_blocked_edges_by_wall;
+use crate::player::Player;
+use crate::strategy::{ChooseResult, EvalWeights, OpeningDeviation, Strategy};
+use crate::types::Coord;
+use std::collections::HashSet;
+
+/// Receives one log entry per decision. Must be `Send + Sync` like the
+/// strategies themselves, so wrapped strategies can still run on worker threads.
+pub type LogSink = Box<dyn FnMut(String) + Send + Sync>;
+
+/// Wraps any strategy and sends one entry to the sink for every move it is
+/// asked for: the position as an ASCII board, what the inner strategy
+/// returned, and the static evaluation (default `EvalWeights`, from Player 1's
+/// perspective) of the position after the move. Moves the game would reject
+/// are logged as illegal and still returned unchanged, so the caller sees
+/// exactly what the inner strategy chose.
+pub struct LoggingStrategy<S: Strategy> {
+    inner: S,
+    sink: LogSink,
+    weights: EvalWeights,
+}
+
+impl<S: Strategy> LoggingStrategy<S> {
+    pub fn new(inner: S, sink: LogSink) -> Self {
+        LoggingStrategy {
+            inner,
+            sink,
+            weights: EvalWeights::default(),
+        }
+    }
+
+    /// Returns the wrapped strategy.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Describes the choice and what it leads to, e.g. "chose e2 (eval +0.60)".
+    fn describe(&self, game: &Quoridor, result: &ChooseResult) -> String {
+        match result {
+            ChooseResult::Move(move_str) => {
+                let mut next = game.clone();
+                if next.apply_move(move_str, true) {
+                    format!("chose {} (eval {:+.2})", move_str, self.weights.evaluate(&next))
+                } else {
+                    format!("chose {} (illegal move)", move_str)
+                }
+            }
+            ChooseResult::Resign => "resigned".to_string(),
+            ChooseResult::NoMove => "no legal move".to_string(),
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for LoggingStrategy<S> {
+    fn name(&self) -> String {
+        format!("{}+log", self.inner.name())
+    }
+
+    fn describe(&self) -> String {
+        format!("{} [logged]", self.inner.describe())
+    }
+
+    fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+        self.choose(game).into_move()
+    }
+
+    fn choose(&mut self, game: &Quoridor) -> ChooseResult {
+        let result = self.inner.choose(game);
+        let entry = format!(
+            "{} as {}, ply {}\n{}{}",
+            self.inner.name(), game.active_player, game.ply(), ascii_board(game), self.describe(game, &result)
+        );
+        (self.sink)(entry);
+        result
+    }
+
+    fn should_swap(&mut self, game: &Quoridor) -> bool {
+        self.inner.should_swap(game)
+    }
+
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        self.inner.set_param(name, value)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.inner.set_opening_mode(mode)
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.inner.opening_deviation()
+    }
+
+    fn save_state(&self) -> Option<Vec<u8>> {
+        self.inner.save_state()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> bool {
+        self.inner.load_state(state)
+    }
+}
+
+/// Draws the board with rank 1 at the bottom: pawns as "1" and "2", empty
+/// squares as ".", walls as "|" between columns and "-" between rows.
+fn ascii_board(game: &Quoridor) -> String {
+    let blocked: HashSet<(Coord, Coord)> = game
+        .placed_walls()
+        .into_iter()
+        .filter_map(|(coord, orientation)| get_blocked_edges_by_wall(coord, orientation, game.size))
+        .flatten()
+        .collect();
+    let is_blocked = |a: Coord, b: Coord| blocked.contains(&(a, b)) || blocked.contains(&(b, a));
+    let square = |coord: Coord| match game.pawn_positions.iter().find(|(_, &pos)| pos == coord) {
+        Some((Player::Player1, _)) => '1',
+        Some((Player::Player2, _)) => '2',
+        None => '.',
+    };
+
+    let mut lines = Vec::new();
+    for row in 0..game.size {
+        if row > 0 {
+            let mut between = String::from("   ");
+            for col in 0..game.size {
+                between.push(if is_blocked((row - 1, col), (row, col)) { '-' } else { ' ' });
+                between.push(' ');
+            }
+            lines.push(between);
+        }
+        let mut rank = format!("{:>2} ", game.size - row);
+        for col in 0..game.size {
+            rank.push(square((row, col)));
+            rank.push(if col + 1 < game.size && is_blocked((row, col), (row, col + 1)) { '|' } else { ' ' });
+        }
+        lines.push(rank);
+    }
+    lines.push(format!("   {}", (0..game.size).map(|col| format!("{} ", (b'a' + col as u8) as char)).collect::<String>()));
+
+    lines.iter().map(|line| format!("{}\n", line.trim_end())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::ShortestPathStrategy;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_one_entry_per_move() {
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&entries);
+        let inner = ShortestPathStrategy::new("", Vec::new());
+        let mut logged = LoggingStrategy::new(inner, Box::new(move |entry| log.lock().unwrap().push(entry)));
+        assert_eq!(logged.name(), "ShortestPath+log");
+
+        let mut game = Quoridor::new(5, 3, None);
+        for _ in 0..3 {
+            let move_str = logged.choose_move(&game).unwrap();
+            assert!(game.apply_move(&move_str, true));
+        }
+
+        let entries = entries.lock().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(entries[0].starts_with("ShortestPath as player1, ply 0\n"));
+        assert!(entries[0].contains(" 1 . . 1 . .\n")); // P1 on c1
+        assert!(entries[0].contains("chose c2 (eval "));
+        assert!(entries[1].contains("player2, ply 1"));
+    }
+
+    #[test]
+    fn test_ascii_board_draws_walls() {
+        let game = Quoridor::new_unchecked(3, 1, "a2 / b2 / b1 b3 / 0 1 / 1");
+        // a2h sits between ranks 2 and 3 on files a and b; b2v between files b
+        // and c on ranks 2 and 3
+        assert_eq!(ascii_board(&game), " 3 . 2|.\n   - -\n 2 . .|.\n\n 1 . 1 .\n   a b c\n");
+    }
+}
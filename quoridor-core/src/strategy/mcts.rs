@@ -2,10 +2,12 @@
 
 use crate::game::Quoridor;
 use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
-use crate::strategy::Strategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
+use crate::strategy::{ChooseResult, Strategy};
 use rand::prelude::*;
 use std::cmp::Ordering; // Needed for max_by
+use std::collections::HashSet;
+use std::hash::{BuildHasher, RandomState};
 use std::{f64, ptr}; // ptr might not be needed if we avoid raw pointers
 
 // --- Platform-specific Timer Handling ---
@@ -116,6 +118,31 @@ impl MCTSNode {
         self.children.push(new_node);
     }
 
+    /// Re-syncs a node whose stored moves no longer match `game`, the state the
+    /// search actually reached: the player to move and the untried moves are
+    /// fetched again, leaving out moves already expanded as children.
+    fn resync(&mut self, game: &Quoridor) {
+        self.player_to_move = game.active_player;
+        let expanded: HashSet<&str> = self.children.iter().map(|child| child.move_str.as_str()).collect();
+        self.unexpanded_moves = game.all_legal_moves(game.active_player)
+            .into_iter()
+            .filter(|move_str| !expanded.contains(move_str.as_str()))
+            .collect();
+    }
+
+    /// Takes the statistics of a dropped descendant back out of this node, which
+    /// counted every playout through it. A playout scores 10 between the two
+    /// players, so the descendant's score converts to this node's side by
+    /// subtracting it from 10 per visit when the player to move differs.
+    fn discount(&mut self, dropped: &MCTSNode) {
+        self.visits -= dropped.visits;
+        self.wins -= if self.player_to_move == dropped.player_to_move {
+            dropped.wins
+        } else {
+            10.0 * dropped.visits as f64 - dropped.wins
+        };
+    }
+
     /// Updates the node's statistics during backpropagation.
     /// `score`: The score from the simulation (e.g., 10.0 for win, 5.0 for draw, 0.0 for loss)
     ///        relative to the player whose turn it is *at this node*.
@@ -131,6 +158,9 @@ pub struct MCTSStrategy {
     base: QuoridorStrategy,
     simulation_limit: usize,
     exploration_param: f64, // C value in UCT
+    max_simulation_moves: Option<usize>, // Rollout length cap override; None scales with board size
+    terminal_checks: bool, // Take immediate wins and answer win threats before searching
+    win_decay: f64, // Per-ply discount on playout results; 1.0 scores every win the same
     #[cfg(not(target_arch = "wasm32"))]
     time_limit: Option<Duration>,
     #[cfg(target_arch = "wasm32")]
@@ -145,6 +175,9 @@ impl MCTSStrategy {
             base: QuoridorStrategy::new(&name, opening_name, opening_moves),
             simulation_limit: sim_limit,
             exploration_param: 1.414_f64, // sqrt(2)
+            max_simulation_moves: None,
+            terminal_checks: true,
+            win_decay: 1.0,
             #[cfg(not(target_arch = "wasm32"))]
             time_limit: None,
             #[cfg(target_arch = "wasm32")]
@@ -174,18 +207,93 @@ impl MCTSStrategy {
         self
     }
 
+    /// Sets the UCT exploration constant C (default sqrt(2)) for later searches.
+    /// Higher values spread simulations across more moves.
+    pub fn set_exploration(&mut self, exploration: f64) {
+        self.exploration_param = exploration;
+    }
+
+    /// Overrides the maximum number of moves played in a single rollout before
+    /// it is scored as a draw.
+    pub fn with_max_simulation_moves(mut self, moves: usize) -> Self {
+        if moves > 0 {
+            self.max_simulation_moves = Some(moves);
+        }
+        self
+    }
+
+    /// Enables or disables the root terminal checks (on by default): a one-move
+    /// win is played without searching, and when the opponent threatens to win
+    /// next turn only the replies that stop it are searched. Random rollouts with
+    /// a small budget can otherwise miss both.
+    pub fn with_terminal_checks(mut self, enabled: bool) -> Self {
+        self.terminal_checks = enabled;
+        self
+    }
+
+    /// Discounts playout results by game length: a win `t` plies after the
+    /// searched position scores `5.0 + 5.0 * decay^t` instead of 10.0 and a
+    /// loss `5.0 - 5.0 * decay^t` instead of 0.0, so the search prefers quicker
+    /// wins and drags out lost games, while any win still beats a draw (5.0)
+    /// and any draw a loss. Values outside (0, 1] are ignored; 1.0 (the
+    /// default) scores every win the same.
+    pub fn with_win_decay(mut self, decay: f64) -> Self {
+        self.set_win_decay(decay);
+        self
+    }
+
+    fn set_win_decay(&mut self, decay: f64) -> bool {
+        if decay > 0.0 && decay <= 1.0 {
+            self.win_decay = decay;
+            return true;
+        }
+        false
+    }
+
+    /// Score of a playout for the player to move at a node, where the game
+    /// ended `plies` plies after the searched position.
+    fn playout_score(&self, winner: Option<Player>, player_to_move: Player, plies: usize) -> f64 {
+        let margin = 5.0 * self.win_decay.powi(plies as i32);
+        match winner {
+            Some(winning_player) if winning_player == player_to_move => 5.0 + margin, // Win
+            Some(_) => 5.0 - margin, // Loss
+            None => 5.0, // Draw
+        }
+    }
+
+    /// Rollout length cap for a board of the given size.
+    /// Defaults to `size * size * 2`, which leaves room for long wall battles on
+    /// large boards without wasting time on shuffling draws on small ones.
+    fn simulation_move_cap(&self, size: usize) -> usize {
+        self.max_simulation_moves.unwrap_or(size * size * 2)
+    }
+
     /// Runs the MCTS search for the best move from the given game state.
-    fn run_search(&self, game: &Quoridor) -> String {
+    fn run_search(&self, game: &Quoridor) -> ChooseResult {
         let mut rng = thread_rng();
         let root_player = game.active_player; // Player whose turn it is at the root
 
         // Get initial legal moves
-        let legal_pawn = game.get_legal_moves(root_player);
-        let legal_walls = game.get_legal_walls(root_player);
-        let root_moves: Vec<String> = legal_pawn.into_iter().chain(legal_walls.into_iter()).collect();
+        let mut root_moves = game.all_legal_moves(root_player);
 
-        if root_moves.is_empty() { return "resign".to_string(); }
-        if root_moves.len() == 1 { return root_moves[0].clone(); }
+        if root_moves.is_empty() { return ChooseResult::NoMove; }
+        if self.terminal_checks {
+            // Check for immediate wins (win_check ignores wall moves)
+            if let Some(winning_move) = root_moves.iter().find(|move_str| game.win_check(move_str)) {
+                return ChooseResult::Move(winning_move.clone());
+            }
+            // Facing a win threat, only search the replies that stop it (if any do)
+            if game.opponent_can_win_next().is_some() {
+                let safe_moves: Vec<String> = root_moves.iter()
+                    .filter(|move_str| !allows_immediate_win(game, move_str))
+                    .cloned()
+                    .collect();
+                if !safe_moves.is_empty() {
+                    root_moves = safe_moves;
+                }
+            }
+        }
+        if root_moves.len() == 1 { return ChooseResult::Move(root_moves[0].clone()); }
 
         // Create the root node representing the current state
         let mut root_node = MCTSNode::new(
@@ -194,6 +302,34 @@ impl MCTSStrategy {
             root_moves.clone(),
         );
 
+        let failures = self.search_tree(game, &mut root_node, &mut rng);
+        debug_assert!(failures.is_empty(), "MCTS: {}", failures.join("; "));
+
+        // --- Select Final Move ---
+         let best_move = if let Some(best_child_idx) = root_node.select_most_visited_child_index() {
+             // Defensive check: ensure index is valid
+              if best_child_idx < root_node.children.len() {
+                  Some(root_node.children[best_child_idx].move_str.clone())
+              } else {
+                  // Fallback if index is somehow out of bounds
+                  eprintln!("MCTS Warning: Best child index out of bounds.");
+                   root_moves.choose(&mut rng).cloned()
+              }
+         } else {
+             // Fallback if root has no children explored (should only happen if error or 1 move)
+              root_moves.choose(&mut rng).cloned()
+         };
+         // root_moves is non-empty here, so this is always a move
+         best_move.map_or(ChooseResult::NoMove, ChooseResult::Move)
+    }
+
+    /// Runs simulations from `game`, the state at `root_node`, growing the tree
+    /// until the simulation or time budget runs out. A tree move that does not
+    /// apply is recovered from (a stale child is dropped, stale untried moves are
+    /// fetched again) and described in the returned list, which `run_search`
+    /// expects to be empty for a tree it built itself.
+    fn search_tree<R: Rng>(&self, game: &Quoridor, root_node: &mut MCTSNode, rng: &mut R) -> Vec<String> {
+        let mut failures = Vec::new();
         let mut simulations_run = 0;
         #[cfg(not(target_arch = "wasm32"))]
         let start_time = Instant::now();
@@ -221,7 +357,7 @@ impl MCTSStrategy {
 
 
             let mut current_game_sim = game.clone(); // Clone state for this simulation run
-            let mut path: Vec<*mut MCTSNode> = vec![&mut root_node]; // Path of *mutable* pointers
+            let mut path: Vec<*mut MCTSNode> = vec![&mut *root_node]; // Path of *mutable* pointers
 
 
             // --- 1. Selection ---
@@ -246,21 +382,25 @@ impl MCTSStrategy {
                  };
 
                  // Get mutable reference to the chosen child and add to path
-                let next_node_ptr = unsafe { &mut (*current_node_ptr).children[best_child_idx] as *mut MCTSNode };
+                let next_node_ptr = unsafe { &mut (&mut (*current_node_ptr).children)[best_child_idx] as *mut MCTSNode };
                 path.push(next_node_ptr);
 
                 // Apply the child's move to the simulation game state
                 let move_str = &unsafe { &*next_node_ptr }.move_str; // Borrow immutably
-                let move_applied = if move_str.len() >= 3 {
-                    current_game_sim.add_wall(move_str, false, true)
-                } else {
-                    current_game_sim.move_pawn(move_str, true)
-                };
+                let move_applied = current_game_sim.apply_move(move_str, true);
 
                 if !move_applied {
-                    eprintln!("MCTS Error: Failed to apply selected move {} during selection.", move_str);
-                    // Backtrack or stop simulation? For now, stop this iteration.
-                    break; // Exit inner loop, simulation will proceed from previous state
+                    // The child's move does not fit the parent's state: drop the child
+                    // and its playouts, re-sync the parent and carry on from there
+                    failures.push(format!("failed to apply selected move {} in {}", move_str, current_game_sim.state_string));
+                    path.pop();
+                    let parent = unsafe { &mut *current_node_ptr };
+                    let dropped = parent.children.remove(best_child_idx);
+                    for node_ptr in &path {
+                        unsafe { &mut **node_ptr }.discount(&dropped);
+                    }
+                    parent.resync(&current_game_sim);
+                    break;
                 }
             } // End Selection loop
 
@@ -274,11 +414,7 @@ impl MCTSStrategy {
                    let player_after_expansion = current_game_sim.active_player; // Player *before* applying expansion move
 
                    // Apply the expansion move
-                    let move_applied = if move_to_expand.len() >= 3 {
-                        current_game_sim.add_wall(&move_to_expand, false, true)
-                    } else {
-                        current_game_sim.move_pawn(&move_to_expand, true)
-                    };
+                    let move_applied = current_game_sim.apply_move(&move_to_expand, true);
 
                     if move_applied {
                           // Get legal moves for the *new* state
@@ -286,9 +422,7 @@ impl MCTSStrategy {
                          let child_moves = if self.is_terminal(&current_game_sim) {
                               Vec::new()
                           } else {
-                              let p = current_game_sim.get_legal_moves(new_node_player);
-                              let w = current_game_sim.get_legal_walls(new_node_player);
-                              p.into_iter().chain(w.into_iter()).collect()
+                              current_game_sim.all_legal_moves(new_node_player)
                           };
 
                           // Add the new child node
@@ -296,46 +430,29 @@ impl MCTSStrategy {
                           let new_child_ptr = expandable_node.children.last_mut().unwrap() as *mut MCTSNode;
                           path.push(new_child_ptr); // Add expanded node to path for backpropagation
                     } else {
-                         // If expansion move failed, just simulate from the current state
-                         // This might happen if get_legal_moves had an issue earlier
-                          eprintln!("MCTS Warning: Failed to apply expansion move {}. Simulating from parent.", move_to_expand);
+                         // The untried moves are stale: re-fetch them for the player
+                         // actually to move and simulate from this node
+                          failures.push(format!("failed to apply expansion move {} in {}", move_to_expand, current_game_sim.state_string));
+                          expandable_node.resync(&current_game_sim);
                     }
               }
 
 
             // --- 3. Simulation ---
             // Simulate from the state reached at the end of selection/expansion
-             let winner: Option<Player> = self.simulate_random_playout(&mut current_game_sim);
+             let (winner, rollout_plies) = self.simulate_random_playout(&mut current_game_sim, &mut *rng);
+             let plies = path.len() - 1 + rollout_plies; // Tree moves plus rollout moves
 
             // --- 4. Backpropagation ---
             // Update nodes along the path with the simulation result
             for node_ptr in path.iter().rev() { // Iterate backwards from leaf to root
                  let node = unsafe { &mut **node_ptr };
                   // The score should be relative to the player whose turn it was *at this node*
-                  let score = match winner {
-                      Some(winning_player) if winning_player == node.player_to_move => 10.0, // Win
-                      Some(_) => 0.0, // Loss
-                      None => 5.0, // Draw
-                  };
-                  node.update(score);
+                  node.update(self.playout_score(winner, node.player_to_move, plies));
             }
 
         } // End MCTS loop
-
-        // --- Select Final Move ---
-         if let Some(best_child_idx) = root_node.select_most_visited_child_index() {
-             // Defensive check: ensure index is valid
-              if best_child_idx < root_node.children.len() {
-                  root_node.children[best_child_idx].move_str.clone()
-              } else {
-                  // Fallback if index is somehow out of bounds
-                  eprintln!("MCTS Warning: Best child index out of bounds.");
-                   root_moves.choose(&mut rng).cloned().unwrap_or_else(|| "resign".to_string())
-              }
-         } else {
-             // Fallback if root has no children explored (should only happen if error or 1 move)
-              root_moves.choose(&mut rng).cloned().unwrap_or_else(|| "resign".to_string())
-         }
+        failures
     }
 
     /// Checks if the game state is terminal (win).
@@ -352,22 +469,32 @@ impl MCTSStrategy {
     }
 
     /// Simulates a game using the heuristic from the Mertens paper (page 23).
-     fn simulate_random_playout(&self, game_state: &mut Quoridor) -> Option<Player> {
+    /// Returns the winner (None for a draw) and the number of moves played.
+     fn simulate_random_playout<R: Rng>(&self, game_state: &mut Quoridor, rng: &mut R) -> (Option<Player>, usize) {
          // No need to clone again if we modify the state passed from run_search directly
          // let mut current_game = game_state.clone();
          let mut current_game = game_state; // Modify the passed mutable state
-         let mut rng = thread_rng();
-         let max_sim_moves = 150; // Limit simulation length
+         let max_sim_moves = self.simulation_move_cap(current_game.size); // Limit simulation length
+         // Hashes of the positions seen in this rollout; a repeat means the pawns are
+         // shuffling. Hashing borrows the state string instead of copying it each ply.
+         let state_hasher = RandomState::new();
+         let mut seen_states: HashSet<u64> = HashSet::new();
 
-         for _ in 0..max_sim_moves {
+         for plies in 0..max_sim_moves {
              // Check for terminal state *before* making a move
-             if let Some(p1_pos) = current_game.pawn_positions.get(&Player::Player1) { if p1_pos.0 == 0 { return Some(Player::Player1); } }
-             if let Some(p2_pos) = current_game.pawn_positions.get(&Player::Player2) { if p2_pos.0 == current_game.size - 1 { return Some(Player::Player2); } }
+             if let Some(p1_pos) = current_game.pawn_positions.get(&Player::Player1) { if p1_pos.0 == 0 { return (Some(Player::Player1), plies); } }
+             if let Some(p2_pos) = current_game.pawn_positions.get(&Player::Player2) { if p2_pos.0 == current_game.size - 1 { return (Some(Player::Player2), plies); } }
 
              let player = current_game.active_player;
              let p_dist = current_game.distance_to_goal(player);
              let o_dist = current_game.distance_to_goal(player.opponent());
 
+             // Repeated position: stop shuffling and score the race instead of burning
+             // the move budget on a draw. The side to move wins ties (it is a tempo up).
+             if !seen_states.insert(state_hasher.hash_one(&current_game.state_string)) {
+                 return (Some(if p_dist <= o_dist { player } else { player.opponent() }), plies);
+             }
+
              let next_move: Option<String>;
 
              // Apply Mertens' simulation heuristic
@@ -393,35 +520,65 @@ impl MCTSStrategy {
                  }
              } else {
                  // --- Heuristic Branch 2: Consider all moves randomly ---
-                 let pawn_moves = current_game.get_legal_moves(player);
-                 let wall_moves = current_game.get_legal_walls(player);
-                 let all_moves: Vec<String> = pawn_moves.into_iter().chain(wall_moves.into_iter()).collect();
-                 next_move = all_moves.choose(&mut rng).cloned();
+                 let all_moves = current_game.all_legal_moves(player);
+                 next_move = all_moves.choose(rng).cloned();
              }
 
              // Apply the chosen move to the main simulation state
              if let Some(mv_str) = next_move {
-                 let moved = if mv_str.len() >= 3 {
-                     current_game.add_wall(&mv_str, false, true) // Use checking move in simulation? Paper implies random valid moves. Let's use check=true.
-                 } else {
-                     current_game.move_pawn(&mv_str, true)
-                 };
+                 let moved = current_game.apply_move(&mv_str, true);
+                 debug_assert!(moved, "MCTS: failed to apply rollout move {} in {}", mv_str, current_game.state_string);
                  if !moved {
-                      // If a chosen "legal" move fails, it indicates a problem. End sim as draw.
-                     // eprintln!("Simulation Error: Failed to apply move {}", mv_str);
-                     return None;
+                     // Fall back to the first legal move that applies, as if it had been chosen
+                     let fallback = current_game.all_legal_moves(current_game.active_player)
+                         .into_iter()
+                         .find(|move_str| current_game.clone().apply_move(move_str, true));
+                     match fallback {
+                         Some(move_str) => { current_game.apply_move(&move_str, true); }
+                         None => return (Some(player.opponent()), plies),
+                     }
                  }
              } else {
                  // No legal move available for the current player - opponent wins
-                 return Some(player.opponent());
+                 return (Some(player.opponent()), plies);
              }
          } // End simulation loop
 
-         None // Draw if max moves reached
+         (None, max_sim_moves) // Draw if max moves reached
      }
 } // end impl MCTSStrategy
 
+/// True if, after `move_str`, the opponent has a pawn move that wins on the spot.
+/// A move that cannot be applied counts as losing.
+fn allows_immediate_win(game: &Quoridor, move_str: &str) -> bool {
+    let mut next = game.clone();
+    let applied = next.apply_move(move_str, true);
+    if !applied {
+        return true;
+    }
+    next.get_legal_moves(next.active_player)
+        .iter()
+        .any(|reply| next.win_check(reply))
+}
+
 impl Strategy for MCTSStrategy {
+    fn describe(&self) -> String {
+        let rollout_cap = self.max_simulation_moves.map_or("2 x board area".to_string(), |moves| moves.to_string());
+        let mut details = format!(
+            "simulations: {}, exploration: {}, rollout cap: {}, terminal checks: {}, win decay: {}",
+            self.simulation_limit, self.exploration_param, rollout_cap, if self.terminal_checks { "on" } else { "off" }, self.win_decay
+        );
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(limit) = self.time_limit {
+            details += &format!(", time limit: {:.2?}", limit);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(iter_limit) = self.time_limit_iterations {
+            details += &format!(", time limit: {} iterations", iter_limit);
+        }
+        format!("{} ({})", self.name(), details)
+    }
+
     fn name(&self) -> String {
         // Provide a name reflecting configuration
         let mut name = format!("MCTS{}", self.simulation_limit);
@@ -439,6 +596,40 @@ impl Strategy for MCTSStrategy {
          name // Return combined name
     }
 
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
+    /// The search tree is rebuilt for every move, so the opening progress is
+    /// the only state carried between moves.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        Some(self.base.save_state())
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> bool {
+        self.base.load_state(state)
+    }
+
+    /// Tunables: "exploration" (see `set_exploration`) and "win_decay" (see
+    /// `with_win_decay`; out-of-range values are rejected).
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        match name {
+            "exploration" => self.set_exploration(value),
+            "win_decay" => return self.set_win_decay(value),
+            _ => return false,
+        }
+        true
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -446,12 +637,205 @@ impl Strategy for MCTSStrategy {
         }
 
         // Run the MCTS search
-        let best_move = self.run_search(game);
+        self.run_search(game).into_move()
+    }
 
-        if best_move == "resign" {
-             None
-        } else {
-             Some(best_move)
+    fn choose(&mut self, game: &Quoridor) -> ChooseResult {
+        if let Some(opening_move) = self.base.try_opening_move(game) {
+            return ChooseResult::Move(opening_move);
         }
+        self.run_search(game)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::RuleSet;
+
+    /// Decisive rollouts out of `runs` from `game`, with a fixed seed so the
+    /// count is the same on every run.
+    fn count_decisive(strategy: &MCTSStrategy, game: &Quoridor, runs: usize) -> usize {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..runs)
+            .filter(|_| strategy.simulate_random_playout(&mut game.clone(), &mut rng).0.is_some())
+            .count()
+    }
+
+    #[test]
+    fn test_simulation_cap_scales_with_board_size() {
+        let strategy = MCTSStrategy::new("", Vec::new(), 10);
+        assert_eq!(strategy.simulation_move_cap(5), 50);
+        assert_eq!(strategy.simulation_move_cap(9), 162);
+        assert_eq!(strategy.simulation_move_cap(11), 242);
+
+        let overridden = MCTSStrategy::new("", Vec::new(), 10).with_max_simulation_moves(150);
+        assert_eq!(overridden.simulation_move_cap(5), 150);
+        assert_eq!(overridden.simulation_move_cap(11), 150);
+    }
+
+    #[test]
+    fn test_small_board_cap_keeps_rollouts_decisive() {
+        // The 5x5 cap is a third of the old fixed 150, but rollouts still finish
+        let game = Quoridor::mini();
+        let scaled = MCTSStrategy::new("", Vec::new(), 10);
+        let fixed = MCTSStrategy::new("", Vec::new(), 10).with_max_simulation_moves(150);
+        let runs = 50;
+        assert_eq!(count_decisive(&fixed, &game, runs), runs);
+        assert_eq!(count_decisive(&scaled, &game, runs), runs);
+    }
+
+    #[test]
+    fn test_simulation_cap_override_truncates_rollouts() {
+        let game = Quoridor::mini();
+        let truncated = MCTSStrategy::new("", Vec::new(), 10).with_max_simulation_moves(2);
+        assert_eq!(count_decisive(&truncated, &game, 20), 0); // Nobody can win in two plies
+    }
+
+    #[test]
+    fn test_shuffling_rollout_is_scored_not_drawn() {
+        // Without jumps and with no walls left, the pawns block each other around
+        // c2h and shuffle back and forth, which used to run out the cap as a draw
+        let game = Quoridor::new(5, 0, Some("c2 / b3d4 / d1 e4 / 0 0 / 1")).with_rules(RuleSet { allow_jumps: false });
+        let strategy = MCTSStrategy::new("", Vec::new(), 10);
+        let mut rollout = game.clone();
+        let (winner, plies) = strategy.simulate_random_playout(&mut rollout, &mut StdRng::seed_from_u64(7));
+        assert!(winner.is_some());
+        assert!(plies < strategy.simulation_move_cap(5));
+        // Scored by the race when the position repeated, not by reaching a goal
+        assert_eq!(rollout.winner(), None);
+        assert_eq!(count_decisive(&strategy, &game, 10), 10);
+    }
+
+    #[test]
+    fn test_exploration_changes_child_selection() {
+        // A: well explored and strong for the parent; B: barely explored and weaker
+        let mut root = MCTSNode::new("root".to_string(), Player::Player1, Vec::new());
+        root.visits = 100;
+        root.add_child("a".to_string(), Player::Player2, Vec::new());
+        root.add_child("b".to_string(), Player::Player2, Vec::new());
+        (root.children[0].visits, root.children[0].wins) = (90, 9.0);
+        (root.children[1].visits, root.children[1].wins) = (10, 5.0);
+
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 10);
+        strategy.set_exploration(0.0);
+        assert_eq!(root.select_best_child_index(strategy.exploration_param), Some(0));
+        assert!(strategy.set_param("exploration", 5.0));
+        assert_eq!(root.select_best_child_index(strategy.exploration_param), Some(1));
+        assert!(!strategy.set_param("wall_preference", 1.0));
+    }
+
+    #[test]
+    fn test_resync_refetches_stale_moves() {
+        // A node holding Player 2's replies although Player 1 is to move
+        let game = Quoridor::mini();
+        let mut node = MCTSNode::new("root".to_string(), Player::Player2, game.all_legal_moves(Player::Player2));
+        node.add_child("c2".to_string(), Player::Player2, Vec::new());
+        node.resync(&game);
+
+        let mut expected = game.all_legal_moves(Player::Player1);
+        expected.retain(|move_str| move_str != "c2");
+        assert_eq!(node.player_to_move, Player::Player1);
+        assert_eq!(node.unexpanded_moves, expected);
+    }
+
+    #[test]
+    fn test_search_recovers_from_a_stale_tree() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        // A root whose only child plays "a1", no move for Player 1 here, and which
+        // already counts that child's three playouts (two wins for Player 2)
+        let game = Quoridor::mini();
+        let mut root = MCTSNode::new("root".to_string(), Player::Player1, Vec::new());
+        root.add_child("a1".to_string(), Player::Player2, Vec::new());
+        root.children[0].visits = 3;
+        root.children[0].wins = 20.0;
+        root.visits = 3;
+        root.wins = 10.0;
+        let strategy = MCTSStrategy::new("", Vec::new(), 50);
+        let failures = strategy.search_tree(&game, &mut root, &mut StdRng::seed_from_u64(3));
+
+        assert_eq!(failures.len(), 1, "{:?}", failures);
+        assert!(failures[0].contains("selected move a1"));
+        assert_eq!(root.visits, 50); // The dropped child's playouts no longer count
+        let legal = game.all_legal_moves(Player::Player1);
+        assert!(root.children.iter().all(|child| legal.contains(&child.move_str)));
+        assert_eq!(root.children.iter().map(|child| child.visits).sum::<usize>(), 50);
+
+        // Stale untried moves are fetched again at expansion
+        let mut root = MCTSNode::new("root".to_string(), Player::Player1, vec!["a1".to_string()]);
+        let failures = strategy.search_tree(&game, &mut root, &mut StdRng::seed_from_u64(3));
+        assert_eq!(failures.len(), 1, "{:?}", failures);
+        assert!(failures[0].contains("expansion move a1"));
+        assert_eq!(root.visits, 50);
+        assert!(root.children.iter().all(|child| legal.contains(&child.move_str)));
+    }
+
+    #[test]
+    fn test_search_applies_every_move_it_tries() {
+        // Without jumps P1 on a1 is stuck behind P2 and a1v, so only walls are
+        // legal and rollouts keep hitting positions with no pawn move. A move the
+        // search cannot apply trips a debug assertion instead of skewing the scores.
+        let game = Quoridor::new(5, 3, Some(" / a1 / a1 a2 / 2 3 / 1")).with_rules(RuleSet { allow_jumps: false });
+        assert!(game.get_legal_moves(Player::Player1).is_empty());
+
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 300);
+        let move_str = strategy.choose_move(&game).unwrap();
+        assert!(game.get_legal_walls(Player::Player1).contains(&move_str));
+    }
+
+    #[test]
+    fn test_tiny_budget_takes_one_move_win() {
+        // P1 on e8 wins with e9; two simulations are far too few to find it by search
+        let game = Quoridor::new(9, 10, Some(" / / e8 e2 / 10 10 / 1"));
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 2);
+        assert_eq!(strategy.choose_move(&game), Some("e9".to_string()));
+    }
+
+    #[test]
+    fn test_tiny_budget_blocks_win_threat() {
+        // P2 on a2 threatens a1; only a1h stops it, so it is the one move left to search
+        let game = Quoridor::new(9, 10, Some(" / / e5 a2 / 10 10 / 1"));
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 2);
+        assert_eq!(strategy.choose_move(&game), Some("a1h".to_string()));
+    }
+
+    #[test]
+    fn test_win_decay_prefers_quicker_win() {
+        // 5x5, no walls: P1 on c4 wins now with c5, and still wins after any
+        // other move since P2 on a5 is four steps from home. Without the root
+        // shortcut only the decay tells the moves apart.
+        let game = Quoridor::new(5, 3, Some(" / / c4 a5 / 0 0 / 1"));
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 200).with_terminal_checks(false).with_win_decay(0.5);
+        assert_eq!(strategy.choose_move(&game), Some("c5".to_string()));
+
+        assert_eq!(strategy.playout_score(Some(Player::Player1), Player::Player1, 2), 6.25);
+        assert_eq!(strategy.playout_score(Some(Player::Player1), Player::Player2, 2), 3.75);
+        assert!(!strategy.set_param("win_decay", 1.5));
+        assert!(strategy.set_param("win_decay", 1.0));
+        assert_eq!(strategy.playout_score(Some(Player::Player1), Player::Player1, 40), 10.0);
+    }
+
+    #[test]
+    fn test_long_win_still_beats_draw_and_loss() {
+        // A full-length 9x9 rollout with a decay that has long since faded
+        let strategy = MCTSStrategy::new("", Vec::new(), 1).with_win_decay(0.99);
+        let plies = 9 * 9 * 2;
+        let win = strategy.playout_score(Some(Player::Player1), Player::Player1, plies);
+        let draw = strategy.playout_score(None, Player::Player1, plies);
+        let loss = strategy.playout_score(Some(Player::Player2), Player::Player1, plies);
+        assert!(win > draw && draw > loss, "win {} draw {} loss {}", win, draw, loss);
+    }
+
+    #[test]
+    fn test_describe_lists_search_settings() {
+        let mut strategy = MCTSStrategy::new("", Vec::new(), 2500).with_max_simulation_moves(80);
+        strategy.set_exploration(0.7);
+        let description = strategy.describe();
+        assert!(description.starts_with("MCTS2500 ("));
+        assert!(description.contains("simulations: 2500"));
+        assert!(description.contains("exploration: 0.7"));
+        assert!(description.contains("rollout cap: 80"));
+    }
+}
@@ -2,13 +2,62 @@
 
 use crate::game::Quoridor;
 use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
-use crate::strategy::Strategy;
-use std::cmp::Ordering;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
+use crate::strategy::evaluation::EvalWeights;
+use crate::strategy::{preset_weights, Strategy};
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+
+/// Rough node budget for adaptive depth: the deepest search whose estimated
+/// tree size (branching factor ^ depth) stays under this is chosen.
+const ADAPTIVE_NODE_BUDGET: f64 = 20_000.0;
+
+// --- Platform-specific Timer Handling ---
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+/// Wall-clock deadline for a time-limited search.
+/// On wasm32 `Instant` is unavailable, so the deadline never expires and
+/// searches always run to their full depth.
+#[derive(Clone, Copy)]
+struct Deadline {
+    #[cfg(not(target_arch = "wasm32"))]
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline that never expires (fixed-depth search).
+    fn none() -> Self {
+        Deadline {
+            #[cfg(not(target_arch = "wasm32"))]
+            at: None,
+        }
+    }
+
+    /// Returns true once the search should stop.
+    fn expired(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(at) = self.at {
+            return Instant::now() >= at;
+        }
+        false
+    }
+}
+// --- End Platform-specific Timer Handling ---
 
 pub struct MinimaxStrategy {
     base: QuoridorStrategy,
-    depth: usize,
+    depth: usize, // Maximum search depth (the fixed depth when no time limit is set)
+    #[cfg(not(target_arch = "wasm32"))]
+    time_limit: Option<Duration>, // Per-move budget; enables iterative deepening
+    weights: EvalWeights, // Static evaluation weights
+    adaptive_depth: Option<(usize, usize)>, // (min, max) depth chosen per move from the branching factor
+    aspiration_window: Option<f64>, // Half-width of the window around the previous depth's score
+    // --- Move ordering state, reset at the start of every choose_move ---
+    move_ordering: bool, // Use killer/history heuristics (false = generation order)
+    killer_moves: Vec<[Option<String>; 2]>, // Last two cutoff moves per remaining depth
+    history: HashMap<String, i32>, // Cutoff counts weighted by depth², shared by all nodes
+    nodes_searched: usize, // Nodes visited by the last search
 }
 
 impl MinimaxStrategy {
@@ -20,63 +69,221 @@ impl MinimaxStrategy {
         MinimaxStrategy {
             base: QuoridorStrategy::new(&name, opening_name, opening_moves),
             depth,
+            #[cfg(not(target_arch = "wasm32"))]
+            time_limit: None,
+            weights: EvalWeights::default(),
+            adaptive_depth: None,
+            aspiration_window: None,
+            move_ordering: true,
+            killer_moves: Vec::new(),
+            history: HashMap::new(),
+            nodes_searched: 0,
         }
     }
 
-    /// Evaluates the current board state from the perspective of the *player whose turn it is*.
-    /// Higher scores are better for the current player.
-    /// Uses the heuristic (f2+f3+f4 with weights) from the Mertens paper (strategy C3).
-    fn evaluate_state(&self, game: &Quoridor) -> f64 {
-        let current_player = game.active_player; // Player to potentially move *next*
-        let opponent = current_player.opponent();
+    /// Enables or disables killer-move and history ordering (on by default).
+    /// With ordering off, moves are searched in generation order.
+    pub fn with_move_ordering(mut self, enabled: bool) -> Self {
+        self.move_ordering = enabled;
+        self
+    }
+
+    /// Replaces the static evaluation weights (default: the paper's C3 weights).
+    pub fn with_eval_weights(mut self, weights: EvalWeights) -> Self {
+        self.weights = weights;
+        self
+    }
 
-         // Important: Evaluate based on the state *before* the current player moves.
-         // Typically, evaluation functions assess the position itself, not whose turn it is.
-         // Let's evaluate from Player 1's perspective consistently, negating if it's P2's turn conceptually in minimax.
-         // Or, evaluate relative to the *player who made the move leading to this state*.
-         // The Mertens paper heuristic seems relative to the MAX player (higher is better).
+    /// Evaluates with a named heuristic preset, e.g. "Minimax2-C1" (see `preset_weights`).
+    pub fn with_preset(mut self, preset: &str) -> Self {
+        self.weights = preset_weights(&mut self.base.name, preset);
+        self
+    }
 
-        let p1_dist = game.distance_to_goal(Player::Player1) as f64;
-        let p2_dist = game.distance_to_goal(Player::Player2) as f64;
+    /// Starts each iterative-deepening depth after the first with the window
+    /// (previous score - `half_width`, previous score + `half_width`) instead of
+    /// a full one, re-searching with a full window when the result falls outside.
+    /// Narrow windows cut more branches when scores are stable between depths;
+    /// the chosen move and score are the same as without. Off by default;
+    /// only applies to iterative deepening (with a time limit, or analysis).
+    pub fn with_aspiration_window(mut self, half_width: f64) -> Self {
+        self.aspiration_window = (half_width > 0.0).then_some(half_width);
+        self
+    }
 
-        // Heuristic relative to Player 1 (consistent reference)
-        // f2: Position difference (opponent distance - player distance)
-        let f2_pos_diff = p2_dist - p1_dist;
+    /// Picks the search depth per move between `min` and `max` instead of
+    /// using a fixed depth: the busier the position (legal moves for both
+    /// sides), the shallower the search, which keeps the cost per move roughly
+    /// bounded without a timer. With a time limit, the chosen depth caps the
+    /// iterative deepening. The name becomes e.g. "Minimax1-4".
+    /// Panics if `min` is 0 or greater than `max`.
+    pub fn with_adaptive_depth(mut self, min: usize, max: usize) -> Self {
+        if min == 0 || min > max {
+            panic!("Adaptive depth needs 1 <= min <= max, got {}..{}", min, max);
+        }
+        self.base.name = self.base.name.replacen(&format!("Minimax{}", self.depth), &format!("Minimax{}-{}", min, max), 1);
+        self.depth = max;
+        self.adaptive_depth = Some((min, max));
+        self
+    }
+
+    /// Depth to search this position to: the fixed depth, or with adaptive
+    /// depth the deepest one in range whose estimated tree, using the average
+    /// of both sides' move counts as the branching factor, fits the node budget.
+    fn search_depth(&self, game: &Quoridor, own_moves: usize) -> usize {
+        let Some((min, max)) = self.adaptive_depth else { return self.depth; };
+        let opponent_moves = game.all_legal_moves(game.active_player.opponent()).len();
+        let branching = ((own_moves + opponent_moves) as f64 / 2.0).max(2.0);
+        let mut depth = min;
+        while depth < max && branching.powi(depth as i32 + 1) <= ADAPTIVE_NODE_BUDGET {
+            depth += 1;
+        }
+        depth
+    }
 
-         // f3: Max-player's (P1) moves to next column (inverted for higher score = better)
-         let p1_moves_next = game.moves_to_next_row(Player::Player1) as f64;
-         let f3_p1_attack = if p1_moves_next == 0.0 { 100.0 } else { 1.0 / (p1_moves_next + 0.1) }; // Avoid div by zero
+    /// Number of nodes visited by the most recent `choose_move` search.
+    pub fn nodes_searched(&self) -> usize {
+        self.nodes_searched
+    }
+
+    /// A fresh searcher with the same evaluation and move-ordering settings but
+    /// no opening book, so analysis can search without disturbing `self`.
+    pub(crate) fn analysis_copy(&self, depth: usize) -> MinimaxStrategy {
+        MinimaxStrategy::new("", Vec::new(), depth.max(1))
+            .with_move_ordering(self.move_ordering)
+            .with_eval_weights(self.weights)
+    }
+
+    /// Iterative deepening from depth 1 to `max_depth` with no time limit.
+    /// Returns the best move and its score (from the side to move's perspective)
+    /// after each depth; entry `i` is depth `i + 1`. Ordering tables carry over
+    /// between depths, as in a time-limited search.
+    pub(crate) fn deepening_scores(&mut self, game: &Quoridor, max_depth: usize) -> Vec<(Option<String>, f64)> {
+        let all_moves = game.all_legal_moves(game.active_player);
+        if all_moves.is_empty() { return Vec::new(); }
+
+        self.depth = self.depth.max(max_depth);
+        self.reset_search_state();
+        let mut previous_score = None;
+        (1..=max_depth)
+            .map(|depth| {
+                let (best_move, score, _) = self.search_iteration(game, &all_moves, depth, previous_score, &Deadline::none());
+                previous_score = Some(score);
+                (best_move, score)
+            })
+            .collect()
+    }
+
+    /// The expected line of play from `game`, at most `depth` plies long: the
+    /// best move searched to `depth`, then the best reply searched one ply
+    /// shallower, and so on. Immediate wins are taken as in `choose_move`, and
+    /// the line stops once the game is over or the side to move has no moves.
+    pub(crate) fn principal_variation(&mut self, game: &Quoridor, depth: usize) -> Vec<String> {
+        self.depth = self.depth.max(depth);
+        let mut line = Vec::new();
+        let mut position = game.clone();
+        for remaining in (1..=depth).rev() {
+            if position.winner().is_some() { break; }
+            let all_moves = position.all_legal_moves(position.active_player);
+            let best_move = match all_moves.iter().find(|move_str| position.win_check(move_str)) {
+                Some(winning_move) => Some(winning_move.clone()),
+                None => {
+                    self.reset_search_state();
+                    // A lost position scores every move -inf; fall back as choose_move does
+                    self.search_root(&position, &all_moves, remaining, &Deadline::none()).0
+                        .or_else(|| all_moves.first().cloned())
+                }
+            };
+            let Some(best_move) = best_move else { break; };
+
+            let moved = position.apply_move(&best_move, false);
+            if !moved { break; }
+            line.push(best_move);
+        }
+        line
+    }
+
+    /// Clears the per-search ordering tables and node counter.
+    fn reset_search_state(&mut self) {
+        self.killer_moves = vec![[None, None]; self.depth + 1];
+        self.history.clear();
+        self.nodes_searched = 0;
+    }
+
+    /// Orders moves for search: killer moves at this depth first, then by
+    /// history score. The sort is stable, so ties keep generation order.
+    fn order_moves(&self, moves: &mut [String], depth: usize) {
+        if !self.move_ordering { return; }
+        let killers = self.killer_moves.get(depth);
+        moves.sort_by_cached_key(|move_str| {
+            let killer_rank = match killers {
+                Some([Some(k0), _]) if k0 == move_str => 0,
+                Some([_, Some(k1)]) if k1 == move_str => 1,
+                _ => 2,
+            };
+            (killer_rank, Reverse(self.history.get(move_str).copied().unwrap_or(0)))
+        });
+    }
+
+    /// Records a move that caused a beta (or alpha) cutoff at this depth.
+    fn record_cutoff(&mut self, move_str: &str, depth: usize) {
+        if !self.move_ordering { return; }
+        if let Some(slots) = self.killer_moves.get_mut(depth) {
+            if slots[0].as_deref() != Some(move_str) {
+                slots[1] = slots[0].take();
+                slots[0] = Some(move_str.to_string());
+            }
+        }
+        *self.history.entry(move_str.to_string()).or_insert(0) += (depth * depth) as i32;
+    }
 
-         // f4: Min-player's (P2) moves to next column (lower score = better for P1)
-          let p2_moves_next = game.moves_to_next_row(Player::Player2) as f64;
-          let f4_p2_defense = p2_moves_next; // Higher value means P2 is slower
+    /// Limits each move to `seconds` of thinking time.
+    /// The search then runs iterative deepening from depth 1 up to `depth`,
+    /// returning the best move of the deepest fully completed iteration.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_time_limit(mut self, seconds: f64) -> Self {
+        if seconds > 0.0 {
+            self.time_limit = Some(Duration::from_secs_f64(seconds));
+        }
+        self
+    }
 
-         // Weights from paper for C3 (f2 + f3 - f4 effectively, as lower f4 is better for Max)
-         const W2: f64 = 0.6001;
-         const W3: f64 = 14.45;
-         const W4: f64 = 6.52; // Weight for opponent's slowness
+    /// Time limits are not supported in WASM (no reliable timer); the search
+    /// keeps its fixed depth.
+    #[cfg(target_arch = "wasm32")]
+    pub fn with_time_limit(self, _seconds: f64) -> Self {
+        self
+    }
 
-          let score = W2 * f2_pos_diff + W3 * f3_p1_attack - W4 * f4_p2_defense;
+    /// Starts the clock for a new move, if a time limit is configured.
+    fn start_deadline(&self) -> Option<Deadline> {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(limit) = self.time_limit {
+            return Some(Deadline { at: Some(Instant::now() + limit) });
+        }
+        None
+    }
 
-        // Adjust score based on whose turn it *actually* is in the simulation tree
-         // If the player who needs to move *from* this state is P1, the score is as is.
-         // If the player who needs to move *from* this state is P2, we negate the score because
-         // P2 wants to minimize this P1-centric evaluation.
-         // Note: The alpha-beta function handles the maximizing/minimizing turns.
-         // The evaluation function itself should just return the static score of the position.
-         score
+    /// Static evaluation of a position, always from Player 1's perspective:
+    /// positive favours Player 1, negative favours Player 2, regardless of
+    /// `active_player`. The minimax recursion handles whose turn it is.
+    /// See `EvalWeights::evaluate` for the terms.
+    pub(crate) fn evaluate_state(&self, game: &Quoridor) -> f64 {
+        self.weights.evaluate(game)
     }
 
 
     /// Recursive minimax function with alpha-beta pruning.
     fn minimax_alphabeta(
-        &self,
+        &mut self,
         game: &Quoridor,
         depth: usize,
         mut alpha: f64, // Best score MAX player can guarantee
         mut beta: f64,  // Best score MIN player can guarantee
         is_maximizing_player: bool, // Is the current node for the player maximizing the score?
+        deadline: &Deadline,
     ) -> f64 {
+         self.nodes_searched += 1;
 
          // Check terminal conditions: depth limit or game over
           // Check if the *previous* move resulted in a win
@@ -89,19 +296,16 @@ impl MinimaxStrategy {
                     }
                }
           }
-          // Check depth limit
-          if depth == 0 {
+          // Check depth limit (an expired deadline cuts the search short; the
+          // root discards results of an interrupted iteration)
+          if depth == 0 || deadline.expired() {
                return self.evaluate_state(game);
           }
 
 
         let current_player = game.active_player;
-        let legal_pawn_moves = game.get_legal_moves(current_player);
-        let legal_wall_moves = game.get_legal_walls(current_player);
-        let all_moves: Vec<String> = legal_pawn_moves
-            .into_iter()
-            .chain(legal_wall_moves.into_iter())
-            .collect();
+        let mut all_moves = game.all_legal_moves(current_player);
+        self.order_moves(&mut all_moves, depth);
 
         if all_moves.is_empty() {
             // No moves possible, usually means the other player wins (or draw if reciprocal)
@@ -112,17 +316,14 @@ impl MinimaxStrategy {
             let mut max_eval = f64::NEG_INFINITY;
             for move_str in all_moves {
                 let mut next_game = game.clone();
-                let moved = if move_str.len() >= 3 {
-                    next_game.add_wall(&move_str, false, false)
-                } else {
-                    next_game.move_pawn(&move_str, false)
-                };
+                let moved = next_game.apply_move(&move_str, false);
                 if !moved { continue; } // Should not happen if get_legal_* works
 
-                let eval = self.minimax_alphabeta(&next_game, depth - 1, alpha, beta, false);
+                let eval = self.minimax_alphabeta(&next_game, depth - 1, alpha, beta, false, deadline);
                 max_eval = max_eval.max(eval);
                 alpha = alpha.max(eval); // Update alpha
                 if beta <= alpha {
+                    self.record_cutoff(&move_str, depth);
                     break; // Beta cutoff
                 }
             }
@@ -131,23 +332,116 @@ impl MinimaxStrategy {
             let mut min_eval = f64::INFINITY;
             for move_str in all_moves {
                 let mut next_game = game.clone();
-                 let moved = if move_str.len() >= 3 {
-                    next_game.add_wall(&move_str, false, false)
-                } else {
-                    next_game.move_pawn(&move_str, false)
-                };
+                 let moved = next_game.apply_move(&move_str, false);
                  if !moved { continue; }
 
-                let eval = self.minimax_alphabeta(&next_game, depth - 1, alpha, beta, true);
+                let eval = self.minimax_alphabeta(&next_game, depth - 1, alpha, beta, true, deadline);
                 min_eval = min_eval.min(eval);
                 beta = beta.min(eval); // Update beta
                 if beta <= alpha {
+                    self.record_cutoff(&move_str, depth);
                     break; // Alpha cutoff
                 }
             }
             min_eval
         }
     }
+
+    /// One iterative-deepening step: a root search to `depth`, inside the
+    /// aspiration window around `previous_score` when one is configured and the
+    /// previous score is finite, repeated with a full window if it fails high or low.
+    fn search_iteration(
+        &mut self,
+        game: &Quoridor,
+        all_moves: &[String],
+        depth: usize,
+        previous_score: Option<f64>,
+        deadline: &Deadline,
+    ) -> (Option<String>, f64, bool) {
+        if let (Some(half_width), Some(previous)) = (self.aspiration_window, previous_score.filter(|score| score.is_finite())) {
+            let (low, high) = (previous - half_width, previous + half_width);
+            let result = self.search_root_window(game, all_moves, depth, deadline, low, high);
+            let (_, score, completed) = result;
+            if !completed || (score > low && score < high) {
+                return result;
+            }
+        }
+        self.search_root(game, all_moves, depth, deadline)
+    }
+
+    /// Searches every root move to `depth` plies.
+    /// Returns the best move, its score from the moving player's perspective,
+    /// and whether the iteration completed before the deadline expired.
+    fn search_root(
+        &mut self,
+        game: &Quoridor,
+        all_moves: &[String],
+        depth: usize,
+        deadline: &Deadline,
+    ) -> (Option<String>, f64, bool) {
+        self.search_root_window(game, all_moves, depth, deadline, f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// `search_root` with every root move searched inside (`low`, `high`), in
+    /// the moving player's perspective. Scores inside the window are exact;
+    /// outside it they are only bounds, so callers must re-search on failure.
+    fn search_root_window(
+        &mut self,
+        game: &Quoridor,
+        all_moves: &[String],
+        depth: usize,
+        deadline: &Deadline,
+        low: f64,
+        high: f64,
+    ) -> (Option<String>, f64, bool) {
+        let mut best_move: Option<String> = None;
+        let mut best_score = f64::NEG_INFINITY; // Score from the root player's perspective
+        // evaluate_state scores for Player 1, so Player 1 maximizes and Player 2 minimizes
+        let root_maximizing = game.active_player == Player::Player1;
+        let (alpha, beta) = if root_maximizing { (low, high) } else { (-high, -low) };
+
+        // Moves that caused cutoffs in shallower iterations are tried first
+        let mut root_moves = all_moves.to_vec();
+        self.order_moves(&mut root_moves, depth);
+
+        // Iterate through possible first moves and evaluate them using minimax
+        for move_str in &root_moves {
+             let mut next_game = game.clone();
+             let moved = next_game.apply_move(move_str, false);
+              if !moved { continue; } // Skip if somehow illegal
+
+             // Call minimax for the opponent's turn
+             let eval = self.minimax_alphabeta(
+                 &next_game,
+                 depth - 1, // Decrease depth
+                 alpha,
+                 beta,
+                 !root_maximizing, // The opponent optimizes the other way
+                 deadline,
+             );
+             let score = if root_maximizing { eval } else { -eval };
+
+             if deadline.expired() {
+                 return (best_move, best_score, false);
+             }
+
+            // Ties go to the move generated first, so the choice does not depend
+            // on how the ordering tables happened to sort the root
+            if score > best_score || (score == best_score && generated_before(all_moves, move_str, best_move.as_deref())) {
+                best_score = score;
+                best_move = Some(move_str.clone());
+            }
+        }
+
+        (best_move, best_score, true)
+    }
+}
+
+/// True if `move_str` comes before `other` (when there is one) in `all_moves`.
+fn generated_before(all_moves: &[String], move_str: &str, other: Option<&str>) -> bool {
+    let Some(other) = other else { return false; };
+    let index = |target: &str| all_moves.iter().position(|m| m == target);
+    index(move_str) < index(other)
 }
 
 impl Strategy for MinimaxStrategy {
@@ -155,6 +449,35 @@ impl Strategy for MinimaxStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        let mut details = format!("depth: {}", self.depth);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(limit) = self.time_limit {
+            details += &format!(", time limit: {:.2?}", limit);
+        }
+        if let Some((min, max)) = self.adaptive_depth {
+            details += &format!(", adaptive depth: {}-{}", min, max);
+        }
+        if let Some(window) = self.aspiration_window {
+            details += &format!(", aspiration window: {}", window);
+        }
+        format!("{} ({}, move ordering: {}, weights: {})", self.name(), details, if self.move_ordering { "on" } else { "off" }, self.weights)
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+        self.reset_search_state();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -162,61 +485,241 @@ impl Strategy for MinimaxStrategy {
         }
 
         let current_player = game.active_player;
-        let legal_pawn_moves = game.get_legal_moves(current_player);
-        let legal_wall_moves = game.get_legal_walls(current_player);
+        let all_moves = game.all_legal_moves(current_player);
 
-         // Check for immediate wins
-         for move_str in &legal_pawn_moves {
+         // Check for immediate wins (win_check ignores wall moves)
+         for move_str in &all_moves {
              if game.win_check(move_str) {
                  return Some(move_str.clone());
              }
          }
 
-        let all_moves: Vec<String> = legal_pawn_moves
-            .into_iter()
-            .chain(legal_wall_moves.into_iter())
-            .collect();
-
         if all_moves.is_empty() {
             return None;
         }
 
+        self.reset_search_state();
         let mut best_move: Option<String> = None;
-        let mut best_score = f64::NEG_INFINITY; // Since the current player is maximizing
+        let max_depth = self.search_depth(game, all_moves.len());
 
-        // Iterate through possible first moves and evaluate them using minimax
-        for move_str in all_moves {
-             let mut next_game = game.clone();
-             let moved = if move_str.len() >= 3 {
-                 next_game.add_wall(&move_str, false, false) // Use internal move for simulation
-             } else {
-                 next_game.move_pawn(&move_str, false)
-             };
-              if !moved { continue; } // Skip if somehow illegal
-
-             // Call minimax for the opponent's turn (minimizing player)
-             let score = self.minimax_alphabeta(
-                 &next_game,
-                 self.depth - 1, // Decrease depth
-                 f64::NEG_INFINITY,
-                 f64::INFINITY,
-                 false, // The next turn is for the minimizing player
-             );
-
-            if score > best_score {
-                best_score = score;
-                best_move = Some(move_str);
+        match self.start_deadline() {
+            None => {
+                // Fixed-depth search
+                best_move = self.search_root(game, &all_moves, max_depth, &Deadline::none()).0;
+            }
+            Some(deadline) => {
+                // Iterative deepening: keep the result of the deepest completed iteration
+                let mut previous_score = None;
+                for depth in 1..=max_depth {
+                    let (move_at_depth, score, completed) = self.search_iteration(game, &all_moves, depth, previous_score, &deadline);
+                    previous_score = Some(score);
+                    // An interrupted iteration is only used if nothing better exists yet
+                    if completed || best_move.is_none() {
+                        best_move = move_at_depth;
+                    }
+                    if !completed || score == f64::INFINITY {
+                        break; // Out of time, or a forced win was found
+                    }
+                }
             }
         }
 
         // Fallback if no move could be evaluated (shouldn't happen if all_moves is not empty)
-        if best_move.is_none() && !game.get_legal_moves(current_player).is_empty() {
-            best_move = Some(game.get_legal_moves(current_player)[0].clone())
-        } else if best_move.is_none() && !game.get_legal_walls(current_player).is_empty() {
-             best_move = Some(game.get_legal_walls(current_player)[0].clone())
+        if best_move.is_none() {
+            best_move = all_moves.first().cloned();
         }
 
 
         best_move
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_time_limited_search_returns_legal_move_within_budget() {
+        let game = Quoridor::new(9, 10, None);
+        // Depth 6 would take far longer than the budget without the deadline
+        let mut strategy = MinimaxStrategy::new("", Vec::new(), 6).with_time_limit(0.2);
+
+        let start = Instant::now();
+        let chosen = strategy.choose_move(&game).expect("a move should be found");
+        assert!(start.elapsed() < Duration::from_secs(2), "search overran: {:?}", start.elapsed());
+
+        let mut legal = game.get_legal_moves(game.active_player);
+        legal.extend(game.get_legal_walls(game.active_player));
+        assert!(legal.contains(&chosen));
+    }
+
+    #[test]
+    fn test_time_limited_search_finds_immediate_win() {
+        // P1 on e8, one step from the goal row
+        let game = Quoridor::new(9, 10, Some(" / / e8 a1 / 10 10 / 1"));
+        let mut strategy = MinimaxStrategy::new("", Vec::new(), 4).with_time_limit(0.5);
+        assert_eq!(strategy.choose_move(&game), Some("e9".to_string()));
+    }
+
+    /// Mirrors a position left-right by rebuilding it from a state string.
+    fn mirror_left_right(game: &Quoridor) -> Quoridor {
+        let n = game.size;
+        let flip = |(r, c): (usize, usize), offset: usize| game.coord_to_algebraic((r, n - offset - c));
+        let walls = |positions: &std::collections::HashSet<(usize, usize)>| positions.iter().map(|&pos| flip(pos, 2)).collect::<String>();
+        let state = format!(
+            "{} / {} / {} {} / {} {} / {}",
+            walls(&game.hwall_positions), walls(&game.vwall_positions),
+            flip(game.pawn_positions[&Player::Player1], 1), flip(game.pawn_positions[&Player::Player2], 1),
+            game.walls_available[&Player::Player1], game.walls_available[&Player::Player2],
+            game.active_player.number(),
+        );
+        Quoridor::new(n, game.walls, Some(&state))
+    }
+
+    fn sample_positions() -> Vec<Quoridor> {
+        vec![
+            Quoridor::new(9, 10, None),
+            Quoridor::new(9, 10, Some("e3c7 / b5 / e4 d6 / 8 9 / 2")),
+            Quoridor::new(9, 10, Some("a2 / f4g7 / b3 h8 / 9 8 / 1")),
+        ]
+    }
+
+    #[test]
+    fn test_evaluation_is_mirror_symmetric() {
+        let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+        for game in sample_positions() {
+            let mirrored = mirror_left_right(&game);
+            assert!((strategy.evaluate_state(&game) - strategy.evaluate_state(&mirrored)).abs() < 1e-9, "{}", game.state_string);
+        }
+    }
+
+    #[test]
+    fn test_swapping_roles_negates_evaluation() {
+        let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+        for game in sample_positions() {
+            let swapped = game.flipped();
+            assert!((strategy.evaluate_state(&game) + strategy.evaluate_state(&swapped)).abs() < 1e-9, "{}", game.state_string);
+        }
+    }
+
+    #[test]
+    fn test_evaluation_ignores_side_to_move() {
+        let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+        let p1_to_move = Quoridor::new(9, 10, Some("e3 / b5 / e4 d6 / 9 10 / 1"));
+        let p2_to_move = Quoridor::new(9, 10, Some("e3 / b5 / e4 d6 / 9 10 / 2"));
+        assert_eq!(strategy.evaluate_state(&p1_to_move), strategy.evaluate_state(&p2_to_move));
+    }
+
+    #[test]
+    fn test_player2_searches_for_its_own_benefit() {
+        // P2 on e3 with no walls left: stepping to e2 is clearly best for P2
+        let game = Quoridor::new(9, 0, Some(" / / a5 e3 / 0 0 / 2"));
+        let mut strategy = MinimaxStrategy::new("", Vec::new(), 2);
+        assert_eq!(strategy.choose_move(&game), Some("e2".to_string()));
+    }
+
+    /// Total nodes searched over a small benchmark suite of positions.
+    fn benchmark_nodes(move_ordering: bool) -> usize {
+        let suite = [
+            (5, 3, " / / c1 c5 / 3 3 / 1"),
+            (5, 3, "c3 / b2 / c2 c4 / 2 2 / 2"),
+            (5, 3, "b4 / c3 / d2 b4 / 2 3 / 1"),
+            (5, 3, " / a3 / c3 c4 / 3 2 / 2"),
+        ];
+        suite.iter().map(|&(size, walls, state)| {
+            let game = Quoridor::new(size, walls, Some(state));
+            let mut strategy = MinimaxStrategy::new("", Vec::new(), 3).with_move_ordering(move_ordering);
+            strategy.choose_move(&game);
+            strategy.nodes_searched()
+        }).sum()
+    }
+
+    #[test]
+    fn test_move_ordering_reduces_nodes_searched() {
+        let plain = benchmark_nodes(false);
+        let ordered = benchmark_nodes(true);
+        assert!(ordered < plain, "ordered {} vs plain {}", ordered, plain);
+    }
+
+    /// Midgame positions with walls on the board and in hand on both sides.
+    fn midgame_positions() -> Vec<Quoridor> {
+        [
+            "c3 / b2 / c2 c4 / 2 2 / 2",
+            "b4 / c3 / d2 b4 / 2 3 / 1",
+            " / a3 / c3 c4 / 3 2 / 2",
+            "a2c4 / d2 / b3 d4 / 1 2 / 1",
+        ]
+        .iter()
+        .map(|state| Quoridor::new(5, 3, Some(state)))
+        .collect()
+    }
+
+    /// Best move and score per depth, as returned by `deepening_scores`.
+    type DepthScores = Vec<(Option<String>, f64)>;
+
+    /// Per-depth results and total nodes of deepening runs to `depth` over `midgame_positions`.
+    fn deepening_with(aspiration: Option<f64>, depth: usize) -> (Vec<DepthScores>, usize) {
+        let mut nodes = 0;
+        let results = midgame_positions().iter().map(|game| {
+            let mut strategy = MinimaxStrategy::new("", Vec::new(), depth);
+            if let Some(half_width) = aspiration {
+                strategy = strategy.with_aspiration_window(half_width);
+            }
+            let scores = strategy.deepening_scores(game, depth);
+            nodes += strategy.nodes_searched();
+            scores
+        }).collect();
+        (results, nodes)
+    }
+
+    #[test]
+    fn test_aspiration_windows_keep_moves_and_scores() {
+        let (plain, _) = deepening_with(None, 3);
+        for half_width in [0.5, 2.0, 10.0] {
+            let (aspirated, _) = deepening_with(Some(half_width), 3);
+            assert_eq!(aspirated, plain, "half-width {}", half_width);
+        }
+    }
+
+    /// Node counts with and without aspiration windows.
+    /// Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_aspiration_window_nodes() {
+        let (_, plain) = deepening_with(None, 4);
+        for half_width in [0.5, 2.0, 10.0] {
+            let (_, aspirated) = deepening_with(Some(half_width), 4);
+            println!("half-width {}: {} nodes vs {} without aspiration", half_width, aspirated, plain);
+        }
+    }
+
+    #[test]
+    fn test_preset_sets_weights_and_name() {
+        let strategy = MinimaxStrategy::new("", Vec::new(), 2).with_preset("C1");
+        assert_eq!(strategy.name(), "Minimax2-C1");
+        assert_eq!(strategy.weights, EvalWeights::c1());
+    }
+
+    #[test]
+    fn test_adaptive_depth_follows_move_counts() {
+        let strategy = MinimaxStrategy::new("", Vec::new(), 2).with_adaptive_depth(1, 6);
+        assert_eq!(strategy.name(), "Minimax1-6");
+
+        // Opening: over a hundred wall placements each, so only a shallow search fits
+        let opening = Quoridor::standard();
+        let opening_moves = opening.all_legal_moves(Player::Player1).len();
+        assert_eq!(strategy.search_depth(&opening, opening_moves), 2);
+
+        // Pawn race with no walls left: a handful of moves each, so search to the cap
+        let race = Quoridor::new(9, 10, Some(" / / e5 c3 / 0 0 / 1"));
+        let race_moves = race.all_legal_moves(Player::Player1).len();
+        assert_eq!(strategy.search_depth(&race, race_moves), 6);
+
+        // The bounds clamp the choice either way
+        let narrow = MinimaxStrategy::new("", Vec::new(), 2).with_adaptive_depth(3, 4);
+        assert_eq!(narrow.search_depth(&opening, opening_moves), 3);
+        assert_eq!(narrow.search_depth(&race, race_moves), 4);
+        assert_eq!(MinimaxStrategy::new("", Vec::new(), 2).search_depth(&race, race_moves), 2);
+    }
+}
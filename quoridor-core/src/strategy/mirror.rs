@@ -3,7 +3,7 @@
 use crate::game::Quoridor;
 use crate::player::Player;
 use crate::types::Coord;
-use crate::strategy::base::QuoridorStrategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::{Strategy, AdaptiveStrategy}; // Using Adaptive as a fallback
 use crate::utils::abs_diff;
 use std::collections::HashSet;
@@ -32,16 +32,16 @@ impl MirrorStrategy {
         self.board_center.unwrap()
     }
 
-    /// Calculates the mirrored position relative to the board center.
+    /// Calculates the mirrored pawn square relative to the board center.
     fn calculate_mirrored_coord(&mut self, game: &Quoridor, coord: Coord) -> Coord {
         let center = self.get_board_center(game);
         // Mirror calculation: 2 * center - coordinate
         let mirrored_row = 2.0 * center.0 - coord.0 as f64;
         let mirrored_col = 2.0 * center.1 - coord.1 as f64;
 
-        // Clamp to board boundaries and round
-        let row = (mirrored_row.round() as i32).clamp(1, (game.size) as i32) as usize;
-        let col = (mirrored_col.round() as i32).clamp(0, (game.size) as i32) as usize;
+        // Clamp to board boundaries and round (walls go through `Quoridor::mirror_wall`)
+        let row = (mirrored_row.round() as i32).clamp(0, game.size as i32 - 1) as usize;
+        let col = (mirrored_col.round() as i32).clamp(0, game.size as i32 - 1) as usize;
         (row, col)
     }
 
@@ -87,36 +87,17 @@ impl MirrorStrategy {
         let legal_walls_set: HashSet<String> = legal_walls.iter().cloned().collect();
 
         // Combine all placed walls for easy lookup
-        let mut placed_walls = HashSet::new();
-        for &h_wall_coord in &game.hwall_positions {
-             placed_walls.insert(format!("{}h", game.coord_to_algebraic(h_wall_coord)));
-        }
-         for &v_wall_coord in &game.vwall_positions {
-              placed_walls.insert(format!("{}v", game.coord_to_algebraic(v_wall_coord)));
-         }
-
+        let placed_walls: HashSet<String> = game.placed_wall_moves().into_iter().collect();
 
-        // Check all horizontal walls
-        for &opponent_h_wall_coord in &game.hwall_positions {
-            // Adjust hwallcoord since placed in bottom left
-            let opponent_h_wall_coord = (opponent_h_wall_coord.0 - 1, opponent_h_wall_coord.1 + 1);
-            let mirrored_coord = self.calculate_mirrored_coord(game, opponent_h_wall_coord);
-            let mirrored_wall_move = format!("{}h", game.coord_to_algebraic(mirrored_coord));
+        // Check all walls, horizontal ones first
+        for (opponent_wall_coord, orientation) in game.placed_walls() {
+            let Some((mirrored_coord, orientation)) = game.mirror_wall(opponent_wall_coord, orientation) else { continue; };
+            let mirrored_wall_move = format!("{}{}", game.coord_to_algebraic(mirrored_coord), orientation);
             if legal_walls_set.contains(&mirrored_wall_move) && !placed_walls.contains(&mirrored_wall_move) {
                 return Some(mirrored_wall_move);
             }
         }
 
-        // Check all vertical walls
-        for &opponent_v_wall_coord in &game.vwall_positions {
-            let opponent_v_wall_coord = (opponent_v_wall_coord.0 - 1, opponent_v_wall_coord.1 + 1);
-            let mirrored_coord = self.calculate_mirrored_coord(game, opponent_v_wall_coord);
-            let mirrored_wall_move = format!("{}v", game.coord_to_algebraic(mirrored_coord));
-             if legal_walls_set.contains(&mirrored_wall_move) && !placed_walls.contains(&mirrored_wall_move) {
-                 return Some(mirrored_wall_move);
-             }
-        }
-
         None // No suitable mirror wall placement found
     }
 }
@@ -126,6 +107,24 @@ impl Strategy for MirrorStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        format!("{} (backup: {})", self.name(), self.backup_strategy.describe())
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+        self.backup_strategy.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
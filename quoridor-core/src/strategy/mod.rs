@@ -4,7 +4,47 @@
 
 // Use super/crate paths if needed to access core types
 use crate::game::Quoridor;
-// Potentially use crate::Player;
+use crate::player::Player;
+
+/// How far ahead (in `EvalWeights::default()` terms) the first move must
+/// leave its player before the default `Strategy::should_swap` takes it over.
+/// An ordinary first step is worth about 0.6 (one square of the race, `w2`),
+/// and the side to move gets that tempo back, so that alone is no reason to swap.
+pub const PIE_RULE_MARGIN: f64 = 1.0;
+
+// --- Move Choice Result ---
+
+/// Outcome of asking a strategy for a move, distinguishing a resignation
+/// from a position where no legal move exists at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChooseResult {
+    /// The chosen move in algebraic notation.
+    Move(String),
+    /// The strategy declined to move even though legal moves exist.
+    Resign,
+    /// The active player has no legal move.
+    NoMove,
+}
+
+impl ChooseResult {
+    /// Classifies a `choose_move` result: `None` counts as a resignation if the
+    /// active player still had legal moves, and as `NoMove` otherwise.
+    pub fn from_choice(game: &Quoridor, choice: Option<String>) -> Self {
+        match choice {
+            Some(move_str) => ChooseResult::Move(move_str),
+            None if game.all_legal_moves(game.active_player).is_empty() => ChooseResult::NoMove,
+            None => ChooseResult::Resign,
+        }
+    }
+
+    /// Returns the move, if one was chosen.
+    pub fn into_move(self) -> Option<String> {
+        match self {
+            ChooseResult::Move(move_str) => Some(move_str),
+            ChooseResult::Resign | ChooseResult::NoMove => None,
+        }
+    }
+}
 
 // --- Strategy Trait ---
 
@@ -13,15 +53,153 @@ pub trait Strategy: Send + Sync { // Add Send + Sync for potential parallel exec
     /// Returns the name of the strategy (e.g., "Random", "Minimax3").
     fn name(&self) -> String;
 
+    /// The name together with the settings that shape play (depth,
+    /// simulations, weights, ...), e.g. "Minimax2 (depth: 2, ...)", for logs
+    /// where `name` would be ambiguous. Defaults to `name`.
+    fn describe(&self) -> String {
+        self.name()
+    }
+
     /// Chooses the next move for the active player in the given game state.
     /// Returns the chosen move in algebraic notation (e.g., "e2", "a3h") or None if no move is possible.
+    /// The built-in strategies only return None then: when their own logic finds nothing
+    /// they fall back to any legal move (`QuoridorStrategy::last_resort_move`).
     /// Takes `&mut self` to allow strategies to maintain internal state (e.g., opening move counters, MCTS tree).
     fn choose_move(&mut self, game: &Quoridor) -> Option<String>;
 
-    // Optional: Add a method to reset strategy state if needed between games
-    // fn reset(&mut self) {}
+    /// Like `choose_move`, but reports *why* no move was returned.
+    /// The default implementation classifies a `None` from `choose_move`.
+    fn choose(&mut self, game: &Quoridor) -> ChooseResult {
+        let choice = self.choose_move(game);
+        ChooseResult::from_choice(game, choice)
+    }
+
+    /// Pie rule: called for the second player right after the first move,
+    /// returns true to take over the side that made it (the other strategy
+    /// then plays on from the second player's side, whose turn it is).
+    /// By default swaps when the default static evaluation puts the player
+    /// who just moved more than `PIE_RULE_MARGIN` ahead.
+    fn should_swap(&mut self, game: &Quoridor) -> bool {
+        let p1_score = EvalWeights::default().evaluate(game);
+        let mover_score = match game.active_player.opponent() {
+            Player::Player1 => p1_score,
+            Player::Player2 => -p1_score,
+        };
+        mover_score > PIE_RULE_MARGIN
+    }
+
+    /// Changes a tuning parameter at runtime, by name, without rebuilding the
+    /// strategy. Returns false if this strategy has no parameter of that name;
+    /// by default there are none. See the implementing strategies for their names.
+    fn set_param(&mut self, _name: &str, _value: f64) -> bool {
+        false
+    }
+
+    /// Clears per-game state (e.g. the position in the opening line) so the
+    /// same strategy can start a new game. Does nothing by default.
+    fn reset(&mut self) {}
+
+    /// Sets what happens when the next move of the opening line is illegal
+    /// (see `OpeningDeviation`). Returns false if the strategy follows no
+    /// opening line, the default.
+    fn set_opening_mode(&mut self, _mode: OpeningDeviation) -> bool {
+        false
+    }
+
+    /// The illegal opening move and its index in the line, once the strategy
+    /// has left its opening in `OpeningDeviation::Report` mode. None by default.
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        None
+    }
+
+    /// Serializes the strategy's internal state, so a paused AI can be resumed
+    /// later (possibly in a new instance built with the same settings) with
+    /// `load_state`. None if the strategy doesn't support it, the default.
+    fn save_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restores state from `save_state`. Returns false if unsupported (the
+    /// default) or if the bytes don't fit this strategy.
+    fn load_state(&mut self, _state: &[u8]) -> bool {
+        false
+    }
+}
+
+/// Boxed strategies (e.g. from a factory) are strategies too, so generic
+/// decorators such as `TimeLimited` can wrap them.
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    fn name(&self) -> String {
+        (**self).name()
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+
+    fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+        (**self).choose_move(game)
+    }
+
+    fn choose(&mut self, game: &Quoridor) -> ChooseResult {
+        (**self).choose(game)
+    }
+
+    fn should_swap(&mut self, game: &Quoridor) -> bool {
+        (**self).should_swap(game)
+    }
+
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        (**self).set_param(name, value)
+    }
+
+    fn reset(&mut self) {
+        (**self).reset()
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        (**self).set_opening_mode(mode)
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        (**self).opening_deviation()
+    }
+
+    fn save_state(&self) -> Option<Vec<u8>> {
+        (**self).save_state()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> bool {
+        (**self).load_state(state)
+    }
 }
 
+/// Test strategy that always gives up, shared with the CLI's tournament tests.
+#[cfg(any(test, feature = "testing"))]
+pub struct Resigner;
+
+#[cfg(any(test, feature = "testing"))]
+impl Strategy for Resigner {
+    fn name(&self) -> String {
+        "Resigner".to_string()
+    }
+
+    fn choose_move(&mut self, _game: &Quoridor) -> Option<String> {
+        None
+    }
+}
+
+// --- Shared Builder Helpers ---
+
+/// Looks up a named heuristic preset ("C1", "C2" or "C3", see `EvalWeights::preset`)
+/// for a strategy's `with_preset` builder, appending it to the strategy's `name`,
+/// e.g. "Minimax2" becomes "Minimax2-C1". Panics on an unknown preset.
+pub(crate) fn preset_weights(name: &mut String, preset: &str) -> EvalWeights {
+    let Some(weights) = EvalWeights::preset(preset) else { panic!("Unknown heuristic preset '{}'", preset); };
+    name.push('-');
+    name.push_str(preset);
+    weights
+}
 
 // --- Module Declarations ---
 // Declare each strategy implementation file as a submodule.
@@ -29,22 +207,108 @@ pub mod adaptive;
 pub mod balanced;
 pub mod base; // Contains QuoridorStrategy base struct
 pub mod defensive;
+pub mod evaluation; // Shared heuristic weights (EvalWeights)
+pub mod greedy_block;
+pub mod logging; // LoggingStrategy decorator (decision log)
 pub mod mcts;
 pub mod minimax;
 pub mod mirror;
 pub mod random;
 pub mod shortest_path;
 pub mod simulated_annealing;
+pub mod time_limited; // TimeLimited decorator (per-move timing)
 
 // --- Public Exports ---
 // Re-export the structs from the submodules so they can be easily used.
 pub use adaptive::AdaptiveStrategy;
 pub use balanced::BalancedStrategy;
-pub use base::QuoridorStrategy; // Base struct might be useful externally too
+pub use base::{OpeningDeviation, QuoridorStrategy}; // Base struct might be useful externally too
 pub use defensive::DefensiveStrategy;
+pub use evaluation::EvalWeights;
+pub use greedy_block::GreedyBlockStrategy;
+pub use logging::{LogSink, LoggingStrategy};
 pub use mcts::MCTSStrategy;
 pub use minimax::MinimaxStrategy;
 pub use mirror::MirrorStrategy;
 pub use random::RandomStrategy;
 pub use shortest_path::ShortestPathStrategy;
-pub use simulated_annealing::SimulatedAnnealingStrategy;
\ No newline at end of file
+pub use simulated_annealing::{CoolingSchedule, SimulatedAnnealingStrategy};
+pub use time_limited::TimeLimited;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::RuleSet;
+
+    #[test]
+    fn test_none_with_legal_moves_is_resignation() {
+        let game = Quoridor::new(9, 10, None);
+        assert_eq!(Resigner.choose(&game), ChooseResult::Resign);
+    }
+
+    #[test]
+    fn test_none_without_legal_moves_is_no_move() {
+        // P1 on b1 walled in on three sides, no walls left
+        let game = Quoridor::new_unchecked(3, 0, "a1 / a1b1 / b1 b3 / 0 0 / 1");
+        assert!(game.all_legal_moves(game.active_player).is_empty());
+        assert_eq!(Resigner.choose(&game), ChooseResult::NoMove);
+        assert_eq!(RandomStrategy::new("", Vec::new()).choose(&game), ChooseResult::NoMove);
+    }
+
+    /// One of every strategy, configured to search as little as possible.
+    fn every_strategy() -> Vec<Box<dyn Strategy>> {
+        vec![
+            Box::new(RandomStrategy::new("", Vec::new())),
+            Box::new(ShortestPathStrategy::new("", Vec::new())),
+            Box::new(DefensiveStrategy::new("", Vec::new(), 1.0, 1.0)),
+            Box::new(GreedyBlockStrategy::new("", Vec::new())),
+            Box::new(BalancedStrategy::new("", Vec::new(), 0.5)),
+            Box::new(AdaptiveStrategy::new("", Vec::new())),
+            Box::new(MinimaxStrategy::new("", Vec::new(), 1)),
+            Box::new(MCTSStrategy::new("", Vec::new(), 20)),
+            Box::new(MirrorStrategy::new("", Vec::new())),
+            Box::new(SimulatedAnnealingStrategy::new("", Vec::new(), 0.02)),
+        ]
+    }
+
+    #[test]
+    fn test_strategies_move_whenever_a_move_exists() {
+        let no_jumps = RuleSet { allow_jumps: false };
+        let mut positions = vec![
+            // Without jumps P1 on a1 is stuck behind P2 and a1v: walls are the only moves
+            Quoridor::new(5, 3, Some(" / a1 / a1 a2 / 2 3 / 1")).with_rules(no_jumps),
+            // The same with P1 boxed in on three sides and no jump over P2
+            Quoridor::new(5, 3, Some("c2 / b1 / c1 c2 / 2 2 / 1")).with_rules(no_jumps),
+        ];
+        let record = Quoridor::play_random_game(5);
+        positions.extend((0..record.len()).step_by(40).map(|ply| record.position_at(ply)));
+
+        for game in &positions {
+            if game.all_legal_moves(game.active_player).is_empty() { continue; }
+            for mut strategy in every_strategy() {
+                let choice = strategy.choose_move(game);
+                assert!(choice.is_some(), "{} gave up with legal moves in {}", strategy.name(), game.state_string);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chosen_move_is_passed_through() {
+        let game = Quoridor::new(9, 10, None);
+        let result = ShortestPathStrategy::new("", Vec::new()).choose(&game);
+        assert_eq!(result, ChooseResult::Move("e2".to_string()));
+        assert_eq!(result.into_move(), Some("e2".to_string()));
+    }
+
+    #[test]
+    fn test_default_should_swap_needs_a_clear_lead() {
+        let mut strategy = ShortestPathStrategy::new("", Vec::new());
+        // An ordinary first step is not worth taking over
+        let mut game = Quoridor::new(9, 10, None);
+        assert!(game.apply_move("e2", true));
+        assert!(!strategy.should_swap(&game));
+        // P1 three rows ahead of P2 in the race is
+        let game = Quoridor::new(5, 3, Some(" / / c4 c5 / 3 3 / 2"));
+        assert!(strategy.should_swap(&game));
+    }
+}
@@ -1,20 +1,55 @@
 // --- File: quoridor-project/quoridor-core/src/strategy/random.rs ---
 
 use crate::game::Quoridor;
-use crate::strategy::base::QuoridorStrategy;
+use crate::player::Player;
+use crate::types::Coord;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::Strategy;
 use rand::prelude::*;
+use rand::rngs::StdRng;
 
 pub struct RandomStrategy {
     base: QuoridorStrategy,
+    // Probability that a wall move is drawn from walls touching a pawn's square
+    // instead of from all legal walls (0.0 = uniform, the default)
+    wall_bias: f64,
+    seed: Option<u64>, // Set by `with_seed`; None draws from OS entropy
+    rng: StdRng,
 }
 
 impl RandomStrategy {
     pub fn new(opening_name: &str, opening_moves: Vec<String>) -> Self {
         RandomStrategy {
             base: QuoridorStrategy::new("Random", opening_name, opening_moves),
+            wall_bias: 0.0,
+            seed: None,
+            rng: StdRng::from_entropy(),
         }
     }
+
+    /// Draws moves from a generator seeded with `seed`. Legal moves are listed
+    /// in a fixed order, so the same seed from the same position always plays
+    /// the same moves; `reset` rewinds to the start of the sequence.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Biases wall choices toward walls next to either pawn: whenever a wall is
+    /// picked, with probability `prob` it is redrawn from the walls touching a
+    /// pawn's square. Gives a more realistic "casual player" baseline.
+    pub fn with_biased_walls(mut self, prob: f64) -> Self {
+        self.wall_bias = prob.clamp(0.0, 1.0);
+        self
+    }
+}
+
+/// Whether the wall at `wall_coord` runs along an edge of the square `pawn`.
+/// A wall referenced at (r, c) borders squares in rows r-1..=r and cols c..=c+1.
+fn wall_touches_square(wall_coord: Coord, pawn: Coord) -> bool {
+    let (r, c) = wall_coord;
+    (pawn.0 + 1 == r || pawn.0 == r) && (pawn.1 == c || pawn.1 == c + 1)
 }
 
 impl Strategy for RandomStrategy {
@@ -22,6 +57,27 @@ impl Strategy for RandomStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        let seed = self.seed.map_or("none".to_string(), |seed| seed.to_string());
+        format!("{} (wall bias: {}, seed: {})", self.name(), self.wall_bias, seed)
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+        if let Some(seed) = self.seed {
+            self.rng = StdRng::seed_from_u64(seed);
+        }
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -29,20 +85,79 @@ impl Strategy for RandomStrategy {
         }
 
         // Otherwise choose randomly from all legal moves
-        let legal_pawn_moves = game.get_legal_moves(game.active_player);
-        let legal_wall_moves = game.get_legal_walls(game.active_player); // Checks availability internally
-
-        let all_legal_moves: Vec<String> = legal_pawn_moves
-            .into_iter()
-            .chain(legal_wall_moves.into_iter())
-            .collect();
+        let all_legal_moves = game.all_legal_moves(game.active_player); // Wall availability checked internally
 
         if all_legal_moves.is_empty() {
             None // No legal moves available
         } else {
-            let mut rng = thread_rng();
+            let rng = &mut self.rng;
             // Select a random move from the combined list
-            all_legal_moves.choose(&mut rng).cloned()
+            let chosen = all_legal_moves.choose(rng).cloned();
+
+            // Optionally swap a far-away wall for one next to a pawn
+            let is_wall = chosen.as_ref().is_some_and(|m| m.ends_with('h') || m.ends_with('v'));
+            if is_wall && self.wall_bias > 0.0 && rng.gen::<f64>() < self.wall_bias {
+                let pawns = [game.pawn_positions[&Player::Player1], game.pawn_positions[&Player::Player2]];
+                let near_walls: Vec<(Coord, char)> = game.get_legal_walls_coords(game.active_player)
+                    .into_iter()
+                    .filter(|(coord, _)| pawns.iter().any(|&pawn| wall_touches_square(*coord, pawn)))
+                    .collect();
+                if let Some((coord, orientation)) = near_walls.choose(rng) {
+                    return Some(format!("{}{}", game.coord_to_algebraic(*coord), orientation));
+                }
+            }
+            chosen
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays `p1` against `p2` from the standard start, returning the moves.
+    fn play_game(p1: &mut RandomStrategy, p2: &mut RandomStrategy) -> Vec<String> {
+        let mut game = Quoridor::standard();
+        let mut moves = Vec::new();
+        while game.winner().is_none() && moves.len() < 1000 {
+            let strategy = if game.active_player == Player::Player1 { &mut *p1 } else { &mut *p2 };
+            let move_str = strategy.choose_move(&game).unwrap();
+            assert!(game.apply_move(&move_str, true), "{} is illegal", move_str);
+            moves.push(move_str);
         }
+        moves
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_seeded_games_are_reproducible() {
+        let seeded = |seed| RandomStrategy::new("", Vec::new()).with_seed(seed).with_biased_walls(0.5);
+        let (mut p1, mut p2) = (seeded(1), seeded(2));
+        let first = play_game(&mut p1, &mut p2);
+        assert_eq!(play_game(&mut seeded(1), &mut seeded(2)), first);
+
+        // Reset strategies replay the game, another seed plays a different one
+        p1.reset();
+        p2.reset();
+        assert_eq!(play_game(&mut p1, &mut p2), first);
+        assert_ne!(play_game(&mut seeded(3), &mut seeded(2)), first);
+    }
+
+    #[test]
+    fn test_biased_walls_stay_near_pawns() {
+        let game = Quoridor::new(9, 10, None);
+        let pawns = [game.pawn_positions[&Player::Player1], game.pawn_positions[&Player::Player2]];
+        let mut strategy = RandomStrategy::new("", Vec::new()).with_biased_walls(1.0);
+
+        let mut walls_seen = 0;
+        for _ in 0..50 {
+            let Some(chosen) = strategy.choose_move(&game) else { panic!("no move chosen"); };
+            if chosen.ends_with('h') || chosen.ends_with('v') {
+                walls_seen += 1;
+                let coord = game.algebraic_to_coord(&chosen[..chosen.len() - 1]);
+                assert!(pawns.iter().any(|&pawn| wall_touches_square(coord, pawn)), "{} is far from both pawns", chosen);
+            }
+        }
+        // Walls dominate the legal move list, so plenty are sampled
+        assert!(walls_seen > 25);
+    }
+}
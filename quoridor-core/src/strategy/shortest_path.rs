@@ -2,19 +2,28 @@
 
 use crate::game::Quoridor;
 use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
 use crate::strategy::Strategy;
 
 pub struct ShortestPathStrategy {
     base: QuoridorStrategy,
+    opponent_aware: bool, // Measure paths with the opponent pawn as an obstacle
 }
 
 impl ShortestPathStrategy {
     pub fn new(opening_name: &str, opening_moves: Vec<String>) -> Self {
         ShortestPathStrategy {
             base: QuoridorStrategy::new("ShortestPath", opening_name, opening_moves),
+            opponent_aware: false,
         }
     }
+
+    /// Measures distances with `distance_to_goal_with_opponent` instead of
+    /// `distance_to_goal`, so the opponent pawn counts as an obstacle.
+    pub fn with_opponent_aware_distance(mut self, enabled: bool) -> Self {
+        self.opponent_aware = enabled;
+        self
+    }
 }
 
 impl Strategy for ShortestPathStrategy {
@@ -22,6 +31,23 @@ impl Strategy for ShortestPathStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        format!("{} (opponent-aware paths: {})", self.name(), if self.opponent_aware { "on" } else { "off" })
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -31,38 +57,52 @@ impl Strategy for ShortestPathStrategy {
         // Always choose the legal pawn move that results in the shortest path to the goal.
         let legal_pawn_moves = game.get_legal_moves(game.active_player);
         if legal_pawn_moves.is_empty() {
-            // If no pawn moves, check for wall moves as fallback? Or just return None?
-            // Standard shortest path usually doesn't place walls. Let's return None if no pawn moves.
-             return None;
+            // Standard shortest path doesn't place walls, but with the pawn stuck a wall
+            // is the only way to keep playing
+             return QuoridorStrategy::last_resort_move(game);
         }
 
         let player = game.active_player;
-        let mut best_move: Option<String> = None;
-        let mut min_distance = usize::MAX;
-
-        for move_str in &legal_pawn_moves {
-             // Check for immediate win first
-             if game.win_check(move_str) {
-                  return Some(move_str.clone());
-             }
-
-            // Simulate the move by creating a temporary game state
-            let mut temp_game = game.clone();
-            if temp_game.move_pawn(move_str, false) { // Use internal move, skipping checks
-                let distance = temp_game.distance_to_goal(player);
-                if distance < min_distance {
-                    min_distance = distance;
-                    best_move = Some(move_str.clone());
-                }
-            }
-        }
+        let center = game.size / 2;
+
+        // Pick the move with the shortest remaining distance (a winning move has
+        // distance 0). Several steps often tie, e.g. sideways around a wall, so
+        // prefer the one nearer the center column, which leaves more routes open,
+        // then the alphabetically first, so the pick never rests on move-generation order.
+        let best_move = legal_pawn_moves
+            .iter()
+            .filter_map(|move_str| {
+                // Simulate the move by creating a temporary game state
+                let mut temp_game = game.clone();
+                if !temp_game.move_pawn(move_str, false) { return None; } // Use internal move, skipping checks
+                let distance = if self.opponent_aware {
+                    temp_game.distance_to_goal_with_opponent(player)
+                } else {
+                    temp_game.distance_to_goal(player)
+                };
+                let col = game.algebraic_to_coord(move_str).1;
+                Some((distance, col.abs_diff(center), move_str.clone()))
+            })
+            .min()
+            .map(|(_, _, move_str)| move_str);
 
-         // Fallback if no move improved distance (should usually not happen unless blocked)
-         if best_move.is_none() && !legal_pawn_moves.is_empty() {
-              best_move = Some(legal_pawn_moves[0].clone()); // Just take the first legal move
-         }
+         // Fallback if no move could be simulated (should not happen with legal moves)
+         best_move.or_else(|| legal_pawn_moves.first().cloned())
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        best_move
+    #[test]
+    fn test_ties_are_broken_deterministically() {
+        // d2h blocks e2-e3, so e2 (round the wall via f2) and f1 (straight up the f-file)
+        // both leave 8 steps; e2 is nearer the center column and must win every time.
+        let game = Quoridor::new(9, 10, Some("d2 / / e1 e9 / 9 10 / 1"));
+        for _ in 0..20 {
+            let mut strategy = ShortestPathStrategy::new("", Vec::new());
+            assert_eq!(strategy.choose_move(&game), Some("e2".to_string()));
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,59 +1,89 @@
 // --- File: quoridor-project/quoridor-core/src/strategy/simulated_annealing.rs ---
 
 use crate::game::Quoridor;
-use crate::player::Player;
-use crate::strategy::base::QuoridorStrategy;
-use crate::strategy::Strategy;
+use crate::strategy::base::{OpeningDeviation, QuoridorStrategy};
+use crate::strategy::evaluation::EvalWeights;
+use crate::strategy::{preset_weights, Strategy};
 use rand::prelude::*;
 use std::f64;
 
+/// Iterations per annealing loop at a time factor of 1.0.
+const BASE_ITERATIONS: usize = 500;
+
+/// How the acceptance temperature falls over an annealing loop. Temperatures
+/// start at 1.0 on the first iteration.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoolingSchedule {
+    /// Falls in equal steps towards 0 on the last iteration (the default).
+    Linear,
+    /// Multiplied by `rate` (between 0 and 1) every iteration.
+    Exponential { rate: f64 },
+}
+
+impl CoolingSchedule {
+    /// Temperature on iteration `step` (1-based) of `max_steps`.
+    fn temperature(self, step: usize, max_steps: usize) -> f64 {
+        match self {
+            CoolingSchedule::Linear => (max_steps - step + 1) as f64 / max_steps as f64,
+            CoolingSchedule::Exponential { rate } => rate.powi(step as i32 - 1),
+        }
+    }
+}
+
 pub struct SimulatedAnnealingStrategy {
     base: QuoridorStrategy,
-    // Parameters can be added here, e.g., iteration count, cooling schedule
-    // For simplicity, using fixed iterations based on paper's context
+    // Iteration caps, scaled from BASE_ITERATIONS by the time factor
     max_global_iterations: usize,
     max_local_iterations: usize,
+    cooling: CoolingSchedule,
+    weights: EvalWeights, // Evaluation weights (w2-w4 from the paper, w5 for walls in hand)
 }
 
 impl SimulatedAnnealingStrategy {
-     pub fn new(opening_name: &str, opening_moves: Vec<String>, _time_factor: f64) -> Self {
-          // The time_factor isn't directly used in the paper's SA logic description,
-          // but we could use it to scale iterations if desired.
-          // Paper implies a large number of iterations are run until a condition is met.
+     /// `time_factor` scales both annealing loops' iteration caps (500 each at
+     /// 1.0, at least 1) and is part of the name exactly as given, e.g.
+     /// "SimulatedAnnealing0.5" or "SimulatedAnnealing1.0", so entrants that
+     /// differ only in factor stay apart in tournament results.
+     pub fn new(opening_name: &str, opening_moves: Vec<String>, time_factor: f64) -> Self {
+          let iterations = ((BASE_ITERATIONS as f64 * time_factor).round() as usize).max(1);
           SimulatedAnnealingStrategy {
-               base: QuoridorStrategy::new("SimulatedAnnealing", opening_name, opening_moves), // Name doesn't include factor for now
-               max_global_iterations: 500, // Example: Limit iterations for performance
-               max_local_iterations: 500,  // Example: Limit iterations
+               base: QuoridorStrategy::new(&format!("SimulatedAnnealing{:?}", time_factor), opening_name, opening_moves),
+               max_global_iterations: iterations,
+               max_local_iterations: iterations,
+               cooling: CoolingSchedule::Linear,
+               weights: EvalWeights::default(),
           }
      }
 
-     /// Evaluation function based on the Mertens paper's Minimax heuristic (C3: f2+f3-f4).
-     /// Higher score is better for Player 1.
-     fn evaluate_position(&self, game: &Quoridor) -> f64 {
-         let p1_dist = game.distance_to_goal(Player::Player1) as f64;
-         let p2_dist = game.distance_to_goal(Player::Player2) as f64;
-         let f2_pos_diff = p2_dist - p1_dist; // P2 further = good for P1
-
-         let p1_moves_next = game.moves_to_next_row(Player::Player1) as f64;
-         let f3_p1_attack = if p1_moves_next == 0.0 { 100.0 } else { 1.0 / (p1_moves_next + 0.1) };
+     /// Sets the cooling schedule used by both annealing loops (default: linear).
+     pub fn with_cooling(mut self, schedule: CoolingSchedule) -> Self {
+          self.cooling = schedule;
+          self
+     }
 
-          let p2_moves_next = game.moves_to_next_row(Player::Player2) as f64;
-          let f4_p2_defense = p2_moves_next; // Higher means P2 is slower = good for P1
+     /// Replaces the evaluation weights (default: the paper's C3 weights).
+     pub fn with_eval_weights(mut self, weights: EvalWeights) -> Self {
+          self.weights = weights;
+          self
+     }
 
-         const W2: f64 = 0.6001;
-         const W3: f64 = 14.45;
-         const W4: f64 = 6.52;
+     /// Evaluates with a named heuristic preset, e.g. "SimulatedAnnealing1.0-C2"
+     /// (see `preset_weights`).
+     pub fn with_preset(mut self, preset: &str) -> Self {
+          self.weights = preset_weights(&mut self.base.name, preset);
+          self
+     }
 
-          // Score from P1's perspective
-          W2 * f2_pos_diff + W3 * f3_p1_attack - W4 * f4_p2_defense
+     /// Static evaluation from Player 1's perspective, shared with Minimax
+     /// (see `EvalWeights::evaluate` for the terms).
+     fn evaluate_position(&self, game: &Quoridor) -> f64 {
+         self.weights.evaluate(game)
      }
 
       /// Selects the opponent's best response (minimizing P1's score).
       fn select_opponent_best_move(&self, game: &Quoridor) -> Option<String> {
           let opponent = game.active_player; // Player whose turn it is in this state
-          let pawn_moves = game.get_legal_moves(opponent);
-          let wall_moves = game.get_legal_walls(opponent);
-          let all_moves: Vec<String> = pawn_moves.into_iter().chain(wall_moves.into_iter()).collect();
+          let all_moves = game.all_legal_moves(opponent);
 
           if all_moves.is_empty() { return None; }
 
@@ -62,11 +92,7 @@ impl SimulatedAnnealingStrategy {
 
           for move_str in all_moves {
                let mut next_game = game.clone();
-               let moved = if move_str.len() >= 3 {
-                    next_game.add_wall(&move_str, false, false)
-               } else {
-                    next_game.move_pawn(&move_str, false)
-               };
+               let moved = next_game.apply_move(&move_str, false);
                 if !moved { continue; }
 
                let score = self.evaluate_position(&next_game); // Evaluate state after opponent moves
@@ -86,6 +112,26 @@ impl Strategy for SimulatedAnnealingStrategy {
         self.base.name.clone()
     }
 
+    fn describe(&self) -> String {
+        format!(
+            "{} (iterations: {} global x {} local, cooling: {:?}, weights: {})",
+            self.name(), self.max_global_iterations, self.max_local_iterations, self.cooling, self.weights
+        )
+    }
+
+    fn reset(&mut self) {
+        self.base.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.base.opening_mode = mode;
+        true
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.base.deviation.clone()
+    }
+
     fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
         // Try opening move first
         if let Some(opening_move) = self.base.try_opening_move(game) {
@@ -101,16 +147,12 @@ impl Strategy for SimulatedAnnealingStrategy {
         let mut best_overall_move: Option<String> = None; // Best first move found
 
          // Pre-calculate legal moves for the current player
-         let player_pawn_moves = game.get_legal_moves(player);
-         let player_wall_moves = game.get_legal_walls(player);
-         let all_player_moves: Vec<String> = player_pawn_moves.iter().cloned()
-             .chain(player_wall_moves.iter().cloned())
-             .collect();
+         let all_player_moves = game.all_legal_moves(player);
 
          if all_player_moves.is_empty() { return None; } // No moves possible
 
-          // Check for immediate win
-          for move_str in &player_pawn_moves {
+          // Check for immediate win (win_check ignores wall moves)
+          for move_str in &all_player_moves {
               if game.win_check(move_str) {
                   return Some(move_str.clone());
               }
@@ -124,11 +166,7 @@ impl Strategy for SimulatedAnnealingStrategy {
 
               // 2. Simulate this move
               let mut game_after_first = game.clone();
-              let moved1 = if candidate_first_move.len() >= 3 {
-                   game_after_first.add_wall(&candidate_first_move, false, false)
-              } else {
-                   game_after_first.move_pawn(&candidate_first_move, false)
-              };
+              let moved1 = game_after_first.apply_move(&candidate_first_move, false);
                if !moved1 { continue; } // Should not happen with legal moves
 
 
@@ -139,11 +177,7 @@ impl Strategy for SimulatedAnnealingStrategy {
                      break;
                };
                let mut game_after_opponent = game_after_first.clone();
-               let moved2 = if opponent_best_response.len() >= 3 {
-                    game_after_opponent.add_wall(&opponent_best_response, false, false)
-               } else {
-                    game_after_opponent.move_pawn(&opponent_best_response, false)
-               };
+               let moved2 = game_after_opponent.apply_move(&opponent_best_response, false);
                 if !moved2 { continue; }
 
 
@@ -152,11 +186,7 @@ impl Strategy for SimulatedAnnealingStrategy {
 
               // 4. Local Annealing Loop (Choosing the second move for *us*)
                let mut best_second_move_found: Option<String> = None;
-               let player2_pawn_moves = game_after_opponent.get_legal_moves(player);
-               let player2_wall_moves = game_after_opponent.get_legal_walls(player);
-                let all_second_moves: Vec<String> = player2_pawn_moves.iter().cloned()
-                    .chain(player2_wall_moves.iter().cloned())
-                    .collect();
+               let all_second_moves = game_after_opponent.all_legal_moves(player);
 
                if all_second_moves.is_empty() { continue; } // Cannot respond
 
@@ -165,11 +195,7 @@ impl Strategy for SimulatedAnnealingStrategy {
 
                     // Simulate second move
                     let mut game_after_second = game_after_opponent.clone();
-                    let moved3 = if candidate_second_move.len() >= 3 {
-                         game_after_second.add_wall(&candidate_second_move, false, false)
-                    } else {
-                         game_after_second.move_pawn(&candidate_second_move, false)
-                    };
+                    let moved3 = game_after_second.apply_move(&candidate_second_move, false);
                      if !moved3 { continue; }
 
                     let score_after_second = self.evaluate_position(&game_after_second);
@@ -183,7 +209,7 @@ impl Strategy for SimulatedAnnealingStrategy {
                          break; // Found a locally better move
                     } else {
                          // Accept worse move with probability
-                         let temp_local = (self.max_local_iterations - time2 + 1) as f64 / self.max_local_iterations as f64; // Example cooling
+                         let temp_local = self.cooling.temperature(time2, self.max_local_iterations);
                           let acceptance_prob = (delta_e_local / temp_local).exp();
                          if rng.gen::<f64>() < acceptance_prob {
                               best_second_move_found = Some(candidate_second_move);
@@ -204,7 +230,7 @@ impl Strategy for SimulatedAnnealingStrategy {
                          break; // Found a globally better move
                     } else {
                          // Accept worse global move with probability
-                          let temp_global = (self.max_global_iterations - time1 + 1) as f64 / self.max_global_iterations as f64; // Example cooling
+                          let temp_global = self.cooling.temperature(time1, self.max_global_iterations);
                           let acceptance_prob = (delta_e_global / temp_global).exp();
                          if rng.gen::<f64>() < acceptance_prob {
                               best_overall_move = Some(candidate_first_move);
@@ -224,4 +250,58 @@ impl Strategy for SimulatedAnnealingStrategy {
              best_overall_move
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+
+    #[test]
+    fn test_time_factor_scales_iterations_and_name() {
+        let half = SimulatedAnnealingStrategy::new("", Vec::new(), 0.5);
+        let normal = SimulatedAnnealingStrategy::new("", Vec::new(), 1.0);
+        let longer = SimulatedAnnealingStrategy::new("", Vec::new(), 1.5);
+        assert_eq!((half.max_global_iterations, half.max_local_iterations), (250, 250));
+        assert_eq!((normal.max_global_iterations, normal.max_local_iterations), (500, 500));
+        assert_eq!((longer.max_global_iterations, longer.max_local_iterations), (750, 750));
+        assert_eq!(SimulatedAnnealingStrategy::new("", Vec::new(), 0.0).max_global_iterations, 1);
+
+        assert_eq!(half.name(), "SimulatedAnnealing0.5");
+        assert_eq!(longer.name(), "SimulatedAnnealing1.5");
+    }
+
+    #[test]
+    fn test_factors_give_distinct_names() {
+        let name = |factor: f64| SimulatedAnnealingStrategy::new("", Vec::new(), factor).name();
+        assert_eq!(name(1.0), "SimulatedAnnealing1.0");
+        assert_ne!(name(0.5), name(1.0));
+        assert_ne!(name(0.25), name(0.3)); // Not rounded together
+    }
+
+    #[test]
+    fn test_cooling_schedules() {
+        assert_eq!(CoolingSchedule::Linear.temperature(1, 4), 1.0);
+        assert_eq!(CoolingSchedule::Linear.temperature(4, 4), 0.25);
+        let exponential = CoolingSchedule::Exponential { rate: 0.5 };
+        assert_eq!(exponential.temperature(1, 4), 1.0);
+        assert_eq!(exponential.temperature(3, 4), 0.25);
+
+        // Either schedule still plays a legal move
+        let game = Quoridor::standard();
+        let mut strategy = SimulatedAnnealingStrategy::new("", Vec::new(), 0.1).with_cooling(exponential);
+        let chosen = strategy.choose_move(&game).expect("a move");
+        assert!(game.all_legal_moves(Player::Player1).contains(&chosen));
+    }
+
+    #[test]
+    fn test_evaluation_matches_minimax() {
+        use crate::strategy::MinimaxStrategy;
+        let weights = EvalWeights::c2().with_wall_weight(0.3).with_mobility_weight(0.2).with_pressure_weight(0.1);
+        let annealing = SimulatedAnnealingStrategy::new("", Vec::new(), 1.0).with_eval_weights(weights);
+        let minimax = MinimaxStrategy::new("", Vec::new(), 1).with_eval_weights(weights);
+        let game = Quoridor::new(9, 10, Some("c3 / e6 / e4 f7 / 9 9 / 1"));
+        assert_eq!(annealing.evaluate_position(&game), minimax.evaluate_state(&game));
+        assert_ne!(annealing.evaluate_position(&game), 0.0);
+    }
+}
@@ -0,0 +1,158 @@
+// --- File: quoridor-project/quoridor-core/src/strategy/time_limited.rs ---
+
+//! Strategy decorator that times every move against a budget.
+
+use crate::game::Quoridor;
+use crate::strategy::{ChooseResult, OpeningDeviation, Strategy};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+/// Wraps any strategy and measures how long each move takes, warning when a
+/// move exceeds the budget. The inner strategy's move is always returned
+/// unchanged; searches that should *stop* at a deadline still need their own
+/// time limit (e.g. `MinimaxStrategy::with_time_limit`).
+///
+/// On wasm32 there is no reliable timer, so moves are forwarded untimed.
+pub struct TimeLimited<S: Strategy> {
+    inner: S,
+    budget: Duration,
+    last_move_time: Option<Duration>, // Time taken by the most recent move
+    overruns: usize, // Moves that exceeded the budget
+}
+
+impl<S: Strategy> TimeLimited<S> {
+    pub fn new(inner: S, budget: Duration) -> Self {
+        TimeLimited {
+            inner,
+            budget,
+            last_move_time: None,
+            overruns: 0,
+        }
+    }
+
+    /// Time taken by the most recent move, if one has been timed.
+    pub fn last_move_time(&self) -> Option<Duration> {
+        self.last_move_time
+    }
+
+    /// Number of moves that took longer than the budget.
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// Returns the wrapped strategy.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Strategy> Strategy for TimeLimited<S> {
+    /// Keeps the inner name so tournament results are unaffected by the wrapper.
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn describe(&self) -> String {
+        format!("{} [move budget: {:.2?}]", self.inner.describe(), self.budget)
+    }
+
+    fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+        self.choose(game).into_move()
+    }
+
+    fn choose(&mut self, game: &Quoridor) -> ChooseResult {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let start = Instant::now();
+            let result = self.inner.choose(game);
+            let elapsed = start.elapsed();
+            self.last_move_time = Some(elapsed);
+            if elapsed > self.budget {
+                self.overruns += 1;
+                eprintln!(
+                    "Warning: {} took {:.2?} for a move (budget {:.2?})",
+                    self.inner.name(), elapsed, self.budget
+                );
+            }
+            result
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.inner.choose(game)
+        }
+    }
+
+    fn should_swap(&mut self, game: &Quoridor) -> bool {
+        self.inner.should_swap(game)
+    }
+
+    fn set_param(&mut self, name: &str, value: f64) -> bool {
+        self.inner.set_param(name, value)
+    }
+
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+
+    fn set_opening_mode(&mut self, mode: OpeningDeviation) -> bool {
+        self.inner.set_opening_mode(mode)
+    }
+
+    fn opening_deviation(&self) -> Option<(usize, String)> {
+        self.inner.opening_deviation()
+    }
+
+    fn save_state(&self) -> Option<Vec<u8>> {
+        self.inner.save_state()
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> bool {
+        self.inner.load_state(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::ShortestPathStrategy;
+    use std::thread;
+
+    /// Test strategy that sleeps before delegating to ShortestPath.
+    struct Sleeper(Duration, ShortestPathStrategy);
+
+    impl Strategy for Sleeper {
+        fn name(&self) -> String {
+            "Sleeper".to_string()
+        }
+
+        fn choose_move(&mut self, game: &Quoridor) -> Option<String> {
+            thread::sleep(self.0);
+            self.1.choose_move(game)
+        }
+    }
+
+    #[test]
+    fn test_forwards_move_and_records_timing() {
+        let game = Quoridor::standard();
+        let inner = Sleeper(Duration::from_millis(20), ShortestPathStrategy::new("", Vec::new()));
+        let mut wrapped = TimeLimited::new(inner, Duration::from_secs(5));
+
+        assert_eq!(wrapped.name(), "Sleeper");
+        assert_eq!(wrapped.last_move_time(), None);
+        assert_eq!(wrapped.choose_move(&game), Some("e2".to_string()));
+        assert!(wrapped.last_move_time().unwrap() >= Duration::from_millis(20));
+        assert_eq!(wrapped.overruns(), 0);
+    }
+
+    #[test]
+    fn test_counts_overruns() {
+        let game = Quoridor::standard();
+        let inner = Sleeper(Duration::from_millis(20), ShortestPathStrategy::new("", Vec::new()));
+        let mut wrapped = TimeLimited::new(inner, Duration::from_millis(1));
+
+        assert_eq!(wrapped.choose(&game), ChooseResult::Move("e2".to_string()));
+        assert_eq!(wrapped.overruns(), 1);
+    }
+}
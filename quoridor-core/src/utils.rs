@@ -4,12 +4,53 @@
 
 use crate::types::Coord; // Use the type alias from this crate
 
+/// What a move string denotes, as parsed by `classify_move`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveKind {
+    /// A pawn move to this square.
+    Pawn(Coord),
+    /// A wall at this reference square, with orientation 'h' or 'v'.
+    Wall(Coord, char),
+    /// Not a square on this board, with or without a wall suffix.
+    Invalid,
+}
+
+/// Parses a move in algebraic notation without panicking. Squares always end
+/// in a digit, so a trailing 'h' or 'v' marks a wall ("e3h", "e10v") and
+/// anything else must be a pawn square ("e3", "e10" on boards above 9x9).
+/// Squares off the `board_size` board are `MoveKind::Invalid`.
+pub fn classify_move(move_str: &str, board_size: usize) -> MoveKind {
+    let (square, orientation) = match move_str.chars().last() {
+        Some(suffix @ ('h' | 'v')) => (&move_str[..move_str.len() - 1], Some(suffix)),
+        _ => (move_str, None),
+    };
+    let Some(coord) = parse_square(square, board_size) else { return MoveKind::Invalid; };
+    match orientation {
+        Some(orientation) => MoveKind::Wall(coord, orientation),
+        None => MoveKind::Pawn(coord),
+    }
+}
+
+/// Fallible core of `algebraic_to_coord`: a file letter then a rank number,
+/// both on the board.
+fn parse_square(square: &str, board_size: usize) -> Option<Coord> {
+    let mut chars = square.chars();
+    let col_char = chars.next().filter(|c| c.is_ascii_lowercase())?;
+    let col = (col_char as u8 - b'a') as usize;
+    let row_str = chars.as_str();
+    if row_str.is_empty() || !row_str.bytes().all(|b| b.is_ascii_digit()) { return None; }
+    let row_num: usize = row_str.parse().ok()?;
+    if col >= board_size || row_num < 1 || row_num > board_size { return None; }
+    Some((board_size - row_num, col))
+}
+
 /// Converts algebraic notation (e.g., "e1", "a9") to board coordinates (row, col).
 /// Panics on invalid input.
 pub fn algebraic_to_coord(square: &str, board_size: usize) -> Coord {
-    // Handle potential wall notation passed erroneously
+    // Handle potential wall notation passed erroneously (only the suffix is dropped,
+    // so two-digit ranks like "e10h" keep their square)
      let pos_str = if square.len() > 2 && (square.ends_with('h') || square.ends_with('v')) {
-         &square[0..2]
+         &square[..square.len() - 1]
      } else {
          square
      };
@@ -115,6 +156,22 @@ mod tests {
           algebraic_to_coord("a", TEST_SIZE);
      }
 
+    #[test]
+    fn test_classify_move() {
+        assert_eq!(classify_move("e2", TEST_SIZE), MoveKind::Pawn((7, 4)));
+        assert_eq!(classify_move("e3h", TEST_SIZE), MoveKind::Wall((6, 4), 'h'));
+        assert_eq!(classify_move("a1v", TEST_SIZE), MoveKind::Wall((8, 0), 'v'));
+
+        // On 11x11, "e10" is a pawn square, not a wall
+        assert_eq!(classify_move("e10", 11), MoveKind::Pawn((1, 4)));
+        assert_eq!(classify_move("e10h", 11), MoveKind::Wall((1, 4), 'h'));
+        assert_eq!(algebraic_to_coord("e10h", 11), (1, 4));
+
+        for bad in ["", "e", "e0", "e10", "j5", "E5", "e5x", "e5hh", "5e"] {
+            assert_eq!(classify_move(bad, TEST_SIZE), MoveKind::Invalid, "{:?}", bad);
+        }
+    }
+
     #[test]
     fn test_abs_diff() {
         assert_eq!(abs_diff(5, 2), 3);
@@ -1,9 +1,10 @@
 // --- File: quoridor-project/quoridor-wasm/src/lib.rs ---
 
 // Import necessary items from the core library
-use quoridor_core::{Quoridor, Player, Strategy, Coord}; // Add more imports as needed
-use quoridor_core::strategy::{ self, RandomStrategy, ShortestPathStrategy, MCTSStrategy, MinimaxStrategy, DefensiveStrategy, AdaptiveStrategy, BalancedStrategy, MirrorStrategy, SimulatedAnnealingStrategy}; // Example strategy imports
+use quoridor_core::{Quoridor, Player, Strategy, Coord, GameRecord, ClassifiedMoves}; // Add more imports as needed
+use quoridor_core::strategy::{ self, RandomStrategy, ShortestPathStrategy, MCTSStrategy, MinimaxStrategy, DefensiveStrategy, GreedyBlockStrategy, AdaptiveStrategy, BalancedStrategy, MirrorStrategy, SimulatedAnnealingStrategy}; // Example strategy imports
 use quoridor_core::openings; // Import the openings module
+use quoridor_core::analysis; // Depth-by-depth evaluation
 use web_sys::js_sys;
 use std::panic;
 
@@ -45,9 +46,15 @@ pub struct QuoridorGame {
 // Methods exposed to JavaScript via wasm-bindgen
 #[wasm_bindgen]
 impl QuoridorGame {
+    /// An invalid size or wall count (see `Quoridor::check_config`) is logged and
+    /// replaced by the standard 9x9 board with 10 walls, rather than trapping the module.
     #[wasm_bindgen(constructor)]
     pub fn new(size: usize, walls: usize) -> Self {
         console_log!("Creating new QuoridorGame instance ({}x{} board, {} walls)", size, size, walls);
+        let (size, walls, error) = config_or_standard(size, walls);
+        if let Some(message) = error {
+            console_log!("Error: {}. Falling back to a standard 9x9 board.", message);
+        }
         let game = Quoridor::new(size, walls, None);
         Self {
             game_instance: game,
@@ -93,6 +100,11 @@ impl QuoridorGame {
         if !opening_moves.is_empty() {
              console_log!("Loaded {} opening moves for Player {}", opening_moves.len(), player_number);
         }
+        // A line with more walls than the player has breaks off once they run out
+        let opening_walls = openings::opening_wall_count(opening_name, player, self.game_instance.size);
+        if opening_walls > self.game_instance.walls {
+            console_log!("Warning: opening '{}' places {} walls but Player {} only has {}", opening_name, opening_walls, player_number, self.game_instance.walls);
+        }
 
         // Create the strategy based on the name
         // This needs to match the strategy implementations in quoridor-core
@@ -100,7 +112,8 @@ impl QuoridorGame {
             "Human" => None, // Represent Human player with None
             "Random" => Some(Box::new(RandomStrategy::new(opening_name, opening_moves))),
             "ShortestPath" => Some(Box::new(ShortestPathStrategy::new(opening_name, opening_moves))),
-            "Defensive" => Some(Box::new(DefensiveStrategy::new(opening_name, opening_moves, 0.7))), // Example param
+            "Defensive" => Some(Box::new(DefensiveStrategy::new(opening_name, opening_moves, 0.7, 0.0))), // Example param
+            "GreedyBlock" => Some(Box::new(GreedyBlockStrategy::new(opening_name, opening_moves))),
             "Balanced" => Some(Box::new(BalancedStrategy::new(opening_name, opening_moves, 0.5))), // Example param
             "Adaptive" => Some(Box::new(AdaptiveStrategy::new(opening_name, opening_moves))),
             "Mirror" => Some(Box::new(MirrorStrategy::new(opening_name, opening_moves))),
@@ -155,6 +168,27 @@ impl QuoridorGame {
         true
     }
 
+    /// Changes a tuning parameter of a player's AI strategy without rebuilding it.
+    /// player_number: 1 or 2
+    /// param: "exploration" (MCTS), "wall_preference" (Defensive) or "defense_weight" (Balanced)
+    /// Returns false if the player has no AI strategy or it has no such parameter.
+    #[wasm_bindgen(js_name = tuneStrategy)]
+    pub fn tune_strategy(&mut self, player_number: usize, param: &str, value: f64) -> bool {
+        let strategy = match player_number {
+            1 => &mut self.player1_strategy,
+            2 => &mut self.player2_strategy,
+            _ => {
+                console_log!("Error: Invalid player number '{}'", player_number);
+                return false;
+            }
+        };
+        let tuned = strategy.as_mut().is_some_and(|s| s.set_param(param, value));
+        if !tuned {
+            console_log!("Player {} strategy has no parameter '{}'", player_number, param);
+        }
+        tuned
+    }
+
     /// Gets the AI's chosen move for the current active player.
     /// Returns the move as a string (e.g., "e2", "a3h") or an empty string if no AI move is applicable.
      pub fn get_ai_move(&mut self) -> String {
@@ -168,11 +202,9 @@ impl QuoridorGame {
 
         if let Some(strategy) = strategy_option {
             console_log!("Using strategy: {}", strategy.name());
-            // Clone the game state to pass to the strategy
-            // This might be inefficient for complex strategies; consider passing a reference if possible,
-            // but mutable access for strategy state (like opening move counters) complicates this.
-            let current_game_state = self.game_instance.clone();
-            match strategy.choose_move(&current_game_state) {
+            // The strategy is borrowed mutably (opening counters, search state) and the
+            // game only shared; they are separate fields, so no copy of the game is needed
+            match strategy.choose_move(&self.game_instance) {
                 Some(move_str) => {
                     console_log!("AI chose move: {}", move_str);
                     move_str
@@ -189,18 +221,49 @@ impl QuoridorGame {
     }
 
 
+    /// Plays AI against AI from the current position for at most `max_moves`
+    /// plies, without a JS round-trip per move, and leaves the board at the end.
+    /// Returns a JSON transcript:
+    /// { "moves": ["e2", ...], "winner": 1 | 2 | null, "end": "win" | "maxMoves" | "noMove" | "illegalMove" },
+    /// or { "error": "..." } if either player has no AI strategy set.
+    #[wasm_bindgen(js_name = playAIGame)]
+    pub fn play_ai_game(&mut self, max_moves: usize) -> String {
+        if self.player1_strategy.is_none() || self.player2_strategy.is_none() {
+            return r#"{"error": "Both players need an AI strategy"}"#.to_string();
+        }
+
+        let mut moves: Vec<String> = Vec::new();
+        let end = loop {
+            if self.game_instance.winner().is_some() { break "win"; }
+            if moves.len() >= max_moves { break "maxMoves"; }
+
+            let strategy = match self.game_instance.active_player {
+                Player::Player1 => &mut self.player1_strategy,
+                Player::Player2 => &mut self.player2_strategy,
+            };
+            let Some(move_str) = strategy.as_mut().and_then(|s| s.choose_move(&self.game_instance)) else { break "noMove"; };
+            if !self.game_instance.apply_move(&move_str, true) {
+                console_log!("AI chose illegal move {} in self-play", move_str);
+                break "illegalMove";
+            }
+            moves.push(move_str);
+        };
+
+        let winner = match self.game_instance.winner() {
+            Some(Player::Player1) => "1",
+            Some(Player::Player2) => "2",
+            None => "null",
+        };
+        format!(r#"{{"moves": {:?}, "winner": {}, "end": {:?}}}"#, moves, winner, end)
+    }
+
     /// Attempts to make a move (pawn or wall) based on algebraic notation.
     /// move_str: The move in algebraic notation (e.g., "e2", "a3h", "b4v").
     /// Returns true if the move was successful, false otherwise.
     pub fn make_move(&mut self, move_str: &str) -> bool {
         console_log!("Attempting to make move: {}", move_str);
-        let result = if move_str.len() >= 3 && (move_str.ends_with('h') || move_str.ends_with('v')) {
-            // It's a wall move
-            self.game_instance.add_wall(move_str, false, true) // check=true
-        } else {
-            // It's a pawn move
-            self.game_instance.move_pawn(move_str, true) // check=true
-        };
+        // Wall or pawn move by notation (see utils::classify_move); malformed input is rejected
+        let result = self.game_instance.apply_move(move_str, true); // check=true
 
         if result {
             console_log!("Move successful: {}", move_str);
@@ -213,6 +276,25 @@ impl QuoridorGame {
         result
     }
 
+    /// Replays a game from the current position, e.g. to restore it without
+    /// serializing the full state. moves_json: a JSON array of move strings
+    /// (e.g. `["e2", "e8", "e3h"]`). The whole list is checked first with
+    /// `Quoridor::validate_sequence`, so it is applied only if every move is legal.
+    /// Returns -1 on success, otherwise the index of the first illegal move with
+    /// the game left unchanged (0 if moves_json is not an array of strings).
+    #[wasm_bindgen(js_name = applyMoves)]
+    pub fn apply_moves(&mut self, moves_json: &str) -> i32 {
+        let Some(moves) = parse_move_list(moves_json) else {
+            console_log!("Error: applyMoves expects a JSON array of move strings, got {}", moves_json);
+            return 0;
+        };
+        let result = apply_move_list(&mut self.game_instance, &moves);
+        if result >= 0 {
+            console_log!("Replay stopped at move {}: {}", result, moves[result as usize]);
+        }
+        result
+    }
+
     /// Gets the list of legal pawn moves for the active player.
     /// Returns a JS array of strings.
     #[wasm_bindgen(js_name = getLegalMoves)]
@@ -222,6 +304,15 @@ impl QuoridorGame {
         JsValue::from(moves.into_iter().map(JsValue::from).collect::<js_sys::Array>())
     }
 
+    /// Gets the active player's legal pawn destinations as a packed bit grid,
+    /// so renders don't allocate a string array. Square (row, col), row 0 being
+    /// the top row, is bit `row * size + col`: byte `index >> 3`, bit `index & 7`.
+    /// Returns a Uint8Array of ceil(size * size / 8) bytes.
+    #[wasm_bindgen(js_name = getLegalMovesMask)]
+    pub fn get_legal_moves_mask(&self) -> Vec<u8> {
+        self.game_instance.legal_moves_mask(self.game_instance.active_player)
+    }
+
     /// Gets the list of legal wall placements for the active player.
     /// Returns a JS array of strings (e.g., ["a3h", "b4v", ...]).
     #[wasm_bindgen(js_name = getLegalWalls)]
@@ -236,39 +327,131 @@ impl QuoridorGame {
          JsValue::from(walls.into_iter().map(JsValue::from).collect::<js_sys::Array>())
      }
 
+    /// Gets the active player's legal moves grouped by kind, as a JSON string:
+    /// { "forward": [...], "sideways": [...], "backward": [...], "jumps": [...],
+    ///   "horizontalWalls": [...], "verticalWalls": [...] }.
+    #[wasm_bindgen(js_name = getClassifiedMoves)]
+    pub fn get_classified_moves(&self) -> String {
+        classified_moves_json(&self.game_instance.classified_legal_moves(self.game_instance.active_player))
+    }
+
+    /// Gets the squares about equally far from both players' goals (distances
+    /// differing by at most `threshold`), for shading the contested band.
+    /// Returns a JS array of algebraic squares (e.g., ["a5", "b5", ...]).
+    #[wasm_bindgen(js_name = getContestedSquares)]
+    pub fn get_contested_squares(&self, threshold: usize) -> JsValue {
+        let game = &self.game_instance;
+        let squares = game.contested_squares(threshold).into_iter().map(|coord| game.coord_to_algebraic(coord));
+        JsValue::from(squares.map(JsValue::from).collect::<js_sys::Array>())
+    }
+
+    /// Gets the squares the active player's pawn can reach within `n` steps
+    /// (walls respected, opponent pawn ignored), for move highlighting.
+    /// Returns a JS array of algebraic squares (e.g., ["e1", "e2", ...]).
+    #[wasm_bindgen(js_name = reachableWithin)]
+    pub fn reachable_within(&self, n: usize) -> JsValue {
+        let squares = reachable_squares(&self.game_instance, n);
+        JsValue::from(squares.into_iter().map(JsValue::from).collect::<js_sys::Array>())
+    }
+
+
+    /// Gets a cheap advantage score for an evaluation bar, roughly in [-1, 1]
+    /// (positive favours Player 1, negative favours Player 2).
+    #[wasm_bindgen(js_name = getAdvantage)]
+    pub fn get_advantage(&self) -> f64 {
+        self.game_instance.advantage()
+    }
+
+    /// Gets a "depth vs eval" curve for the side to move as a JSON string, e.g.
+    /// [{"depth": 1, "score": 1.8, "forced": 0, "bestMove": "c4"}, ...].
+    /// Scores are from the side to move's perspective; a forced win or loss has
+    /// `score: null` and `forced: 1` or `-1`. Searches with default Minimax settings.
+    /// Depths above `MAX_ANALYSIS_DEPTH` are skipped with an error in the console.
+    #[wasm_bindgen(js_name = evaluateAtDepths)]
+    pub fn evaluate_at_depths(&self, depths: Vec<u32>) -> String {
+        let (depths, error) = analysis_depths(&depths);
+        if let Some(message) = error {
+            console_log!("Error: {}", message);
+        }
+        depth_curve_json(&self.game_instance, &depths)
+    }
+
+    /// Gets the line the engine expects from here, e.g. ["e2", "e8", "e3h"], searched
+    /// with default Minimax settings to `depth` plies. Returns an empty JS array
+    /// once the game is over. The game itself is not changed.
+    #[wasm_bindgen(js_name = getPrincipalVariation)]
+    pub fn get_pv(&mut self, depth: usize) -> JsValue {
+        let line = if self.game_instance.winner().is_some() {
+            Vec::new()
+        } else {
+            let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+            analysis::principal_variation(&self.game_instance, &strategy, depth)
+        };
+        JsValue::from(line.into_iter().map(JsValue::from).collect::<js_sys::Array>())
+    }
+
+    /// Gets every move played so far, oldest first.
+    /// Returns a JS array of strings (e.g., ["e2", "e8", "e3h"]).
+    #[wasm_bindgen(js_name = getMoveHistory)]
+    pub fn get_move_history(&self) -> JsValue {
+        JsValue::from(self.game_instance.move_history().iter().map(|m| JsValue::from(m.as_str())).collect::<js_sys::Array>())
+    }
 
     /// Gets the current game state as a JSON string.
     /// Suitable for sending to the frontend to render the board.
     #[wasm_bindgen(js_name = getGameState)]
     pub fn get_game_state(&self) -> String {
-        // Use serde_json if more complex state is needed. For now, manual string building.
-        let p1 = self.game_instance.pawn_positions[&Player::Player1];
-        let p2 = self.game_instance.pawn_positions[&Player::Player2];
-
-        // Convert wall coordinates to algebraic notation strings
-        let h_walls_alg: Vec<String> = self.game_instance.hwall_positions.iter()
-            .map(|&pos| self.game_instance.coord_to_algebraic(pos))
-            .collect();
-        let v_walls_alg: Vec<String> = self.game_instance.vwall_positions.iter()
-            .map(|&pos| self.game_instance.coord_to_algebraic(pos))
-            .collect();
-
-        // Use format! macro with proper JSON syntax, escaping strings
-        format!(
-            r#"{{"size": {}, "player1": {{"row": {}, "col": {}}}, "player2": {{"row": {}, "col": {}}}, "player1Walls": {}, "player2Walls": {}, "hWalls": {:?}, "vWalls": {:?}, "activePlayer": {}, "lastMove": {:?}, "currentStateString": {:?}}}"#,
-            self.game_instance.size,
-            p1.0, p1.1,
-            p2.0, p2.1,
-            self.game_instance.walls_available[&Player::Player1],
-            self.game_instance.walls_available[&Player::Player2],
-            h_walls_alg, // Already Vec<String>, no extra quotes needed by {:?}
-            v_walls_alg, // Already Vec<String>
-            if self.game_instance.active_player == Player::Player1 { 1 } else { 2 },
-            self.game_instance.last_move,
-            self.game_instance.state_string
-        )
+        game_state_json(&self.game_instance)
+    }
+
+
+    /// Gets every placed wall as a typed object, e.g. [{ square: "e3", orientation: "h" }, ...].
+    /// Horizontal walls come first, each group sorted by square. Saves the JS side
+    /// from suffix-parsing the string arrays in `getGameState`.
+    #[wasm_bindgen(js_name = getWallsTyped)]
+    pub fn get_walls_typed(&self) -> JsValue {
+        let array = js_sys::Array::new();
+        for (square, orientation) in typed_walls(&self.game_instance) {
+            let wall = js_sys::Object::new();
+            // Setting plain properties on a fresh object cannot fail
+            let _ = js_sys::Reflect::set(&wall, &JsValue::from("square"), &JsValue::from(square));
+            let _ = js_sys::Reflect::set(&wall, &JsValue::from("orientation"), &JsValue::from(orientation.to_string()));
+            array.push(&wall);
+        }
+        JsValue::from(array)
+    }
+
+    /// Gets the active player's `k` most obstructive walls as a teaching aid,
+    /// e.g. [{ wall: "a4h", distance: 8 }, ...], where `distance` is the opponent's
+    /// shortest path after that wall is placed. Longest detour first.
+    #[wasm_bindgen(js_name = getBlockingWalls)]
+    pub fn get_blocking_walls(&self, k: usize) -> JsValue {
+        let array = js_sys::Array::new();
+        let player = self.game_instance.active_player;
+        for (wall_move, distance) in self.game_instance.find_maximally_blocking_walls(player, k) {
+            let entry = js_sys::Object::new();
+            let _ = js_sys::Reflect::set(&entry, &JsValue::from("wall"), &JsValue::from(wall_move));
+            let _ = js_sys::Reflect::set(&entry, &JsValue::from("distance"), &JsValue::from(distance as u32));
+            array.push(&entry);
+        }
+        JsValue::from(array)
     }
 
+    /// Gets every square's distance to `player_number`'s goal (1 or 2) for shading
+    /// the board, as { size, distances } where `distances` is row-major from the
+    /// top-left square (a9 on 9x9) and `null` marks squares with no path to goal.
+    #[wasm_bindgen(js_name = getDistanceField)]
+    pub fn get_distance_field(&self, player_number: usize) -> JsValue {
+        let player = if player_number == 2 { Player::Player2 } else { Player::Player1 };
+        let distances = js_sys::Array::new();
+        for dist in self.game_instance.distance_field(player).into_iter().flatten() {
+            distances.push(&dist.map_or(JsValue::NULL, |d| JsValue::from(d as u32)));
+        }
+        let field = js_sys::Object::new();
+        let _ = js_sys::Reflect::set(&field, &JsValue::from("size"), &JsValue::from(self.game_instance.size as u32));
+        let _ = js_sys::Reflect::set(&field, &JsValue::from("distances"), &distances);
+        JsValue::from(field)
+    }
 
     /// Checks if the given pawn move would result in a win for the currently active player.
     /// move_str: The pawn move in algebraic notation (e.g., "e1").
@@ -291,4 +474,267 @@ impl QuoridorGame {
              Player::Player2 => 2,
          }
      }
-}
\ No newline at end of file
+}
+
+/// Steps through a recorded game for a review UI. Positions are rebuilt by
+/// replaying the move list, so jumping to any ply works in either direction.
+#[wasm_bindgen]
+pub struct GameReview {
+    record: GameRecord,
+    current_ply: usize,
+}
+
+#[wasm_bindgen]
+impl GameReview {
+    /// moves: the game's moves in order from the standard start (e.g. ["e2", "e8", "e3h"]).
+    /// An invalid size or wall count falls back to 9x9 with 10 walls, as in `QuoridorGame`.
+    /// Throws, naming the move, if any move is illegal where it is played, rather
+    /// than showing a game cut short at that point.
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: usize, walls: usize, moves: Vec<String>) -> Result<GameReview, JsValue> {
+        let (size, walls, error) = config_or_standard(size, walls);
+        if let Some(message) = error {
+            console_log!("Error: {}. Falling back to a standard 9x9 board.", message);
+        }
+        match review_record(size, walls, &moves) {
+            Ok(record) => Ok(Self { record, current_ply: 0 }),
+            Err((index, move_str)) => {
+                let message = format!("Move {} ({}) is illegal in the game being reviewed", index + 1, move_str);
+                console_log!("Error: {}", message);
+                Err(JsValue::from_str(&message))
+            }
+        }
+    }
+
+    /// Number of plies in the game.
+    pub fn length(&self) -> usize {
+        self.record.len()
+    }
+
+    /// The ply last jumped to (0 = initial position).
+    #[wasm_bindgen(js_name = currentPly)]
+    pub fn current_ply(&self) -> usize {
+        self.current_ply
+    }
+
+    /// Jumps to the position after `n` plies (clamped to the game length) and
+    /// returns it as JSON in the same format as `QuoridorGame.getGameState`.
+    #[wasm_bindgen(js_name = gotoPly)]
+    pub fn goto_ply(&mut self, n: usize) -> String {
+        self.current_ply = n.min(self.record.len());
+        game_state_json(&self.record.position_at(self.current_ply))
+    }
+}
+
+/// Board state as the JSON object the frontend renders from.
+fn game_state_json(game: &Quoridor) -> String {
+    // Use serde_json if more complex state is needed. For now, manual string building.
+    let p1 = game.pawn_positions[&Player::Player1];
+    let p2 = game.pawn_positions[&Player::Player2];
+
+    // Convert wall coordinates to algebraic notation strings, in getWallsTyped order
+    let walls = typed_walls(game);
+    let squares_of = |wanted: char| -> Vec<String> {
+        walls.iter().filter(|(_, orientation)| *orientation == wanted).map(|(square, _)| square.clone()).collect()
+    };
+    let h_walls_alg = squares_of('h');
+    let v_walls_alg = squares_of('v');
+
+    // Use format! macro with proper JSON syntax, escaping strings
+    format!(
+        r#"{{"size": {}, "player1": {{"row": {}, "col": {}}}, "player2": {{"row": {}, "col": {}}}, "player1Walls": {}, "player2Walls": {}, "hWalls": {:?}, "vWalls": {:?}, "hWallCount": {}, "vWallCount": {}, "activePlayer": {}, "lastMove": {:?}, "currentStateString": {:?}}}"#,
+        game.size,
+        p1.0, p1.1,
+        p2.0, p2.1,
+        game.walls_available[&Player::Player1],
+        game.walls_available[&Player::Player2],
+        h_walls_alg, // Already Vec<String>, no extra quotes needed by {:?}
+        v_walls_alg, // Already Vec<String>
+        game.hwall_positions.len(),
+        game.vwall_positions.len(),
+        if game.active_player == Player::Player1 { 1 } else { 2 },
+        game.last_move,
+        game.state_string
+    )
+}
+
+/// Parses a JSON array of strings such as `["e2", "e3h"]`. Move strings need
+/// no escapes, so a string containing a backslash is rejected like any other
+/// malformed input.
+fn parse_move_list(json: &str) -> Option<Vec<String>> {
+    let inner = json.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    if inner.is_empty() {
+        return Some(Vec::new());
+    }
+    inner
+        .split(',')
+        .map(|item| {
+            let move_str = item.trim().strip_prefix('"')?.strip_suffix('"')?;
+            (!move_str.contains(['"', '\\'])).then(|| move_str.to_string())
+        })
+        .collect()
+}
+
+/// Deepest search `evaluateAtDepths` will run. Each extra ply multiplies the work
+/// by the number of legal moves, so deeper requests would hang the page.
+const MAX_ANALYSIS_DEPTH: usize = 4;
+
+/// The requested depths that are at most `MAX_ANALYSIS_DEPTH`, in the order given,
+/// along with a message naming any that were refused.
+fn analysis_depths(depths: &[u32]) -> (Vec<usize>, Option<String>) {
+    let (accepted, refused): (Vec<usize>, Vec<usize>) = depths
+        .iter()
+        .map(|&depth| depth as usize)
+        .partition(|&depth| depth <= MAX_ANALYSIS_DEPTH);
+    let error = (!refused.is_empty())
+        .then(|| format!("depths {:?} are above the maximum of {}", refused, MAX_ANALYSIS_DEPTH));
+    (accepted, error)
+}
+
+/// Returns `size` and `walls` if `Quoridor::check_config` accepts them, or the
+/// standard 9x9 board with 10 walls along with the reason they were refused.
+fn config_or_standard(size: usize, walls: usize) -> (usize, usize, Option<String>) {
+    match Quoridor::check_config(size, walls) {
+        Ok(()) => (size, walls, None),
+        Err(message) => (9, 10, Some(message)),
+    }
+}
+
+/// The record of `moves` played from the standard start on a `size` board,
+/// or the index and text of the first one that is illegal where it is played
+/// (see `Quoridor::validate_sequence`).
+fn review_record(size: usize, walls: usize, moves: &[String]) -> Result<GameRecord, (usize, String)> {
+    let record = GameRecord::new(size, walls, None);
+    record.initial_position().validate_sequence(moves)?;
+    Ok(GameRecord { moves: moves.to_vec(), ..record })
+}
+
+/// Plays `moves` on `game` if the whole sequence is legal. Returns -1 on
+/// success, or the index of the first illegal move with `game` untouched.
+fn apply_move_list(game: &mut Quoridor, moves: &[String]) -> i32 {
+    if let Err((index, _)) = game.validate_sequence(moves) {
+        return index as i32;
+    }
+    for move_str in moves {
+        game.apply_move(move_str, false);
+    }
+    -1
+}
+
+/// JSON object with one array of algebraic moves per `ClassifiedMoves` bucket.
+fn classified_moves_json(moves: &ClassifiedMoves) -> String {
+    format!(
+        r#"{{"forward": {:?}, "sideways": {:?}, "backward": {:?}, "jumps": {:?}, "horizontalWalls": {:?}, "verticalWalls": {:?}}}"#,
+        moves.forward, moves.sideways, moves.backward, moves.jumps, moves.horizontal_walls, moves.vertical_walls
+    )
+}
+
+/// Squares reachable by the active player within `n` steps, in algebraic notation.
+/// Sorted so the frontend gets a stable order.
+fn reachable_squares(game: &Quoridor, n: usize) -> Vec<String> {
+    let mut squares: Vec<String> = game.reachable_within(game.active_player, n)
+        .into_iter()
+        .map(|coord| game.coord_to_algebraic(coord))
+        .collect();
+    squares.sort();
+    squares
+}
+
+/// Placed walls as (algebraic square, orientation) pairs: horizontal walls
+/// first, each group sorted by square so the frontend gets a stable order.
+fn typed_walls(game: &Quoridor) -> Vec<(String, char)> {
+    let mut walls: Vec<(String, char)> = game.placed_walls()
+        .into_iter()
+        .map(|(coord, orientation)| (game.coord_to_algebraic(coord), orientation))
+        .collect();
+    // `placed_walls` lists each group from the top row down; keep the square order
+    walls.sort_by(|(a, a_orientation), (b, b_orientation)| (a_orientation, a).cmp(&(b_orientation, b)));
+    walls
+}
+
+/// JSON array of (depth, score, best move) entries from `analysis::evaluate_at_depths`.
+fn depth_curve_json(game: &Quoridor, depths: &[usize]) -> String {
+    let strategy = MinimaxStrategy::new("", Vec::new(), 1);
+    let entries: Vec<String> = analysis::evaluate_at_depths(game, &strategy, depths)
+        .into_iter()
+        .map(|(depth, score, best_move)| {
+            // JSON has no infinities, so forced results are flagged separately
+            let (score_json, forced) = if score.is_finite() {
+                (score.to_string(), 0)
+            } else {
+                ("null".to_string(), if score > 0.0 { 1 } else { -1 })
+            };
+            format!(r#"{{"depth": {}, "score": {}, "forced": {}, "bestMove": {:?}}}"#, depth, score_json, forced, best_move)
+        })
+        .collect();
+    format!("[{}]", entries.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_record_rejects_an_illegal_move_mid_game() {
+        let moves = parse_move_list(r#"["e2", "e8", "e2h", "d8", "e3", "d7"]"#).unwrap();
+        // e3 is walled off from e2 by e2h, so the review stops there with an error
+        assert_eq!(review_record(9, 10, &moves), Err((4, "e3".to_string())));
+
+        let legal = &moves[..4];
+        let record = review_record(9, 10, legal).expect("a legal game");
+        assert_eq!(record.moves, legal);
+        assert_eq!(record.position_at(record.len()).last_move, "d8");
+    }
+
+    #[test]
+    fn test_analysis_depths_refuses_deep_searches() {
+        assert_eq!(analysis_depths(&[1, 2, 3]), (vec![1, 2, 3], None));
+        let (depths, error) = analysis_depths(&[2, 40, 1, u32::MAX]);
+        assert_eq!(depths, [2, 1]);
+        assert!(error.unwrap().contains("[40, 4294967295]"));
+    }
+
+    #[test]
+    fn test_typed_walls_sorted_by_square() {
+        let mut game = Quoridor::standard();
+        for wall in ["c5h", "e3v", "a3h", "b7v"] {
+            assert!(game.add_wall(wall, false, true));
+        }
+        // a3h sits below c5h but sorts first by square
+        let walls = typed_walls(&game);
+        let expected = [("a3", 'h'), ("c5", 'h'), ("b7", 'v'), ("e3", 'v')];
+        assert_eq!(walls, expected.map(|(square, orientation)| (square.to_string(), orientation)));
+    }
+
+    #[test]
+    fn test_config_or_standard_falls_back_on_invalid_boards() {
+        assert_eq!(config_or_standard(5, 3), (5, 3, None));
+        for (size, walls) in [(8, 10), (1, 0), (5, 9)] {
+            let (size, walls, error) = config_or_standard(size, walls);
+            assert_eq!((size, walls), (9, 10));
+            assert!(error.is_some());
+        }
+    }
+
+    #[test]
+    fn test_apply_move_list_replays_or_reports_first_illegal_move() {
+        let mut game = Quoridor::standard();
+        let moves = parse_move_list(r#"["e2", "e8", "e2h"]"#).unwrap();
+        assert_eq!(apply_move_list(&mut game, &moves), -1);
+        assert_eq!(game.move_history(), moves); // What getMoveHistory returns
+        assert_eq!(game.pawn_positions[&Player::Player1], game.algebraic_to_coord("e2"));
+        assert_eq!(game.last_move, "e2h");
+        assert_eq!(game.active_player, Player::Player2);
+
+        // e3 is walled off from e2 by e2h: nothing after the replayed prefix is applied
+        let state = game.state_string.clone();
+        let moves = parse_move_list(r#"[ "d8", "e3" ,"e7" ]"#).unwrap();
+        assert_eq!(apply_move_list(&mut game, &moves), 1);
+        assert_eq!(game.state_string, state);
+        assert_eq!(game.move_history(), ["e2", "e8", "e2h"]);
+
+        assert_eq!(parse_move_list("[]"), Some(Vec::new()));
+        assert_eq!(parse_move_list(r#"["e2", e8]"#), None);
+        assert_eq!(parse_move_list(r#"{"moves": ["e2"]}"#), None);
+    }
+}